@@ -0,0 +1,74 @@
+# Port of the reference HSLuv implementation (https://github.com/hsluv/hsluv, revision 4 of
+# the algorithm), used to write hsluv_snapshot.json in the same format as the official
+# snapshot-rev4.json. Run it from this directory with `python3 hsluv_snapshot.py`.
+import math, json, os
+m = [[3.240969941904521, -1.537383177570093, -0.498610760293],
+     [-0.96924363628087, 1.87596750150772, 0.041555057407175],
+     [0.055630079696993, -0.20397695888897, 1.056971514242878]]
+m_inv = [[0.41239079926595, 0.35758433938387, 0.18048078840183],
+         [0.21263900587151, 0.71516867876775, 0.072192315360733],
+         [0.019330818715591, 0.11919477979462, 0.95053215224966]]
+ref_y = 1.0; ref_u = 0.19783000664283; ref_v = 0.46831999493879
+kappa = 903.2962962; epsilon = 0.0088564516
+def get_bounds(l):
+    result = []
+    sub1 = ((l + 16) ** 3) / 1560896
+    sub2 = sub1 if sub1 > epsilon else l / kappa
+    for c in range(3):
+        m1, m2, m3 = m[c]
+        for t in range(2):
+            top1 = (284517 * m1 - 94839 * m3) * sub2
+            top2 = (838422 * m3 + 769860 * m2 + 731718 * m1) * l * sub2 - 769860 * t * l
+            bottom = (632260 * m3 - 126452 * m2) * sub2 + 126452 * t
+            result.append((top1 / bottom, top2 / bottom))
+    return result
+def max_safe_chroma_for_l(l):
+    return min(abs(i) / math.sqrt(s * s + 1) for s, i in get_bounds(l))
+def max_chroma_for_lh(l, h):
+    hrad = h / 360 * math.pi * 2
+    mn = float('inf')
+    for s, i in get_bounds(l):
+        length = i / (math.sin(hrad) - s * math.cos(hrad))
+        if length >= 0: mn = min(mn, length)
+    return mn
+def dot(a, b): return sum(x * y for x, y in zip(a, b))
+def to_linear(c): return ((c + 0.055) / 1.055) ** 2.4 if c > 0.04045 else c / 12.92
+def rgb_to_xyz(rgb): return [dot(r, [to_linear(c) for c in rgb]) for r in m_inv]
+def y_to_l(y): return y / ref_y * kappa if y <= epsilon else 116 * (y / ref_y) ** (1 / 3) - 16
+def xyz_to_luv(t):
+    x, y, z = t
+    d = x + 15 * y + 3 * z
+    vu, vv = (0, 0) if d == 0 else (4 * x / d, 9 * y / d)
+    l = y_to_l(y)
+    if l == 0: return [0.0, 0.0, 0.0]
+    return [l, 13 * l * (vu - ref_u), 13 * l * (vv - ref_v)]
+def luv_to_lch(t):
+    l, u, v = t
+    c = math.sqrt(u * u + v * v)
+    if c < 1e-8: h = 0.0
+    else:
+        h = math.degrees(math.atan2(v, u))
+        if h < 0: h += 360
+    return [l, c, h]
+def lch_to_hsluv(t):
+    l, c, h = t
+    if l > 99.9999999: return [h, 0.0, 100.0]
+    if l < 1e-8: return [h, 0.0, 0.0]
+    return [h, c / max_chroma_for_lh(l, h) * 100, l]
+def lch_to_hpluv(t):
+    l, c, h = t
+    if l > 99.9999999: return [h, 0.0, 100.0]
+    if l < 1e-8: return [h, 0.0, 0.0]
+    return [h, c / max_safe_chroma_for_l(l) * 100, l]
+out = {}
+steps = [0x00, 0x33, 0x66, 0x99, 0xcc, 0xff]
+for r in steps:
+    for g in steps:
+        for b in steps:
+            hx = '#%02x%02x%02x' % (r, g, b)
+            rgb = [r / 255, g / 255, b / 255]
+            xyz = rgb_to_xyz(rgb); luv = xyz_to_luv(xyz); lch = luv_to_lch(luv)
+            out[hx] = {"rgb": rgb, "xyz": xyz, "luv": luv, "lchuv": lch,
+                       "hsluv": lch_to_hsluv(lch), "hpluv": lch_to_hpluv(lch)}
+path = os.path.join(os.path.dirname(os.path.abspath(__file__)), 'hsluv_snapshot.json')
+open(path, 'w').write(json.dumps(out, indent=1))
@@ -0,0 +1,315 @@
+use crate::{LinearRgba, SRgba};
+
+/// Blend modes from the W3C
+/// [Compositing and Blending Level 1](https://www.w3.org/TR/compositing-1/#blending)
+/// specification, as used by CSS `mix-blend-mode` and by most image editors.
+///
+/// Modes up to and including [`BlendMode::Exclusion`] are *separable*, meaning that each
+/// color channel is blended independently. The remaining modes are *non-separable*, and
+/// operate on the hue, saturation and luminosity of the color as a whole.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// The source color replaces the backdrop.
+    #[default]
+    Normal,
+    /// Multiplies the backdrop and source colors. The result is always at least as dark as
+    /// either of the inputs.
+    Multiply,
+    /// Multiplies the complements of the backdrop and source colors, then complements the
+    /// result. The result is always at least as light as either of the inputs.
+    Screen,
+    /// Multiplies or screens the colors, depending on the backdrop color.
+    Overlay,
+    /// Selects the darker of the backdrop and source colors.
+    Darken,
+    /// Selects the lighter of the backdrop and source colors.
+    Lighten,
+    /// Brightens the backdrop color to reflect the source color.
+    ColorDodge,
+    /// Darkens the backdrop color to reflect the source color.
+    ColorBurn,
+    /// Multiplies or screens the colors, depending on the source color.
+    HardLight,
+    /// Darkens or lightens the colors, depending on the source color.
+    SoftLight,
+    /// Subtracts the darker of the two colors from the lighter.
+    Difference,
+    /// Similar to [`BlendMode::Difference`], but lower in contrast.
+    Exclusion,
+    /// The hue of the source, with the saturation and luminosity of the backdrop.
+    Hue,
+    /// The saturation of the source, with the hue and luminosity of the backdrop.
+    Saturation,
+    /// The hue and saturation of the source, with the luminosity of the backdrop.
+    Color,
+    /// The luminosity of the source, with the hue and saturation of the backdrop.
+    Luminosity,
+}
+
+impl BlendMode {
+    /// Returns true if this blend mode operates on each color channel independently.
+    pub const fn is_separable(&self) -> bool {
+        !matches!(
+            self,
+            BlendMode::Hue | BlendMode::Saturation | BlendMode::Color | BlendMode::Luminosity
+        )
+    }
+
+    /// Apply the blend function `B(Cb, Cs)` to a pair of opaque colors, without any alpha
+    /// compositing.
+    pub(crate) fn apply(&self, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+        match self {
+            BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            BlendMode::Color => set_lum(cs, lum(cb)),
+            BlendMode::Luminosity => set_lum(cb, lum(cs)),
+            _ => [
+                self.apply_channel(cb[0], cs[0]),
+                self.apply_channel(cb[1], cs[1]),
+                self.apply_channel(cb[2], cs[2]),
+            ],
+        }
+    }
+
+    /// Blend a single channel using one of the separable blend modes.
+    fn apply_channel(&self, cb: f32, cs: f32) -> f32 {
+        match self {
+            BlendMode::Normal => cs,
+            BlendMode::Multiply => cb * cs,
+            BlendMode::Screen => cb + cs - cb * cs,
+            BlendMode::Overlay => BlendMode::HardLight.apply_channel(cs, cb),
+            BlendMode::Darken => cb.min(cs),
+            BlendMode::Lighten => cb.max(cs),
+            BlendMode::ColorDodge => {
+                if cb == 0. {
+                    0.
+                } else if cs >= 1. {
+                    1.
+                } else {
+                    (cb / (1. - cs)).min(1.)
+                }
+            }
+            BlendMode::ColorBurn => {
+                if cb >= 1. {
+                    1.
+                } else if cs == 0. {
+                    0.
+                } else {
+                    1. - ((1. - cb) / cs).min(1.)
+                }
+            }
+            BlendMode::HardLight => {
+                if cs <= 0.5 {
+                    BlendMode::Multiply.apply_channel(cb, 2. * cs)
+                } else {
+                    BlendMode::Screen.apply_channel(cb, 2. * cs - 1.)
+                }
+            }
+            BlendMode::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1. - 2. * cs) * cb * (1. - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16. * cb - 12.) * cb + 4.) * cb
+                    } else {
+                        cb.sqrt()
+                    };
+                    cb + (2. * cs - 1.) * (d - cb)
+                }
+            }
+            BlendMode::Difference => (cb - cs).abs(),
+            BlendMode::Exclusion => cb + cs - 2. * cb * cs,
+            BlendMode::Hue | BlendMode::Saturation | BlendMode::Color | BlendMode::Luminosity => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Blend and then composite a straight-alpha source color over a straight-alpha backdrop
+    /// using the `source-over` Porter-Duff operator. The result is also straight alpha.
+    pub(crate) fn composite(&self, backdrop: [f32; 4], source: [f32; 4]) -> [f32; 4] {
+        let [rb, gb, bb, ab] = backdrop;
+        let [rs, gs, bs, as_] = source;
+        let blended = self.apply([rb, gb, bb], [rs, gs, bs]);
+        let alpha = as_ + ab * (1. - as_);
+        if alpha <= 0. {
+            return [0., 0., 0., 0.];
+        }
+        let [red, green, blue] = std::array::from_fn(|i| {
+            // The backdrop only affects the blended color in proportion to its coverage.
+            let cs = (1. - ab) * source[i] + ab * blended[i];
+            (as_ * cs + ab * backdrop[i] * (1. - as_)) / alpha
+        });
+        [red, green, blue, alpha]
+    }
+}
+
+/// Blend one color on top of another using one of the standard [`BlendMode`]s.
+///
+/// The blend is computed in the color space of the implementing type, so blending two
+/// [`SRgba`] colors gives the same result as a web browser or image editor, whereas blending
+/// two [`LinearRgba`] colors is physically based.
+pub trait Blend: Sized {
+    /// Blend the `source` color on top of this color, which is the backdrop. Alpha is
+    /// handled by compositing the blended result over the backdrop.
+    fn blend(&self, source: &Self, mode: BlendMode) -> Self;
+}
+
+impl Blend for SRgba {
+    fn blend(&self, source: &Self, mode: BlendMode) -> Self {
+        let [red, green, blue, alpha] = mode.composite((*self).into(), (*source).into());
+        Self::new(red, green, blue, alpha)
+    }
+}
+
+impl Blend for LinearRgba {
+    fn blend(&self, source: &Self, mode: BlendMode) -> Self {
+        let [red, green, blue, alpha] = mode.composite((*self).into(), (*source).into());
+        Self::new(red, green, blue, alpha)
+    }
+}
+
+/// Luminosity as defined by the compositing spec. Note that these are not the same weights
+/// as [`crate::LuminanceOps::luminance`].
+fn lum([r, g, b]: [f32; 3]) -> f32 {
+    0.3 * r + 0.59 * g + 0.11 * b
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    let mut c = c;
+    if n < 0. {
+        c = c.map(|v| l + (v - l) * l / (l - n));
+    }
+    if x > 1. {
+        c = c.map(|v| l + (v - l) * (1. - l) / (x - l));
+    }
+    c
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color(c.map(|v| v + d))
+}
+
+fn sat([r, g, b]: [f32; 3]) -> f32 {
+    r.max(g).max(b) - r.min(g).min(b)
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    if x > n {
+        // Maps the max channel to `s`, the min channel to zero, and scales the middle one.
+        c.map(|v| (v - n) * s / (x - n))
+    } else {
+        [0., 0., 0.]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::assert_approx_eq, WithAlpha};
+
+    const BACKDROP: SRgba = SRgba::new(0.2, 0.4, 0.8, 1.0);
+    const SOURCE: SRgba = SRgba::new(0.6, 0.5, 0.1, 1.0);
+
+    fn assert_blend(backdrop: SRgba, source: SRgba, mode: BlendMode, expected: [f32; 3]) {
+        let result = backdrop.blend(&source, mode);
+        assert_approx_eq!(result.red, expected[0], 0.0001);
+        assert_approx_eq!(result.green, expected[1], 0.0001);
+        assert_approx_eq!(result.blue, expected[2], 0.0001);
+        assert_approx_eq!(result.alpha, 1.0, 0.0001);
+    }
+
+    #[test]
+    fn separable_modes() {
+        let blend = |mode, expected| assert_blend(BACKDROP, SOURCE, mode, expected);
+        blend(BlendMode::Normal, [0.6, 0.5, 0.1]);
+        blend(BlendMode::Multiply, [0.12, 0.2, 0.08]);
+        blend(BlendMode::Screen, [0.68, 0.7, 0.82]);
+        blend(BlendMode::Overlay, [0.24, 0.4, 0.64]);
+        blend(BlendMode::Darken, [0.2, 0.4, 0.1]);
+        blend(BlendMode::Lighten, [0.6, 0.5, 0.8]);
+        blend(BlendMode::ColorDodge, [0.5, 0.8, 0.888889]);
+        blend(BlendMode::ColorBurn, [0.0, 0.0, 0.0]);
+        blend(BlendMode::HardLight, [0.36, 0.4, 0.16]);
+        blend(BlendMode::SoftLight, [0.2496, 0.4, 0.672]);
+        blend(BlendMode::Difference, [0.4, 0.1, 0.7]);
+        blend(BlendMode::Exclusion, [0.56, 0.5, 0.74]);
+    }
+
+    #[test]
+    fn dodge_burn_edge_cases() {
+        let black = SRgba::BLACK;
+        let white = SRgba::WHITE;
+        assert_blend(black, white, BlendMode::ColorDodge, [0.0, 0.0, 0.0]);
+        assert_blend(SOURCE, white, BlendMode::ColorDodge, [1.0, 1.0, 1.0]);
+        assert_blend(white, black, BlendMode::ColorBurn, [1.0, 1.0, 1.0]);
+        assert_blend(SOURCE, black, BlendMode::ColorBurn, [0.0, 0.0, 0.0]);
+        assert_blend(
+            SOURCE,
+            SRgba::new(0.8, 0.8, 0.8, 1.0),
+            BlendMode::ColorBurn,
+            [0.5, 0.375, 0.0],
+        );
+    }
+
+    #[test]
+    fn non_separable_modes() {
+        let gray = SRgba::GRAY;
+        let red = SRgba::RED;
+        assert_blend(red, gray, BlendMode::Luminosity, [1.0, 0.285714, 0.285714]);
+        assert_blend(gray, red, BlendMode::Color, [1.0, 0.285714, 0.285714]);
+        assert_blend(BACKDROP, red, BlendMode::Hue, [0.804, 0.204, 0.204]);
+        assert_blend(BACKDROP, gray, BlendMode::Saturation, [0.384, 0.384, 0.384]);
+        assert!(!BlendMode::Hue.is_separable());
+        assert!(BlendMode::Multiply.is_separable());
+    }
+
+    #[test]
+    fn alpha_compositing() {
+        // Fully transparent source leaves the backdrop unchanged.
+        let result = BACKDROP.blend(&SOURCE.with_alpha(0.), BlendMode::Multiply);
+        assert_approx_eq!(result.red, BACKDROP.red, 0.0001);
+        assert_approx_eq!(result.blue, BACKDROP.blue, 0.0001);
+
+        // Fully transparent backdrop shows the source, regardless of mode.
+        let result = BACKDROP.with_alpha(0.).blend(&SOURCE, BlendMode::Multiply);
+        assert_approx_eq!(result.red, SOURCE.red, 0.0001);
+        assert_approx_eq!(result.blue, SOURCE.blue, 0.0001);
+        assert_approx_eq!(result.alpha, 1.0, 0.0001);
+
+        // Half transparent source is averaged with the backdrop.
+        let result = BACKDROP.blend(&SOURCE.with_alpha(0.5), BlendMode::Multiply);
+        assert_approx_eq!(result.red, 0.16, 0.0001);
+        assert_approx_eq!(result.green, 0.3, 0.0001);
+        assert_approx_eq!(result.blue, 0.44, 0.0001);
+        assert_approx_eq!(result.alpha, 1.0, 0.0001);
+
+        // Two half transparent layers.
+        let result = BACKDROP
+            .with_alpha(0.5)
+            .blend(&SOURCE.with_alpha(0.5), BlendMode::Normal);
+        assert_approx_eq!(result.alpha, 0.75, 0.0001);
+        assert_approx_eq!(result.red, (0.5 * 0.6 + 0.25 * 0.2) / 0.75, 0.0001);
+
+        assert_eq!(
+            SRgba::NONE.blend(&SRgba::NONE, BlendMode::Screen),
+            SRgba::NONE
+        );
+    }
+
+    #[test]
+    fn linear_blend() {
+        let backdrop = LinearRgba::new(0.2, 0.4, 0.8, 1.0);
+        let source = LinearRgba::new(0.6, 0.5, 0.1, 1.0);
+        let result = backdrop.blend(&source, BlendMode::Screen);
+        assert_approx_eq!(result.red, 0.68, 0.0001);
+        assert_approx_eq!(result.green, 0.7, 0.0001);
+        assert_approx_eq!(result.blue, 0.82, 0.0001);
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{
+    to_css_string::{RoundToDecimalPlaces, ToCssString},
+    LinearRgba, Mix, SRgba, WithAlpha,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the device-dependent CMYK (cyan, magenta, yellow, black) process color model,
+/// with alpha.
+///
+/// Without an ICC profile, CMYK values are only an approximation of what a press will
+/// print. The conversions here use the simple subtractive model in which each ink
+/// multiplies the reflected light, so `red = (1 - cyan) * (1 - black)`. The [`From`]
+/// conversion uses the naive separation, in which black replaces the whole gray component;
+/// use [`CmykSeparation`] to control black generation and the total amount of ink.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Cmyka {
+    pub cyan: f32,
+    pub magenta: f32,
+    pub yellow: f32,
+    pub black: f32,
+    pub alpha: f32,
+}
+
+impl Cmyka {
+    /// Construct a new [`Cmyka`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `cyan` - Cyan ink. [0.0, 1.0]
+    /// * `magenta` - Magenta ink. [0.0, 1.0]
+    /// * `yellow` - Yellow ink. [0.0, 1.0]
+    /// * `black` - Black ink. [0.0, 1.0]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(cyan: f32, magenta: f32, yellow: f32, black: f32, alpha: f32) -> Self {
+        Self {
+            cyan,
+            magenta,
+            yellow,
+            black,
+            alpha,
+        }
+    }
+
+    /// Convert the Cmyka color to a tuple of components (cyan, magenta, yellow, black,
+    /// alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32, f32) {
+        (self.cyan, self.magenta, self.yellow, self.black, self.alpha)
+    }
+
+    /// Construct a new [`Cmyka`] color from a tuple of components (cyan, magenta, yellow,
+    /// black, alpha).
+    #[inline]
+    pub const fn from_components(
+        (cyan, magenta, yellow, black, alpha): (f32, f32, f32, f32, f32),
+    ) -> Self {
+        Self::new(cyan, magenta, yellow, black, alpha)
+    }
+
+    /// The total amount of ink, from 0.0 to 4.0 (400%).
+    pub fn total_ink(&self) -> f32 {
+        self.cyan + self.magenta + self.yellow + self.black
+    }
+}
+
+impl Default for Cmyka {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1., 1.)
+    }
+}
+
+impl Mix for Cmyka {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            cyan: self.cyan * n_factor + other.cyan * factor,
+            magenta: self.magenta * n_factor + other.magenta * factor,
+            yellow: self.yellow * n_factor + other.yellow * factor,
+            black: self.black * n_factor + other.black * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Cmyka {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl ToCssString for Cmyka {
+    fn to_css_string(&self) -> String {
+        format!(
+            "device-cmyk({}% {}% {}% {}% / {})",
+            (self.cyan * 100.).round_to_decimal_places(3),
+            (self.magenta * 100.).round_to_decimal_places(3),
+            (self.yellow * 100.).round_to_decimal_places(3),
+            (self.black * 100.).round_to_decimal_places(3),
+            self.alpha
+        )
+    }
+}
+
+/// How much black ink replaces the gray component of a color, which is the part of it that
+/// equal amounts of cyan, magenta and yellow would print.
+///
+/// The presets follow the options offered by common separation tools: the lighter the
+/// setting, the later black starts and the less of the gray component it replaces.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum BlackGeneration {
+    /// No black ink; colors are printed with cyan, magenta and yellow only.
+    None,
+    /// Black starts at 50% gray and replaces up to 60% of the gray component.
+    Light,
+    /// Black starts at 25% gray and replaces up to 80% of the gray component.
+    #[default]
+    Medium,
+    /// Black starts at 10% gray and replaces up to 95% of the gray component.
+    Heavy,
+    /// Black replaces the whole gray component, as the naive formula does.
+    Maximum,
+    /// A custom curve. No black is generated below a gray component of `start`; above it,
+    /// black rises to `max` at full gray, following a power curve with `exponent`.
+    Custom { start: f32, max: f32, exponent: f32 },
+}
+
+impl BlackGeneration {
+    /// The amount of black ink for a gray component in [0, 1]. The result never exceeds
+    /// `gray`, so that the color can be preserved by reducing the other inks.
+    pub fn black(self, gray: f32) -> f32 {
+        let (start, max, exponent) = match self {
+            BlackGeneration::None => return 0.,
+            BlackGeneration::Light => (0.5, 0.6, 1.5),
+            BlackGeneration::Medium => (0.25, 0.8, 1.2),
+            BlackGeneration::Heavy => (0.1, 0.95, 1.),
+            BlackGeneration::Maximum => return gray,
+            BlackGeneration::Custom {
+                start,
+                max,
+                exponent,
+            } => (start, max, exponent),
+        };
+        if gray <= start {
+            return 0.;
+        }
+        let t = ((gray - start) / (1. - start).max(f32::EPSILON)).min(1.);
+        (max * t.powf(exponent)).clamp(0., gray)
+    }
+}
+
+/// Which colors black ink is applied to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Hash, Serialize, Deserialize)]
+pub enum SeparationMode {
+    /// Gray component replacement: black replaces the gray component of every color,
+    /// including saturated ones.
+    #[default]
+    Gcr,
+    /// Under-color removal: black only replaces the gray component of neutral colors, and
+    /// fades out as colors become more saturated.
+    Ucr,
+}
+
+/// Settings for separating an sRGB color into CMYK inks.
+///
+/// Black is generated from the gray component of the color by the [`BlackGeneration`]
+/// curve, and cyan, magenta and yellow are reduced to preserve the color. If the total ink
+/// then exceeds `total_ink_limit`, cyan, magenta and yellow are scaled down to fit, and
+/// black is reduced too if it exceeds the limit on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct CmykSeparation {
+    pub mode: SeparationMode,
+    pub black_generation: BlackGeneration,
+    /// The maximum sum of all four inks, from 0.0 to 4.0. Coated stock is typically printed
+    /// with a limit of about 3.0 (300%), and uncoated stock with about 2.6.
+    pub total_ink_limit: f32,
+}
+
+impl CmykSeparation {
+    /// The naive separation, with maximum black and no ink limit. This is what the [`From`]
+    /// conversion from [`SRgba`] to [`Cmyka`] uses.
+    pub const NAIVE: Self = Self {
+        mode: SeparationMode::Gcr,
+        black_generation: BlackGeneration::Maximum,
+        total_ink_limit: 4.,
+    };
+
+    /// Construct a new [`CmykSeparation`].
+    pub const fn new(
+        mode: SeparationMode,
+        black_generation: BlackGeneration,
+        total_ink_limit: f32,
+    ) -> Self {
+        Self {
+            mode,
+            black_generation,
+            total_ink_limit,
+        }
+    }
+
+    /// Separate an sRGB color into CMYK inks. Channels outside [0, 1] are clamped.
+    pub fn separate(&self, color: SRgba) -> Cmyka {
+        let [cyan, magenta, yellow] =
+            [color.red, color.green, color.blue].map(|channel| 1. - channel.clamp(0., 1.));
+        let gray = cyan.min(magenta).min(yellow);
+        let mut black = self.black_generation.black(gray);
+        if self.mode == SeparationMode::Ucr {
+            let saturation = cyan.max(magenta).max(yellow) - gray;
+            black *= 1. - saturation;
+        }
+
+        // Remove the inks that the black now prints: (1 - ink') * (1 - black) = 1 - ink.
+        let mut inks = if black >= 1. {
+            [0.; 3]
+        } else {
+            [cyan, magenta, yellow].map(|ink| ((ink - black) / (1. - black)).max(0.))
+        };
+
+        let limit = self.total_ink_limit.clamp(0., 4.);
+        let colored: f32 = inks.iter().sum();
+        if colored + black > limit {
+            let black_limit = black.min(limit);
+            if colored > 0. {
+                let scale = (limit - black_limit) / colored;
+                inks = inks.map(|ink| ink * scale);
+            }
+            black = black_limit;
+        }
+        let [cyan, magenta, yellow] = inks;
+        Cmyka::new(cyan, magenta, yellow, black, color.alpha)
+    }
+}
+
+impl Default for CmykSeparation {
+    fn default() -> Self {
+        Self::new(SeparationMode::Gcr, BlackGeneration::Medium, 3.)
+    }
+}
+
+impl From<SRgba> for Cmyka {
+    fn from(value: SRgba) -> Self {
+        CmykSeparation::NAIVE.separate(value)
+    }
+}
+
+impl From<Cmyka> for SRgba {
+    fn from(value: Cmyka) -> Self {
+        let white = 1. - value.black;
+        SRgba::new(
+            (1. - value.cyan) * white,
+            (1. - value.magenta) * white,
+            (1. - value.yellow) * white,
+            value.alpha,
+        )
+    }
+}
+
+impl From<LinearRgba> for Cmyka {
+    fn from(value: LinearRgba) -> Self {
+        Cmyka::from(SRgba::from(value))
+    }
+}
+
+impl From<Cmyka> for LinearRgba {
+    fn from(value: Cmyka) -> Self {
+        LinearRgba::from(SRgba::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    fn assert_srgb_eq(actual: SRgba, expected: SRgba) {
+        assert_approx_eq!(actual.red, expected.red, 0.0001);
+        assert_approx_eq!(actual.green, expected.green, 0.0001);
+        assert_approx_eq!(actual.blue, expected.blue, 0.0001);
+        assert_approx_eq!(actual.alpha, expected.alpha, 0.0001);
+    }
+
+    #[test]
+    fn naive() {
+        assert_eq!(Cmyka::from(SRgba::RED), Cmyka::new(0., 1., 1., 0., 1.));
+        assert_eq!(Cmyka::from(SRgba::WHITE), Cmyka::new(0., 0., 0., 0., 1.));
+        assert_eq!(Cmyka::from(SRgba::BLACK), Cmyka::new(0., 0., 0., 1., 1.));
+        let firebrick = Cmyka::from(SRgba::rgb_u8(178, 34, 34));
+        assert_approx_eq!(firebrick.cyan, 0., 0.0001);
+        assert_approx_eq!(firebrick.magenta, 0.809, 0.001);
+        assert_approx_eq!(firebrick.yellow, 0.809, 0.001);
+        assert_approx_eq!(firebrick.black, 0.302, 0.001);
+        for color in TEST_COLORS.iter() {
+            assert_srgb_eq(SRgba::from(Cmyka::from(color.rgb)), color.rgb);
+        }
+    }
+
+    #[test]
+    fn black_generation() {
+        let gray = SRgba::new(0.4, 0.4, 0.4, 1.);
+        let none = CmykSeparation::new(SeparationMode::Gcr, BlackGeneration::None, 4.);
+        let cmy = none.separate(gray);
+        assert_eq!(cmy.black, 0.);
+        assert_approx_eq!(cmy.cyan, 0.6, 0.0001);
+
+        // Heavier settings use more black and less colored ink, and preserve the color.
+        let mut last_black = 0.;
+        for black_generation in [
+            BlackGeneration::None,
+            BlackGeneration::Light,
+            BlackGeneration::Medium,
+            BlackGeneration::Heavy,
+            BlackGeneration::Maximum,
+        ] {
+            let separation = CmykSeparation::new(SeparationMode::Gcr, black_generation, 4.);
+            for color in TEST_COLORS.iter() {
+                assert_srgb_eq(SRgba::from(separation.separate(color.rgb)), color.rgb);
+            }
+            let cmyk = separation.separate(gray);
+            assert!(cmyk.black >= last_black, "{:?}", black_generation);
+            last_black = cmyk.black;
+        }
+        assert_approx_eq!(last_black, 0.6, 0.0001);
+
+        let custom = BlackGeneration::Custom {
+            start: 0.5,
+            max: 1.,
+            exponent: 1.,
+        };
+        assert_eq!(custom.black(0.4), 0.);
+        assert_approx_eq!(custom.black(0.75), 0.5, 0.0001);
+    }
+
+    #[test]
+    fn under_color_removal() {
+        let ucr = CmykSeparation::new(SeparationMode::Ucr, BlackGeneration::Maximum, 4.);
+        let gcr = CmykSeparation::new(SeparationMode::Gcr, BlackGeneration::Maximum, 4.);
+        let gray = SRgba::new(0.3, 0.3, 0.3, 1.);
+        assert_eq!(ucr.separate(gray), gcr.separate(gray));
+
+        let brown = SRgba::new(0.5, 0.3, 0.1, 1.);
+        assert!(ucr.separate(brown).black < gcr.separate(brown).black);
+        assert_srgb_eq(SRgba::from(ucr.separate(brown)), brown);
+    }
+
+    #[test]
+    fn total_ink_limit() {
+        let rich_black = SRgba::new(0.05, 0.02, 0.02, 1.);
+        let separation = CmykSeparation::new(SeparationMode::Gcr, BlackGeneration::None, 2.6);
+        let cmyk = separation.separate(rich_black);
+        assert_approx_eq!(cmyk.total_ink(), 2.6, 0.0001);
+
+        let separation = CmykSeparation::default();
+        for color in TEST_COLORS.iter() {
+            assert!(separation.separate(color.rgb).total_ink() <= 3.0001);
+        }
+
+        // A limit below full black caps the black of neutral colors, with no other inks.
+        let separation = CmykSeparation::new(SeparationMode::Gcr, BlackGeneration::Maximum, 0.5);
+        for color in [SRgba::BLACK, SRgba::new(0.2, 0.2, 0.2, 1.)] {
+            let cmyk = separation.separate(color);
+            assert_eq!([cmyk.cyan, cmyk.magenta, cmyk.yellow], [0.; 3]);
+            assert_approx_eq!(cmyk.black, 0.5, 0.0001);
+        }
+        let cmyk = separation.separate(rich_black);
+        assert!(!cmyk.cyan.is_nan());
+        assert_approx_eq!(cmyk.total_ink(), 0.5, 0.0001);
+    }
+
+    #[test]
+    fn to_css_string() {
+        assert_eq!(
+            Cmyka::from(SRgba::RED).to_css_string(),
+            "device-cmyk(0% 100% 100% 0% / 1)"
+        );
+        assert_eq!(
+            Cmyka::new(0.1, 0.25, 0.5, 0.125, 0.5).to_css_string(),
+            "device-cmyk(10% 25% 50% 12.5% / 0.5)"
+        );
+    }
+}
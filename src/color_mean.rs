@@ -0,0 +1,248 @@
+use crate::{Hsla, Lcha, LinearRgba, Oklaba};
+
+/// Averaging of any number of colors within a given color space.
+///
+/// Color components are weighted by alpha, so that a fully transparent color does not
+/// contribute to the resulting color, only to the resulting alpha. For color spaces with a
+/// hue component, the hue is a circular mean weighted by saturation or chroma, so that
+/// averaging 350° and 10° gives 0° rather than 180°.
+///
+/// To average colors in a different working space, use [`mean_in`] or [`weighted_mean_in`].
+pub trait Mean: Sized {
+    /// Compute the mean of a sequence of colors, each paired with a non-negative weight.
+    /// Returns `None` if the sequence is empty or the weights sum to zero.
+    fn weighted_mean<I: IntoIterator<Item = (Self, f32)>>(colors: I) -> Option<Self>;
+
+    /// Compute the mean of a sequence of colors, each with equal weight.
+    /// Returns `None` if the sequence is empty.
+    fn mean<I: IntoIterator<Item = Self>>(colors: I) -> Option<Self> {
+        Self::weighted_mean(colors.into_iter().map(|color| (color, 1.0)))
+    }
+}
+
+/// Compute the mean of a sequence of colors, using the color space `S` as the working space.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{mean_in, LinearRgba, SRgba};
+/// // Average in linear light, but return the result in sRGB.
+/// let average = mean_in::<LinearRgba, _>([SRgba::RED, SRgba::BLUE]).unwrap();
+/// ```
+pub fn mean_in<S, T>(colors: impl IntoIterator<Item = T>) -> Option<T>
+where
+    S: Mean + From<T>,
+    T: From<S>,
+{
+    S::mean(colors.into_iter().map(S::from)).map(T::from)
+}
+
+/// Compute the weighted mean of a sequence of colors, using the color space `S` as the
+/// working space.
+pub fn weighted_mean_in<S, T>(colors: impl IntoIterator<Item = (T, f32)>) -> Option<T>
+where
+    S: Mean + From<T>,
+    T: From<S>,
+{
+    S::weighted_mean(
+        colors
+            .into_iter()
+            .map(|(color, weight)| (S::from(color), weight)),
+    )
+    .map(T::from)
+}
+
+impl Mean for LinearRgba {
+    fn weighted_mean<I: IntoIterator<Item = (Self, f32)>>(colors: I) -> Option<Self> {
+        let (c, alpha) =
+            premultiplied_mean(colors.into_iter().map(|(color, weight)| {
+                ([color.red, color.green, color.blue], color.alpha, weight)
+            }))?;
+        Some(Self::new(c[0], c[1], c[2], alpha))
+    }
+}
+
+impl Mean for Oklaba {
+    fn weighted_mean<I: IntoIterator<Item = (Self, f32)>>(colors: I) -> Option<Self> {
+        let (c, alpha) = premultiplied_mean(
+            colors
+                .into_iter()
+                .map(|(color, weight)| ([color.l, color.a, color.b], color.alpha, weight)),
+        )?;
+        Some(Self::new(c[0], c[1], c[2], alpha))
+    }
+}
+
+impl Mean for Hsla {
+    fn weighted_mean<I: IntoIterator<Item = (Self, f32)>>(colors: I) -> Option<Self> {
+        let (c, alpha) = premultiplied_mean(colors.into_iter().map(|(color, weight)| {
+            let (x, y) = hue_vector(color.hue, color.saturation);
+            (
+                [x, y, color.saturation, color.lightness],
+                color.alpha,
+                weight,
+            )
+        }))?;
+        Some(Self::new(hue_angle(c[0], c[1]), c[2], c[3], alpha))
+    }
+}
+
+impl Mean for Lcha {
+    fn weighted_mean<I: IntoIterator<Item = (Self, f32)>>(colors: I) -> Option<Self> {
+        let (c, alpha) = premultiplied_mean(colors.into_iter().map(|(color, weight)| {
+            let (x, y) = hue_vector(color.hue, color.chroma);
+            ([x, y, color.lightness, color.chroma], color.alpha, weight)
+        }))?;
+        Some(Self::new(c[2], c[3], hue_angle(c[0], c[1]), alpha))
+    }
+}
+
+/// Weighted mean of alpha-premultiplied components. If every color is fully transparent,
+/// the components are averaged without alpha weighting instead.
+fn premultiplied_mean<const N: usize>(
+    colors: impl Iterator<Item = ([f32; N], f32, f32)>,
+) -> Option<([f32; N], f32)> {
+    let mut total_weight = 0.;
+    let mut total_alpha = 0.;
+    let mut premultiplied = [0.; N];
+    let mut straight = [0.; N];
+    for (components, alpha, weight) in colors {
+        total_weight += weight;
+        total_alpha += weight * alpha;
+        for i in 0..N {
+            premultiplied[i] += components[i] * weight * alpha;
+            straight[i] += components[i] * weight;
+        }
+    }
+    if total_weight <= 0. {
+        return None;
+    }
+    let components = if total_alpha > 0. {
+        premultiplied.map(|c| c / total_alpha)
+    } else {
+        straight.map(|c| c / total_weight)
+    };
+    Some((components, total_alpha / total_weight))
+}
+
+/// Convert a hue angle in degrees into a vector whose length is the saturation or chroma.
+fn hue_vector(hue: f32, magnitude: f32) -> (f32, f32) {
+    let (sin, cos) = hue.to_radians().sin_cos();
+    (cos * magnitude, sin * magnitude)
+}
+
+/// Convert a hue vector back into an angle in degrees, in the range [0.0, 360.0).
+fn hue_angle(x: f32, y: f32) -> f32 {
+    if x.abs() < 1e-6 && y.abs() < 1e-6 {
+        return 0.;
+    }
+    let hue = y.atan2(x).to_degrees().rem_euclid(360.);
+    // `rem_euclid` can round up to exactly 360.0 for tiny negative angles.
+    if hue >= 360. {
+        0.
+    } else {
+        hue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::assert_approx_eq, SRgba};
+
+    #[test]
+    fn linear_mean() {
+        let mean = LinearRgba::mean([
+            LinearRgba::new(1.0, 0.0, 0.0, 1.0),
+            LinearRgba::new(0.0, 0.0, 1.0, 1.0),
+        ])
+        .unwrap();
+        assert_eq!(mean, LinearRgba::new(0.5, 0.0, 0.5, 1.0));
+
+        assert_eq!(LinearRgba::mean([]), None);
+        assert_eq!(
+            LinearRgba::weighted_mean([(LinearRgba::default(), 0.0)]),
+            None
+        );
+    }
+
+    #[test]
+    fn weighted_mean() {
+        let mean = Oklaba::weighted_mean([
+            (Oklaba::new(1.0, 0.0, 0.0, 1.0), 3.0),
+            (Oklaba::new(0.0, 0.2, 0.4, 1.0), 1.0),
+        ])
+        .unwrap();
+        assert_approx_eq!(mean.l, 0.75, 0.0001);
+        assert_approx_eq!(mean.a, 0.05, 0.0001);
+        assert_approx_eq!(mean.b, 0.1, 0.0001);
+        assert_approx_eq!(mean.alpha, 1.0, 0.0001);
+    }
+
+    #[test]
+    fn alpha_weighting() {
+        // A transparent color only affects alpha.
+        let mean = LinearRgba::mean([
+            LinearRgba::new(1.0, 0.0, 0.0, 1.0),
+            LinearRgba::new(0.0, 0.0, 1.0, 0.0),
+        ])
+        .unwrap();
+        assert_eq!(mean, LinearRgba::new(1.0, 0.0, 0.0, 0.5));
+
+        let mean = LinearRgba::mean([
+            LinearRgba::new(1.0, 0.0, 0.0, 0.75),
+            LinearRgba::new(0.0, 0.0, 1.0, 0.25),
+        ])
+        .unwrap();
+        assert_eq!(mean, LinearRgba::new(0.75, 0.0, 0.25, 0.5));
+
+        // If everything is transparent, fall back to an unweighted average.
+        let mean = LinearRgba::mean([
+            LinearRgba::new(1.0, 0.0, 0.0, 0.0),
+            LinearRgba::new(0.0, 0.0, 1.0, 0.0),
+        ])
+        .unwrap();
+        assert_eq!(mean, LinearRgba::new(0.5, 0.0, 0.5, 0.0));
+    }
+
+    #[test]
+    fn circular_hue() {
+        let mean = Hsla::mean([
+            Hsla::new(350.0, 1.0, 0.5, 1.0),
+            Hsla::new(30.0, 1.0, 0.5, 1.0),
+        ])
+        .unwrap();
+        assert_approx_eq!(mean.hue, 10.0, 0.001);
+
+        let mean = Hsla::mean([
+            Hsla::new(350.0, 0.5, 0.4, 1.0),
+            Hsla::new(10.0, 0.5, 0.6, 1.0),
+        ])
+        .unwrap();
+        assert!(mean.hue < 0.001 || mean.hue > 359.999, "{}", mean.hue);
+        assert_approx_eq!(mean.saturation, 0.5, 0.0001);
+        assert_approx_eq!(mean.lightness, 0.5, 0.0001);
+
+        // Achromatic colors don't pull the hue.
+        let mean = Lcha::mean([
+            Lcha::new(0.5, 0.0, 0.0, 1.0),
+            Lcha::new(0.5, 0.6, 240.0, 1.0),
+        ])
+        .unwrap();
+        assert_approx_eq!(mean.hue, 240.0, 0.001);
+        assert_approx_eq!(mean.chroma, 0.3, 0.0001);
+    }
+
+    #[test]
+    fn working_space() {
+        let linear = mean_in::<LinearRgba, _>([SRgba::RED, SRgba::BLUE]).unwrap();
+        assert_approx_eq!(linear.red, 0.735357, 0.0001);
+        assert_approx_eq!(linear.blue, 0.735357, 0.0001);
+
+        let oklab =
+            weighted_mean_in::<Oklaba, _>([(SRgba::WHITE, 1.0), (SRgba::BLACK, 1.0)]).unwrap();
+        let expected: SRgba = Oklaba::new(0.5, 0.0, 0.0, 1.0).into();
+        assert_approx_eq!(oklab.red, expected.red, 0.0001);
+        assert_approx_eq!(oklab.green, expected.green, 0.0001);
+    }
+}
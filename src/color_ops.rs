@@ -41,3 +41,129 @@ pub trait WithAlpha: Sized {
     /// Return a new version of this color with the given alpha value.
     fn with_alpha(&self, alpha: f32) -> Self;
 }
+
+/// Implements the arithmetic operators for a color type whose components form a vector
+/// space, treating every listed field (including alpha) as a vector component.
+///
+/// This should only be used for color spaces in which linear math is meaningful, which
+/// excludes gamma-encoded spaces like [`crate::SRgba`] and polar spaces like [`crate::Hsla`].
+macro_rules! impl_componentwise_ops {
+    ($ty:ident, [$($field:ident),+]) => {
+        impl std::ops::Add<Self> for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self {
+                    $($field: self.$field + rhs.$field,)+
+                }
+            }
+        }
+
+        impl std::ops::AddAssign<Self> for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl std::ops::Sub<Self> for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self {
+                    $($field: self.$field - rhs.$field,)+
+                }
+            }
+        }
+
+        impl std::ops::SubAssign<Self> for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl std::ops::Mul<Self> for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                Self {
+                    $($field: self.$field * rhs.$field,)+
+                }
+            }
+        }
+
+        impl std::ops::MulAssign<Self> for $ty {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl std::ops::Mul<f32> for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: f32) -> Self {
+                Self {
+                    $($field: self.$field * rhs,)+
+                }
+            }
+        }
+
+        impl std::ops::Mul<$ty> for f32 {
+            type Output = $ty;
+
+            fn mul(self, rhs: $ty) -> $ty {
+                rhs * self
+            }
+        }
+
+        impl std::ops::MulAssign<f32> for $ty {
+            fn mul_assign(&mut self, rhs: f32) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl std::ops::Div<f32> for $ty {
+            type Output = Self;
+
+            fn div(self, rhs: f32) -> Self {
+                Self {
+                    $($field: self.$field / rhs,)+
+                }
+            }
+        }
+
+        impl std::ops::DivAssign<f32> for $ty {
+            fn div_assign(&mut self, rhs: f32) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl std::ops::Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self {
+                    $($field: -self.$field,)+
+                }
+            }
+        }
+
+        impl std::iter::Sum for $ty {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(
+                    Self {
+                        $($field: 0.0,)+
+                    },
+                    |acc, color| acc + color,
+                )
+            }
+        }
+
+        impl<'a> std::iter::Sum<&'a $ty> for $ty {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_componentwise_ops;
@@ -1,28 +1,131 @@
-use crate::{LinearRgba, Mix};
+use crate::{Easing, LinearRgba, Mix};
+
+/// How a [`ColorRange`] treats interpolation factors outside of the range 0.0 to 1.0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// The factor is passed through unchanged, extrapolating beyond the start and end colors.
+    #[default]
+    Extend,
+    /// The factor is clamped, so that the range holds its start and end colors.
+    Clamp,
+    /// The range repeats, so that it jumps back to the start color after each whole unit.
+    /// Positive whole factors map to the end color, so that `0.0..=1.0` still spans the range.
+    Repeat,
+    /// The range repeats in alternating directions, so that it ping-pongs between the start
+    /// and end colors.
+    Mirror,
+}
+
+impl OutOfRange {
+    /// Map an interpolation factor according to this mode.
+    pub fn apply(self, factor: f32) -> f32 {
+        match self {
+            OutOfRange::Extend => factor,
+            OutOfRange::Clamp => factor.clamp(0., 1.),
+            OutOfRange::Repeat => {
+                let t = factor - factor.floor();
+                if t == 0. && factor > 0. {
+                    1.
+                } else {
+                    t
+                }
+            }
+            OutOfRange::Mirror => {
+                let t = factor.rem_euclid(2.);
+                if t > 1. {
+                    2. - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
 
 /// Represents a range of colors that can be linearly interpolated, defined by a start and
 /// end point which must be in the same color space. It works for any color type that
 /// implements [`Mix`].
 ///
+/// The interpolation factor can optionally be remapped by an [`Easing`], and factors outside
+/// of the range 0.0 to 1.0 are handled according to an [`OutOfRange`] mode.
+///
 /// This is useful for defining gradients or animated color transitions.
 pub struct ColorRange<T: Mix> {
     start: T,
     end: T,
+    easing: Easing,
+    out_of_range: OutOfRange,
 }
 
 impl<T> ColorRange<T>
 where
     T: Mix,
 {
-    /// Construct a new color range from the start and end values.
+    /// Construct a new color range from the start and end values, with linear easing.
     pub fn new(start: T, end: T) -> Self {
-        Self { start, end }
+        Self {
+            start,
+            end,
+            easing: Easing::Linear,
+            out_of_range: OutOfRange::Extend,
+        }
+    }
+
+    /// Return this range with the given easing.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Return this range with the given treatment of out-of-range factors.
+    pub fn with_out_of_range(mut self, out_of_range: OutOfRange) -> Self {
+        self.out_of_range = out_of_range;
+        self
+    }
+
+    /// Return this range traversed backwards, from the end color to the start color, such
+    /// that `range.reversed().at(t) == range.at(1.0 - t)`. The easing is reversed as well.
+    pub fn reversed(self) -> Self {
+        Self {
+            start: self.end,
+            end: self.start,
+            easing: self.easing.reversed(),
+            out_of_range: self.out_of_range,
+        }
+    }
+
+    /// The start color of the range.
+    pub fn start(&self) -> &T {
+        &self.start
+    }
+
+    /// The end color of the range.
+    pub fn end(&self) -> &T {
+        &self.end
+    }
+
+    /// The easing applied to the interpolation factor.
+    pub fn easing(&self) -> Easing {
+        self.easing
     }
 
-    /// Get the color value at the given interpolation factor, which should be between 0.0
-    /// and 1.0.
+    /// The treatment of out-of-range factors.
+    pub fn out_of_range(&self) -> OutOfRange {
+        self.out_of_range
+    }
+
+    /// Get the color value at the given interpolation factor. Factors between 0.0 and 1.0
+    /// span the range, others are handled according to the [`OutOfRange`] mode.
     pub fn at(&self, factor: f32) -> T {
-        self.start.mix(&self.end, factor)
+        self.start
+            .mix(&self.end, self.easing.ease(self.out_of_range.apply(factor)))
+    }
+
+    /// Return an iterator over `count` evenly spaced colors, starting with the start color
+    /// and ending with the end color. If `count` is 1, only the start color is returned.
+    pub fn steps(&self, count: usize) -> impl ExactSizeIterator<Item = T> + '_ {
+        let divisor = count.saturating_sub(1).max(1) as f32;
+        (0..count).map(move |i| self.at(i as f32 / divisor))
     }
 }
 
@@ -46,7 +149,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{LinearRgba, SRgba};
+    use crate::{testing::assert_approx_eq, LinearRgba, SRgba};
 
     #[test]
     fn test_color_range() {
@@ -63,4 +166,57 @@ mod tests {
         assert_eq!(range.at(0.5), LinearRgba::new(0.5, 0.0, 0.5, 1.0));
         assert_eq!(range.at(1.0), lblue);
     }
+
+    #[test]
+    fn easing() {
+        let range = ColorRange::new(SRgba::BLACK, SRgba::WHITE).with_easing(Easing::QuadIn);
+        assert_eq!(range.at(0.5), SRgba::new(0.25, 0.25, 0.25, 1.0));
+
+        let reversed = range.reversed();
+        assert_eq!(reversed.easing(), Easing::QuadOut);
+        assert_eq!(reversed.start(), &SRgba::WHITE);
+        for t in [0.0, 0.2, 0.5, 0.9] {
+            let expected = ColorRange::new(SRgba::BLACK, SRgba::WHITE)
+                .with_easing(Easing::QuadIn)
+                .at(1.0 - t);
+            assert_approx_eq!(reversed.at(t).red, expected.red, 0.0001);
+        }
+    }
+
+    #[test]
+    fn steps() {
+        let range = ColorRange::new(SRgba::BLACK, SRgba::WHITE);
+        let steps: Vec<_> = range.steps(5).map(|color| color.red).collect();
+        assert_eq!(steps, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(range.steps(1).collect::<Vec<_>>(), vec![SRgba::BLACK]);
+        assert_eq!(range.steps(0).count(), 0);
+
+        // Every mode keeps the last step at the end color.
+        let range = range.with_out_of_range(OutOfRange::Repeat);
+        assert_eq!(range.steps(3).last(), Some(SRgba::WHITE));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let range = ColorRange::new(SRgba::BLACK, SRgba::WHITE);
+        assert_approx_eq!(range.at(1.5).red, 1.5, 0.0001);
+
+        let range = range.with_out_of_range(OutOfRange::Clamp);
+        assert_eq!(range.at(1.5), SRgba::WHITE);
+        assert_eq!(range.at(-0.5), SRgba::BLACK);
+
+        let range = range.with_out_of_range(OutOfRange::Repeat);
+        assert_approx_eq!(range.at(1.25).red, 0.25, 0.0001);
+        assert_approx_eq!(range.at(-0.25).red, 0.75, 0.0001);
+        assert_eq!(range.at(0.0), SRgba::BLACK);
+        assert_eq!(range.at(1.0), SRgba::WHITE);
+        assert_eq!(range.at(2.0), SRgba::WHITE);
+        assert_eq!(range.at(-1.0), SRgba::BLACK);
+
+        let range = range.with_out_of_range(OutOfRange::Mirror);
+        assert_approx_eq!(range.at(1.25).red, 0.75, 0.0001);
+        assert_approx_eq!(range.at(2.25).red, 0.25, 0.0001);
+        assert_approx_eq!(range.at(-0.25).red, 0.25, 0.0001);
+        assert_approx_eq!(range.at(1.0).red, 1.0, 0.0001);
+    }
 }
@@ -0,0 +1,558 @@
+use crate::{AnyColorRange, LinearRgba, SRgba};
+
+/// A built-in colormap for scientific visualization, such as heatmaps and debug views.
+///
+/// Each colormap is a static table of evenly spaced sRGB colors, and sampling interpolates
+/// linearly between neighboring entries in sRGB, as matplotlib does. The maps include the
+/// perceptually uniform matplotlib maps, cividis (designed for color vision deficiency),
+/// Google's turbo, and the diverging maps coolwarm and RdBu. Every map also has a reversed
+/// variant, obtained with [`Colormap::reversed`].
+///
+/// # Accuracy
+///
+/// RdBu is exact, as matplotlib also interpolates between the 11 ColorBrewer colors. Coolwarm
+/// samples Kenneth Moreland's diverging interpolation at 33 points, the resolution of his
+/// published table, and is within 1/255 of the continuous map between them.
+///
+/// The other maps are compact 33-entry approximations, not matplotlib's 256-entry tables.
+/// Viridis, magma, inferno, plasma and cividis are polynomial fits, corrected to match the
+/// reference colors at every eighth of the range to 8-bit precision. Between those points
+/// they differ from the reference by up to 0.03 per channel. Turbo samples Google's polynomial
+/// approximation of the map, corrected to match its reference end colors.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{AnyColorRange, Colormap, SRgba};
+/// let hot: SRgba = Colormap::INFERNO.at(0.9);
+/// let cold = Colormap::INFERNO.reversed().at(0.9);
+/// let range: &dyn AnyColorRange = &Colormap::VIRIDIS;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Colormap {
+    name: &'static str,
+    table: &'static [[f32; 3]],
+    reversed: bool,
+}
+
+impl Colormap {
+    /// The matplotlib default colormap, from dark blue through green to yellow.
+    pub const VIRIDIS: Colormap = Colormap::new("viridis", VIRIDIS);
+    /// A perceptually uniform map from black through purple and orange to pale yellow.
+    pub const MAGMA: Colormap = Colormap::new("magma", MAGMA);
+    /// A perceptually uniform map from black through purple and orange to bright yellow.
+    pub const INFERNO: Colormap = Colormap::new("inferno", INFERNO);
+    /// A perceptually uniform map from dark blue through magenta to yellow.
+    pub const PLASMA: Colormap = Colormap::new("plasma", PLASMA);
+    /// A perceptually uniform map from blue to yellow, readable with color vision
+    /// deficiency.
+    pub const CIVIDIS: Colormap = Colormap::new("cividis", CIVIDIS);
+    /// Google's improved rainbow map, from dark blue through green and yellow to dark red.
+    pub const TURBO: Colormap = Colormap::new("turbo", TURBO);
+    /// Kenneth Moreland's diverging map from blue through light gray to red.
+    pub const COOLWARM: Colormap = Colormap::new("coolwarm", COOLWARM);
+    /// The ColorBrewer diverging map from dark red through white to dark blue.
+    pub const RD_BU: Colormap = Colormap::new("RdBu", RDBU);
+
+    /// All of the built-in colormaps, not reversed.
+    pub const ALL: [Colormap; 8] = [
+        Colormap::VIRIDIS,
+        Colormap::MAGMA,
+        Colormap::INFERNO,
+        Colormap::PLASMA,
+        Colormap::CIVIDIS,
+        Colormap::TURBO,
+        Colormap::COOLWARM,
+        Colormap::RD_BU,
+    ];
+
+    const fn new(name: &'static str, table: &'static [[f32; 3]]) -> Self {
+        Self {
+            name,
+            table,
+            reversed: false,
+        }
+    }
+
+    /// Look up a colormap by its matplotlib name, such as `"viridis"` or `"RdBu"`. Names
+    /// ending in `_r` return the reversed map. Names are case-insensitive.
+    pub fn from_name(name: &str) -> Option<Colormap> {
+        let (base, reversed) = match name.strip_suffix("_r").or(name.strip_suffix("_R")) {
+            Some(base) => (base, true),
+            None => (name, false),
+        };
+        Self::ALL
+            .into_iter()
+            .find(|map| map.name.eq_ignore_ascii_case(base))
+            .map(|map| if reversed { map.reversed() } else { map })
+    }
+
+    /// The matplotlib name of this colormap, without the `_r` suffix of reversed maps.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns true if this colormap is reversed.
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Return this colormap traversed in the opposite direction.
+    pub const fn reversed(self) -> Self {
+        Self {
+            reversed: !self.reversed,
+            ..self
+        }
+    }
+
+    /// The table of sRGB colors, in their original order even if the map is reversed.
+    pub fn table(&self) -> &'static [[f32; 3]] {
+        self.table
+    }
+
+    /// Get the color at the given factor, which is clamped to the range 0.0 to 1.0.
+    pub fn at(&self, factor: f32) -> SRgba {
+        let mut factor = factor.clamp(0., 1.);
+        if self.reversed {
+            factor = 1. - factor;
+        }
+        let last = self.table.len() - 1;
+        let scaled = factor * last as f32;
+        let index = (scaled as usize).min(last - 1);
+        let t = scaled - index as f32;
+        let (start, end) = (self.table[index], self.table[index + 1]);
+        SRgba::new(
+            start[0] + (end[0] - start[0]) * t,
+            start[1] + (end[1] - start[1]) * t,
+            start[2] + (end[2] - start[2]) * t,
+            1.,
+        )
+    }
+}
+
+impl AnyColorRange for Colormap {
+    fn at_linear(&self, factor: f32) -> LinearRgba {
+        self.at(factor).into()
+    }
+}
+
+const VIRIDIS: &[[f32; 3]] = &[
+    [0.267004, 0.004874, 0.329415],
+    [0.272546, 0.049046, 0.373286],
+    [0.276681, 0.092754, 0.414503],
+    [0.278792, 0.135362, 0.451193],
+    [0.278431, 0.176471, 0.482353],
+    [0.270776, 0.215310, 0.505964],
+    [0.260290, 0.252384, 0.523900],
+    [0.247065, 0.287754, 0.536695],
+    [0.231373, 0.321569, 0.545098],
+    [0.218008, 0.354367, 0.551381],
+    [0.203225, 0.386053, 0.554959],
+    [0.187778, 0.416872, 0.556587],
+    [0.172549, 0.447059, 0.556863],
+    [0.157189, 0.476752, 0.556240],
+    [0.144120, 0.506190, 0.554822],
+    [0.134475, 0.535488, 0.552525],
+    [0.129412, 0.564706, 0.549020],
+    [0.124095, 0.593500, 0.542826],
+    [0.125601, 0.622145, 0.534148],
+    [0.134930, 0.650516, 0.522133],
+    [0.152941, 0.678431, 0.505882],
+    [0.191195, 0.706604, 0.485471],
+    [0.239221, 0.733831, 0.459266],
+    [0.297140, 0.759829, 0.426851],
+    [0.364706, 0.784314, 0.388235],
+    [0.430396, 0.807059, 0.343888],
+    [0.503889, 0.827799, 0.295318],
+    [0.583442, 0.846379, 0.244870],
+    [0.666667, 0.862745, 0.196078],
+    [0.758418, 0.875908, 0.154060],
+    [0.846808, 0.887183, 0.125279],
+    [0.927018, 0.897030, 0.118305],
+    [0.993248, 0.906157, 0.143936],
+];
+
+const MAGMA: &[[f32; 3]] = &[
+    [0.001462, 0.000466, 0.013866],
+    [0.014953, 0.022437, 0.084530],
+    [0.040346, 0.039684, 0.153484],
+    [0.074148, 0.053985, 0.218692],
+    [0.113725, 0.066667, 0.278431],
+    [0.161796, 0.067193, 0.342959],
+    [0.212359, 0.067741, 0.399553],
+    [0.264502, 0.068772, 0.447467],
+    [0.317647, 0.070588, 0.486275],
+    [0.365143, 0.088371, 0.504218],
+    [0.413090, 0.107284, 0.513118],
+    [0.461353, 0.127454, 0.513433],
+    [0.509804, 0.149020, 0.505882],
+    [0.561534, 0.161712, 0.505297],
+    [0.613024, 0.176202, 0.498960],
+    [0.663915, 0.192771, 0.488206],
+    [0.713725, 0.211765, 0.474510],
+    [0.765170, 0.232933, 0.451891],
+    [0.814225, 0.257346, 0.429538],
+    [0.860101, 0.285444, 0.409092],
+    [0.901961, 0.317647, 0.392157],
+    [0.931531, 0.364432, 0.378587],
+    [0.955490, 0.415944, 0.371390],
+    [0.973215, 0.472285, 0.371705],
+    [0.984314, 0.533333, 0.380392],
+    [0.995605, 0.586273, 0.404750],
+    [1.000000, 0.642737, 0.438078],
+    [1.000000, 0.701498, 0.479962],
+    [0.996078, 0.760784, 0.529412],
+    [0.985319, 0.830319, 0.582680],
+    [0.976859, 0.894796, 0.639444],
+    [0.975408, 0.950178, 0.696451],
+    [0.987053, 0.991438, 0.749504],
+];
+
+const INFERNO: &[[f32; 3]] = &[
+    [0.001462, 0.000466, 0.013866],
+    [0.012470, 0.017542, 0.111771],
+    [0.039470, 0.029634, 0.185632],
+    [0.077170, 0.038928, 0.241077],
+    [0.121569, 0.047059, 0.282353],
+    [0.173098, 0.048258, 0.330817],
+    [0.226366, 0.050298, 0.370334],
+    [0.280042, 0.053713, 0.402281],
+    [0.333333, 0.058824, 0.427451],
+    [0.384650, 0.074516, 0.434027],
+    [0.435060, 0.092149, 0.434295],
+    [0.484591, 0.111749, 0.428208],
+    [0.533333, 0.133333, 0.415686],
+    [0.583604, 0.149632, 0.404114],
+    [0.633151, 0.168034, 0.386243],
+    [0.681847, 0.188677, 0.362436],
+    [0.729412, 0.211765, 0.333333],
+    [0.773609, 0.241205, 0.301900],
+    [0.815639, 0.273665, 0.267407],
+    [0.854775, 0.309487, 0.231481],
+    [0.890196, 0.349020, 0.196078],
+    [0.920473, 0.392540, 0.146103],
+    [0.945365, 0.440332, 0.101448],
+    [0.964181, 0.492515, 0.064857],
+    [0.976471, 0.549020, 0.039216],
+    [0.984514, 0.604992, 0.050601],
+    [0.986345, 0.664313, 0.078769],
+    [0.983030, 0.725919, 0.126451],
+    [0.976471, 0.788235, 0.196078],
+    [0.967646, 0.851289, 0.269970],
+    [0.962658, 0.909976, 0.369177],
+    [0.967125, 0.960564, 0.494291],
+    [0.988362, 0.998364, 0.644924],
+];
+
+const PLASMA: &[[f32; 3]] = &[
+    [0.050383, 0.029803, 0.527975],
+    [0.117965, 0.031218, 0.554546],
+    [0.181277, 0.024783, 0.582726],
+    [0.241103, 0.015722, 0.609126],
+    [0.298039, 0.007843, 0.631373],
+    [0.350095, 0.000065, 0.648117],
+    [0.400006, 0.000000, 0.658370],
+    [0.447975, 0.001540, 0.661878],
+    [0.494118, 0.011765, 0.658824],
+    [0.538950, 0.035942, 0.647306],
+    [0.582019, 0.065521, 0.630464],
+    [0.623298, 0.099622, 0.609161],
+    [0.662745, 0.137255, 0.584314],
+    [0.700011, 0.169963, 0.557872],
+    [0.735340, 0.204256, 0.529635],
+    [0.768684, 0.239319, 0.500339],
+    [0.800000, 0.274510, 0.470588],
+    [0.827681, 0.311499, 0.443543],
+    [0.853269, 0.347967, 0.416799],
+    [0.876734, 0.383929, 0.390510],
+    [0.898039, 0.419608, 0.364706],
+    [0.920248, 0.458177, 0.336887],
+    [0.940162, 0.497415, 0.309373],
+    [0.957657, 0.537945, 0.282055],
+    [0.972549, 0.580392, 0.254902],
+    [0.982825, 0.622355, 0.228537],
+    [0.989870, 0.667117, 0.202841],
+    [0.993195, 0.714709, 0.178547],
+    [0.992157, 0.764706, 0.156863],
+    [0.989804, 0.820985, 0.136290],
+    [0.981231, 0.876847, 0.122695],
+    [0.965156, 0.929539, 0.119532],
+    [0.940015, 0.975158, 0.131326],
+];
+
+const CIVIDIS: &[[f32; 3]] = &[
+    [0.000000, 0.135112, 0.304751],
+    [0.002336, 0.155238, 0.350614],
+    [0.008972, 0.175683, 0.381555],
+    [0.039665, 0.196376, 0.401153],
+    [0.077517, 0.217258, 0.412467],
+    [0.119901, 0.238285, 0.418064],
+    [0.164665, 0.259424, 0.420055],
+    [0.210101, 0.280653, 0.420134],
+    [0.254902, 0.301961, 0.419608],
+    [0.291872, 0.324077, 0.421913],
+    [0.326648, 0.346276, 0.425210],
+    [0.358902, 0.368571, 0.429856],
+    [0.388557, 0.390982, 0.435964],
+    [0.415748, 0.413534, 0.443436],
+    [0.440785, 0.436257, 0.451995],
+    [0.464112, 0.459185, 0.461221],
+    [0.486275, 0.482353, 0.470588],
+    [0.513877, 0.506485, 0.473983],
+    [0.541545, 0.530934, 0.476282],
+    [0.569890, 0.555737, 0.476845],
+    [0.599469, 0.580932, 0.475060],
+    [0.630747, 0.606554, 0.470385],
+    [0.664060, 0.632633, 0.462376],
+    [0.699575, 0.659199, 0.450725],
+    [0.737255, 0.686275, 0.435294],
+    [0.774531, 0.712346, 0.413012],
+    [0.813125, 0.738955, 0.387325],
+    [0.852155, 0.766102, 0.358812],
+    [0.890380, 0.793782, 0.328364],
+    [0.926161, 0.821978, 0.297213],
+    [0.957428, 0.850665, 0.266973],
+    [0.981638, 0.879804, 0.239669],
+    [0.995737, 0.909344, 0.217772],
+];
+
+const TURBO: &[[f32; 3]] = &[
+    [0.189950, 0.071760, 0.232170],
+    [0.289941, 0.146972, 0.556385],
+    [0.329412, 0.228888, 0.782688],
+    [0.327529, 0.315084, 0.928238],
+    [0.300383, 0.403250, 1.000000],
+    [0.261198, 0.491207, 1.000000],
+    [0.220547, 0.576908, 1.000000],
+    [0.186558, 0.658457, 0.983053],
+    [0.165134, 0.734115, 0.920572],
+    [0.160159, 0.802310, 0.844678],
+    [0.173712, 0.861646, 0.761572],
+    [0.206278, 0.910917, 0.676284],
+    [0.256963, 0.949115, 0.592766],
+    [0.323702, 0.975437, 0.513993],
+    [0.403474, 0.989302, 0.442058],
+    [0.492513, 0.990354, 0.378274],
+    [0.586520, 0.978477, 0.323267],
+    [0.680875, 0.953803, 0.277076],
+    [0.770848, 0.916724, 0.239253],
+    [0.851813, 0.867897, 0.208957],
+    [0.919461, 0.808262, 0.185054],
+    [0.970007, 0.739045, 0.166214],
+    [1.000000, 0.661773, 0.151009],
+    [1.000000, 0.578281, 0.138010],
+    [0.993560, 0.490724, 0.125889],
+    [0.955828, 0.401584, 0.113509],
+    [0.897405, 0.313687, 0.100030],
+    [0.822122, 0.230205, 0.085002],
+    [0.735823, 0.154671, 0.068462],
+    [0.646574, 0.090987, 0.051036],
+    [0.564878, 0.043437, 0.034034],
+    [0.503884, 0.016694, 0.019548],
+    [0.479600, 0.015830, 0.010550],
+];
+
+const COOLWARM: &[[f32; 3]] = &[
+    [0.229806, 0.298718, 0.753683],
+    [0.266231, 0.353094, 0.801468],
+    [0.303862, 0.406534, 0.844961],
+    [0.342795, 0.458755, 0.883729],
+    [0.383002, 0.509416, 0.917391],
+    [0.424356, 0.558145, 0.945623],
+    [0.466652, 0.604559, 0.968159],
+    [0.509619, 0.648277, 0.984792],
+    [0.552936, 0.688925, 0.995379],
+    [0.596245, 0.726144, 0.999838],
+    [0.639159, 0.759593, 0.998153],
+    [0.681275, 0.788957, 0.990364],
+    [0.722178, 0.813943, 0.976574],
+    [0.761451, 0.834291, 0.956943],
+    [0.798680, 0.849772, 0.931685],
+    [0.833458, 0.860191, 0.901064],
+    [0.865390, 0.865389, 0.865389],
+    [0.897756, 0.848913, 0.820864],
+    [0.924075, 0.827358, 0.774484],
+    [0.944399, 0.800899, 0.726704],
+    [0.958771, 0.769738, 0.677970],
+    [0.967237, 0.734104, 0.628710],
+    [0.969858, 0.694239, 0.579331],
+    [0.966713, 0.650395, 0.530218],
+    [0.957906, 0.602819, 0.481730],
+    [0.943568, 0.551732, 0.4341998],
+    [0.923858, 0.497293, 0.387930],
+    [0.898969, 0.439549, 0.343194],
+    [0.869121, 0.378307, 0.300237],
+    [0.834569, 0.312873, 0.259278],
+    [0.795596, 0.241286, 0.220509],
+    [0.752516, 0.157251, 0.184107],
+    [0.705673, 0.015557, 0.150233],
+];
+
+const RDBU: &[[f32; 3]] = &[
+    [0.403922, 0.000000, 0.121569],
+    [0.698039, 0.094118, 0.168627],
+    [0.839216, 0.376471, 0.301961],
+    [0.956863, 0.647059, 0.509804],
+    [0.992157, 0.858824, 0.780392],
+    [0.968627, 0.968627, 0.968627],
+    [0.819608, 0.898039, 0.941176],
+    [0.572549, 0.772549, 0.870588],
+    [0.262745, 0.576471, 0.764706],
+    [0.129412, 0.400000, 0.674510],
+    [0.019608, 0.188235, 0.380392],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_hex(map: Colormap, factor: f32, hex: &str) {
+        assert_hex_within(map, factor, hex, 1.5 / 255.);
+    }
+
+    fn assert_hex_within(map: Colormap, factor: f32, hex: &str, tolerance: f32) {
+        let expected = SRgba::hex(hex).unwrap();
+        let actual = map.at(factor);
+        for (a, e) in [
+            (actual.red, expected.red),
+            (actual.green, expected.green),
+            (actual.blue, expected.blue),
+        ] {
+            assert!(
+                (a - e).abs() <= tolerance,
+                "{} at {}: {:?} != {}",
+                map.name(),
+                factor,
+                actual,
+                hex
+            );
+        }
+    }
+
+    #[test]
+    fn reference_points() {
+        for (map, hexes) in [
+            (
+                Colormap::VIRIDIS,
+                ["440154", "3b528b", "21908c", "5dc863", "fde725"],
+            ),
+            (
+                Colormap::MAGMA,
+                ["000004", "51127c", "b63679", "fb8861", "fcfdbf"],
+            ),
+            (
+                Colormap::INFERNO,
+                ["000004", "550f6d", "ba3655", "f98c0a", "fcffa4"],
+            ),
+            (
+                Colormap::PLASMA,
+                ["0d0887", "7e03a8", "cc4678", "f89441", "f0f921"],
+            ),
+            (
+                Colormap::CIVIDIS,
+                ["00224e", "414d6b", "7c7b78", "bcaf6f", "fee838"],
+            ),
+        ] {
+            for (i, hex) in hexes.into_iter().enumerate() {
+                assert_hex(map, i as f32 / 4., hex);
+            }
+        }
+        for (factor, hex) in [
+            (0., "67001f"),
+            (0.3, "f4a582"),
+            (0.5, "f7f7f7"),
+            (0.7, "92c5de"),
+            (1., "053061"),
+        ] {
+            assert_hex(Colormap::RD_BU, factor, hex);
+        }
+        assert_hex(Colormap::TURBO, 0., "30123b");
+        assert_hex(Colormap::TURBO, 1., "7a0403");
+        assert_hex(Colormap::COOLWARM, 0., "3b4cc0");
+        assert_hex(Colormap::COOLWARM, 0.5, "dddddd");
+        assert_hex(Colormap::COOLWARM, 1., "b40426");
+    }
+
+    #[test]
+    fn between_nodes() {
+        // Reference colors at thirds and fifths of the range, which fall between the 33
+        // entries of the tables.
+        for (map, thirds, fifths) in [
+            (
+                Colormap::VIRIDIS,
+                ["31688e", "35b779"],
+                ["414487", "2a788e", "22a884", "7ad151"],
+            ),
+            (
+                Colormap::MAGMA,
+                ["721f81", "f1605d"],
+                ["3b0f70", "8c2981", "de4968", "fe9f6d"],
+            ),
+            (
+                Colormap::INFERNO,
+                ["781c6d", "ed6925"],
+                ["420a68", "932667", "dd513a", "fca50a"],
+            ),
+            (
+                Colormap::PLASMA,
+                ["9c179e", "ed7953"],
+                ["6a00a8", "b12a90", "e16462", "fca636"],
+            ),
+        ] {
+            for (i, hex) in thirds.into_iter().enumerate() {
+                assert_hex_within(map, (i + 1) as f32 / 3., hex, 0.03);
+            }
+            for (i, hex) in fifths.into_iter().enumerate() {
+                assert_hex_within(map, (i + 1) as f32 / 5., hex, 0.03);
+            }
+        }
+
+        // Moreland's diverging interpolation, evaluated directly.
+        for (factor, hex) in [
+            (0.1, "5978e3"),
+            (0.3, "9fbeff"),
+            (0.7, "f7ad8f"),
+            (0.9, "d75344"),
+        ] {
+            assert_hex(Colormap::COOLWARM, factor, hex);
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        // RdBu has 11 entries, so 0.05 is halfway between the first two.
+        let color = Colormap::RD_BU.at(0.05);
+        let [a, b] = [RDBU[0], RDBU[1]];
+        assert!((color.red - (a[0] + b[0]) / 2.).abs() < 1e-5);
+        assert!((color.blue - (a[2] + b[2]) / 2.).abs() < 1e-5);
+
+        assert_eq!(Colormap::VIRIDIS.at(-1.), Colormap::VIRIDIS.at(0.));
+        assert_eq!(Colormap::VIRIDIS.at(2.), Colormap::VIRIDIS.at(1.));
+    }
+
+    #[test]
+    fn reversed() {
+        for map in Colormap::ALL {
+            let reversed = map.reversed();
+            assert!(reversed.is_reversed());
+            assert_eq!(reversed.reversed(), map);
+            for factor in [0., 0.3, 0.5, 1.] {
+                assert_eq!(reversed.at(factor), map.at(1. - factor));
+            }
+        }
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(Colormap::from_name("viridis"), Some(Colormap::VIRIDIS));
+        assert_eq!(Colormap::from_name("RdBu"), Some(Colormap::RD_BU));
+        assert_eq!(
+            Colormap::from_name("Magma_r"),
+            Some(Colormap::MAGMA.reversed())
+        );
+        assert_eq!(Colormap::from_name("jet"), None);
+        assert_eq!(Colormap::from_name("r"), None);
+        assert_eq!(Colormap::from_name("éa"), None);
+        assert_eq!(Colormap::from_name("viridisé"), None);
+    }
+}
@@ -0,0 +1,481 @@
+use crate::{ColorRepresentation, Hsla, Lcha, LinearRgba, Oklaba, SRgba};
+use std::fmt;
+
+/// An error which occurs when parsing a CSS value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssParseError {
+    /// The value is not a function call, or is a function that isn't supported.
+    UnknownFunction(String),
+    /// A color could not be parsed.
+    InvalidColor(String),
+    /// A function argument could not be parsed.
+    InvalidArgument(String),
+    /// A gradient was specified with fewer than one color stop.
+    MissingColorStops,
+}
+
+impl fmt::Display for CssParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CssParseError::UnknownFunction(name) => write!(f, "Unknown CSS function: {name}"),
+            CssParseError::InvalidColor(color) => write!(f, "Invalid CSS color: {color}"),
+            CssParseError::InvalidArgument(arg) => write!(f, "Invalid CSS argument: {arg}"),
+            CssParseError::MissingColorStops => write!(f, "Gradient has no color stops"),
+        }
+    }
+}
+
+impl std::error::Error for CssParseError {}
+
+/// Split a CSS function call such as `rgb(1 2 3)` into its name and argument string.
+pub(crate) fn parse_function(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim();
+    let open = s.find('(')?;
+    let args = s[open + 1..].strip_suffix(')')?;
+    Some((s[..open].trim(), args))
+}
+
+/// Split a string on a separator, ignoring separators that are nested inside parentheses.
+/// Empty parts are discarded.
+pub(crate) fn split_top_level(s: &str, is_separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if depth == 0 && is_separator(c) => {
+                parts.push(s[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Split a string into whitespace-separated tokens, keeping function calls intact.
+pub(crate) fn split_tokens(s: &str) -> Vec<&str> {
+    split_top_level(s, char::is_whitespace)
+}
+
+pub(crate) fn parse_number(s: &str) -> Option<f32> {
+    if s.eq_ignore_ascii_case("none") {
+        return Some(0.);
+    }
+    s.parse::<f32>().ok().filter(|n| n.is_finite())
+}
+
+/// Parse a percentage, returning a fraction (i.e. `50%` is 0.5).
+pub(crate) fn parse_percentage(s: &str) -> Option<f32> {
+    parse_number(s.strip_suffix('%')?).map(|n| n / 100.)
+}
+
+/// Parse a CSS angle, returning degrees. A bare number is only accepted if `allow_number`
+/// is true, otherwise only `0` may omit the unit.
+pub(crate) fn parse_angle(s: &str, allow_number: bool) -> Option<f32> {
+    let s = s.to_ascii_lowercase();
+    if let Some(n) = s.strip_suffix("deg") {
+        parse_number(n)
+    } else if let Some(n) = s.strip_suffix("grad") {
+        parse_number(n).map(|n| n * 0.9)
+    } else if let Some(n) = s.strip_suffix("rad") {
+        parse_number(n).map(f32::to_degrees)
+    } else if let Some(n) = s.strip_suffix("turn") {
+        parse_number(n).map(|n| n * 360.)
+    } else {
+        parse_number(&s).filter(|n| allow_number || *n == 0.)
+    }
+}
+
+/// Parse a number or percentage, where 100% is equal to `scale`.
+fn parse_number_or_percentage(s: &str, scale: f32) -> Option<f32> {
+    parse_percentage(s)
+        .map(|n| n * scale)
+        .or_else(|| parse_number(s))
+}
+
+/// Split the arguments of a color function into channels and an optional alpha. Accepts
+/// both the legacy comma syntax and the modern space-separated syntax with a `/` before
+/// alpha. A fourth space-separated channel is also treated as alpha, which is the format
+/// produced by [`crate::ToCssString`].
+fn color_channels(args: &str) -> Option<([&str; 3], Option<&str>)> {
+    let (channels, alpha) = if args.contains(',') {
+        let mut parts = split_top_level(args, |c| c == ',');
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    } else if let Some((channels, alpha)) = args.split_once('/') {
+        (split_tokens(channels), Some(alpha.trim()))
+    } else {
+        let mut parts = split_tokens(args);
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    };
+    match channels[..] {
+        [a, b, c] => Some(([a, b, c], alpha)),
+        _ => None,
+    }
+}
+
+fn parse_alpha(alpha: Option<&str>) -> Option<f32> {
+    match alpha {
+        Some(alpha) => parse_number_or_percentage(alpha, 1.).map(|a| a.clamp(0., 1.)),
+        None => Some(1.),
+    }
+}
+
+/// Parse a CSS color value. Supported formats are hex colors, named colors, `rgb()`,
+/// `hsl()`, `oklab()`, `oklch()`, `lch()` and `color()` with the `srgb`, `srgb-linear`,
+/// `oklab` and `lch` color spaces.
+///
+/// `lch()` uses CSS units, where lightness is 0 to 100 and 100% chroma is 150, while
+/// `color(lch ...)` uses the units of [`Lcha`], which is the format produced by
+/// [`crate::ToCssString`].
+pub(crate) fn parse_css_color(s: &str) -> Result<ColorRepresentation, CssParseError> {
+    let s = s.trim();
+    let invalid = || CssParseError::InvalidColor(s.to_string());
+    if s.starts_with('#') {
+        return SRgba::hex(s)
+            .map(ColorRepresentation::SRgba)
+            .map_err(|_| invalid());
+    }
+
+    let Some((name, args)) = parse_function(s) else {
+        let name = s.to_ascii_lowercase();
+        if name == "transparent" {
+            return Ok(ColorRepresentation::SRgba(SRgba::NONE));
+        }
+        return NAMED_COLORS
+            .binary_search_by_key(&name.as_str(), |(name, _)| name)
+            .map(|i| {
+                let [r, g, b] = NAMED_COLORS[i].1;
+                ColorRepresentation::SRgba(SRgba::rgb_u8(r, g, b))
+            })
+            .map_err(|_| invalid());
+    };
+
+    let name = name.to_ascii_lowercase();
+    let is_color_function = name == "color";
+    let (space, args) = if is_color_function {
+        let args = args.trim_start();
+        let end = args.find(char::is_whitespace).ok_or_else(invalid)?;
+        (args[..end].to_ascii_lowercase(), &args[end..])
+    } else {
+        (name, args)
+    };
+    let ([c0, c1, c2], alpha) = color_channels(args).ok_or_else(invalid)?;
+    let alpha = parse_alpha(alpha).ok_or_else(invalid)?;
+
+    let color = match space.as_str() {
+        "rgb" | "rgba" => (|| {
+            Some(ColorRepresentation::SRgba(SRgba::new(
+                parse_number_or_percentage(c0, 255.)? / 255.,
+                parse_number_or_percentage(c1, 255.)? / 255.,
+                parse_number_or_percentage(c2, 255.)? / 255.,
+                alpha,
+            )))
+        })(),
+        "srgb" => (|| {
+            Some(ColorRepresentation::SRgba(SRgba::new(
+                parse_number_or_percentage(c0, 1.)?,
+                parse_number_or_percentage(c1, 1.)?,
+                parse_number_or_percentage(c2, 1.)?,
+                alpha,
+            )))
+        })(),
+        "srgb-linear" => (|| {
+            Some(ColorRepresentation::LinearRgba(LinearRgba::new(
+                parse_number_or_percentage(c0, 1.)?,
+                parse_number_or_percentage(c1, 1.)?,
+                parse_number_or_percentage(c2, 1.)?,
+                alpha,
+            )))
+        })(),
+        "hsl" | "hsla" => (|| {
+            Some(ColorRepresentation::Hsla(Hsla::new(
+                parse_angle(c0, true)?.rem_euclid(360.),
+                parse_number_or_percentage(c1, 100.)? / 100.,
+                parse_number_or_percentage(c2, 100.)? / 100.,
+                alpha,
+            )))
+        })(),
+        "oklab" => (|| {
+            Some(ColorRepresentation::Oklaba(Oklaba::new(
+                parse_number_or_percentage(c0, 1.)?,
+                parse_number_or_percentage(c1, 0.4)?,
+                parse_number_or_percentage(c2, 0.4)?,
+                alpha,
+            )))
+        })(),
+        "oklch" => (|| {
+            let l = parse_number_or_percentage(c0, 1.)?;
+            let chroma = parse_number_or_percentage(c1, 0.4)?;
+            let (sin, cos) = parse_angle(c2, true)?.to_radians().sin_cos();
+            Some(ColorRepresentation::Oklaba(Oklaba::new(
+                l,
+                chroma * cos,
+                chroma * sin,
+                alpha,
+            )))
+        })(),
+        "lch" if is_color_function => (|| {
+            Some(ColorRepresentation::Lcha(Lcha::new(
+                parse_number_or_percentage(c0, 1.)?,
+                parse_number(c1)?,
+                parse_angle(c2, true)?.rem_euclid(360.),
+                alpha,
+            )))
+        })(),
+        "lch" => (|| {
+            Some(ColorRepresentation::Lcha(Lcha::new(
+                parse_number_or_percentage(c0, 100.)? / 100.,
+                parse_number_or_percentage(c1, 150.)? / 100.,
+                parse_angle(c2, true)?.rem_euclid(360.),
+                alpha,
+            )))
+        })(),
+        _ => None,
+    };
+    color.ok_or_else(invalid)
+}
+
+/// Named colors from [CSS Color 4](https://www.w3.org/TR/css-color-4/#named-colors),
+/// sorted by name.
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color_difference::EuclideanDistance, testing::assert_approx_eq, ToCssString};
+
+    fn srgba(s: &str) -> SRgba {
+        match parse_css_color(s) {
+            Ok(ColorRepresentation::SRgba(color)) => color,
+            other => panic!("{s}: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn named_colors_are_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn parse_srgb() {
+        assert_eq!(srgba("red"), SRgba::RED);
+        assert_eq!(srgba("RebeccaPurple"), SRgba::rgb_u8(102, 51, 153));
+        assert_eq!(srgba("transparent"), SRgba::NONE);
+        assert_eq!(srgba("#03a9f4"), SRgba::rgb_u8(3, 169, 244));
+        assert_eq!(srgba("rgb(255, 0, 0)"), SRgba::RED);
+        assert_eq!(srgba("rgba(255 0 0 / 50%)"), SRgba::new(1., 0., 0., 0.5));
+        assert_eq!(srgba("rgb(100% 0% 100%)"), SRgba::FUCHSIA);
+        assert_eq!(srgba("color(srgb 1 0.5 0)"), SRgba::new(1., 0.5, 0., 1.));
+        assert!(parse_css_color("reddish").is_err());
+        assert!(parse_css_color("rgb(1, 2)").is_err());
+        assert!(parse_css_color("#ggg").is_err());
+    }
+
+    #[test]
+    fn parse_other_spaces() {
+        let Ok(ColorRepresentation::Hsla(hsla)) = parse_css_color("hsl(240deg 100% 50%)") else {
+            panic!()
+        };
+        assert_eq!(hsla, Hsla::new(240., 1., 0.5, 1.));
+
+        let Ok(ColorRepresentation::Oklaba(oklab)) = parse_css_color("oklab(50% 0.1 -0.1 / 0.5)")
+        else {
+            panic!()
+        };
+        assert_eq!(oklab, Oklaba::new(0.5, 0.1, -0.1, 0.5));
+
+        let Ok(ColorRepresentation::Oklaba(oklch)) = parse_css_color("oklch(0.5 0.2 90deg)") else {
+            panic!()
+        };
+        assert_approx_eq!(oklch.a, 0., 0.0001);
+        assert_approx_eq!(oklch.b, 0.2, 0.0001);
+    }
+
+    #[test]
+    fn parse_lch() {
+        let lcha = |s: &str| match parse_css_color(s) {
+            Ok(ColorRepresentation::Lcha(color)) => color,
+            other => panic!("{s}: {other:?}"),
+        };
+        let expected = Lcha::new(0.5, 0.3, 40., 1.);
+        assert_eq!(lcha("lch(50 30 40)"), expected);
+        assert_eq!(lcha("lch(50% 30 40deg)"), expected);
+        assert_eq!(lcha("lch(50% 20% 40)"), expected);
+        assert_eq!(lcha("lch(50 30 40 / 50%)"), Lcha::new(0.5, 0.3, 40., 0.5));
+        assert_eq!(lcha("color(lch 50% 0.3 40)"), expected);
+        assert_eq!(lcha("color(lch 0.5 0.3 40)"), expected);
+    }
+
+    #[test]
+    fn round_trip() {
+        for color in [
+            ColorRepresentation::SRgba(SRgba::new(0.2, 0.4, 0.6, 0.8)),
+            ColorRepresentation::Hsla(Hsla::new(120., 0.5, 0.25, 1.0)),
+            ColorRepresentation::Oklaba(Oklaba::new(0.5, 0.1, -0.1, 1.0)),
+            ColorRepresentation::Lcha(Lcha::new(0.5, 0.3, 40., 1.0)),
+        ] {
+            let css = color.to_css_string();
+            let parsed = parse_css_color(&css).unwrap();
+            assert!(
+                parsed.linear().distance(&color.linear()) < 0.0001,
+                "{css}: {parsed:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,937 @@
+use crate::{
+    css::{
+        parse_angle, parse_css_color, parse_function, parse_number, split_tokens, split_top_level,
+        CssParseError,
+    },
+    ColorRepresentation, ColorStop, Gradient, HueInterpolation, InterpolationSpace, LinearRgba,
+    ToCssString,
+};
+use bevy::math::Vec2;
+
+/// A CSS length or percentage, such as `10px` or `50%`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LengthPercentage {
+    /// An absolute length in pixels.
+    Px(f32),
+    /// A percentage, where 100.0 is the full reference length.
+    Percent(f32),
+}
+
+impl LengthPercentage {
+    /// Resolve this value to pixels, given the reference length that 100% corresponds to.
+    pub fn resolve(&self, reference: f32) -> f32 {
+        match *self {
+            LengthPercentage::Px(px) => px,
+            LengthPercentage::Percent(percent) => percent * reference / 100.,
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        if let Some(percent) = s.strip_suffix('%') {
+            parse_number(percent).map(LengthPercentage::Percent)
+        } else if let Some(px) = s.strip_suffix("px") {
+            parse_number(px).map(LengthPercentage::Px)
+        } else {
+            parse_number(s)
+                .filter(|n| *n == 0.)
+                .map(LengthPercentage::Px)
+        }
+    }
+}
+
+impl ToCssString for LengthPercentage {
+    fn to_css_string(&self) -> String {
+        match self {
+            LengthPercentage::Px(px) => format!("{px}px"),
+            LengthPercentage::Percent(percent) => format!("{percent}%"),
+        }
+    }
+}
+
+/// The center of a radial or conic gradient, relative to the top-left corner of the box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CssPosition {
+    pub x: LengthPercentage,
+    pub y: LengthPercentage,
+}
+
+impl CssPosition {
+    /// The center of the box, which is the default position.
+    pub const CENTER: CssPosition = CssPosition {
+        x: LengthPercentage::Percent(50.),
+        y: LengthPercentage::Percent(50.),
+    };
+
+    /// Resolve this position to pixel coordinates within a box of the given size.
+    pub fn resolve(&self, size: Vec2) -> Vec2 {
+        Vec2::new(self.x.resolve(size.x), self.y.resolve(size.y))
+    }
+
+    fn parse(tokens: &[&str]) -> Option<Self> {
+        let keyword = |s: &str| match s.to_ascii_lowercase().as_str() {
+            "left" | "top" => Some(LengthPercentage::Percent(0.)),
+            "center" => Some(LengthPercentage::Percent(50.)),
+            "right" | "bottom" => Some(LengthPercentage::Percent(100.)),
+            _ => LengthPercentage::parse(s),
+        };
+        let is_vertical = |s: &str| matches!(s.to_ascii_lowercase().as_str(), "top" | "bottom");
+        let is_horizontal = |s: &str| matches!(s.to_ascii_lowercase().as_str(), "left" | "right");
+        match *tokens {
+            [a] if is_vertical(a) => Some(CssPosition {
+                x: LengthPercentage::Percent(50.),
+                y: keyword(a)?,
+            }),
+            [a] => Some(CssPosition {
+                x: keyword(a)?,
+                y: LengthPercentage::Percent(50.),
+            }),
+            [a, b] if is_vertical(a) || is_horizontal(b) => Some(CssPosition {
+                x: keyword(b)?,
+                y: keyword(a)?,
+            }),
+            [a, b] => Some(CssPosition {
+                x: keyword(a)?,
+                y: keyword(b)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Default for CssPosition {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+impl ToCssString for CssPosition {
+    fn to_css_string(&self) -> String {
+        format!("{} {}", self.x.to_css_string(), self.y.to_css_string())
+    }
+}
+
+/// The direction of a CSS linear gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinearGradientDirection {
+    /// An angle in degrees, where 0 points up and positive angles go clockwise.
+    Angle(f32),
+    /// Towards a corner of the box, such as `to top right`. The actual angle depends on the
+    /// aspect ratio of the box.
+    Corner { right: bool, bottom: bool },
+}
+
+impl LinearGradientDirection {
+    /// Resolve the direction to an angle in degrees for a box of the given size.
+    pub fn angle(&self, size: Vec2) -> f32 {
+        match *self {
+            LinearGradientDirection::Angle(angle) => angle,
+            LinearGradientDirection::Corner { right, bottom } => {
+                // The gradient line is perpendicular to the diagonal between the other two
+                // corners.
+                let sx = if right { size.y } else { -size.y };
+                let sy = if bottom { size.x } else { -size.x };
+                sx.atan2(-sy).to_degrees().rem_euclid(360.)
+            }
+        }
+    }
+}
+
+/// The shape of a CSS radial gradient.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RadialShape {
+    Circle,
+    #[default]
+    Ellipse,
+}
+
+/// The size of a CSS radial gradient's ending shape.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum RadialSize {
+    ClosestSide,
+    ClosestCorner,
+    FarthestSide,
+    #[default]
+    FarthestCorner,
+    /// Explicit horizontal and vertical radii. For circles, both radii are the same length.
+    Explicit(LengthPercentage, LengthPercentage),
+}
+
+/// The shape of a CSS gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CssGradientGeometry {
+    /// A `linear-gradient()`.
+    Linear(LinearGradientDirection),
+    /// A `radial-gradient()`.
+    Radial {
+        shape: RadialShape,
+        size: RadialSize,
+        center: CssPosition,
+    },
+    /// A `conic-gradient()`. The starting angle is in degrees, clockwise from the top.
+    Conic { from: f32, center: CssPosition },
+}
+
+impl CssGradientGeometry {
+    const fn function_name(&self) -> &'static str {
+        match self {
+            CssGradientGeometry::Linear(_) => "linear-gradient",
+            CssGradientGeometry::Radial { .. } => "radial-gradient",
+            CssGradientGeometry::Conic { .. } => "conic-gradient",
+        }
+    }
+
+    /// The length in pixels of the gradient line or ray for a box of the given size, which is
+    /// the reference length for percentages in color stop positions. For conic gradients
+    /// this is 360, so that stop positions can be expressed in degrees.
+    pub fn line_length(&self, size: Vec2) -> f32 {
+        match self {
+            CssGradientGeometry::Linear(direction) => {
+                let (sin, cos) = direction.angle(size).to_radians().sin_cos();
+                (size.x * sin).abs() + (size.y * cos).abs()
+            }
+            CssGradientGeometry::Radial {
+                shape,
+                size: radial_size,
+                center,
+            } => radial_radii(*shape, *radial_size, center.resolve(size), size).x,
+            CssGradientGeometry::Conic { .. } => 360.,
+        }
+    }
+
+    /// Compute the position along the gradient line of a point within a box of the given
+    /// size, where 0.0 is the start of the gradient line and 1.0 is the end.
+    pub fn position_of(&self, point: Vec2, size: Vec2) -> f32 {
+        match self {
+            CssGradientGeometry::Linear(direction) => {
+                let (sin, cos) = direction.angle(size).to_radians().sin_cos();
+                let length = (size.x * sin).abs() + (size.y * cos).abs();
+                if length <= 0. {
+                    return 0.;
+                }
+                // Screen coordinates have y pointing down, and 0deg points up.
+                let offset = point - size * 0.5;
+                (offset.x * sin - offset.y * cos) / length + 0.5
+            }
+            CssGradientGeometry::Radial {
+                shape,
+                size: radial_size,
+                center,
+            } => {
+                let center = center.resolve(size);
+                let radii =
+                    radial_radii(*shape, *radial_size, center, size).max(Vec2::splat(f32::EPSILON));
+                ((point - center) / radii).length()
+            }
+            CssGradientGeometry::Conic { from, center } => {
+                let offset = point - center.resolve(size);
+                let angle = offset.x.atan2(-offset.y).to_degrees();
+                (angle - from).rem_euclid(360.) / 360.
+            }
+        }
+    }
+}
+
+/// Compute the horizontal and vertical radii of a radial gradient's ending shape.
+fn radial_radii(shape: RadialShape, size: RadialSize, center: Vec2, bounds: Vec2) -> Vec2 {
+    let near = center.abs().min((bounds - center).abs());
+    let far = center.abs().max((bounds - center).abs());
+    let radii = match size {
+        RadialSize::ClosestSide | RadialSize::ClosestCorner => near,
+        RadialSize::FarthestSide | RadialSize::FarthestCorner => far,
+        RadialSize::Explicit(x, y) => {
+            return Vec2::new(x.resolve(bounds.x), y.resolve(bounds.y));
+        }
+    };
+    let corner = matches!(size, RadialSize::ClosestCorner | RadialSize::FarthestCorner);
+    match (shape, corner) {
+        (RadialShape::Circle, false) => {
+            let side = match size {
+                RadialSize::ClosestSide => radii.min_element(),
+                _ => radii.max_element(),
+            };
+            Vec2::splat(side)
+        }
+        (RadialShape::Circle, true) => Vec2::splat(radii.length()),
+        (RadialShape::Ellipse, false) => radii,
+        // An ellipse with the same aspect ratio as the side case, passing through the corner.
+        (RadialShape::Ellipse, true) => radii * std::f32::consts::SQRT_2,
+    }
+}
+
+/// A color stop in a CSS gradient, as written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CssColorStop {
+    pub color: ColorRepresentation,
+    /// The position of the stop. If omitted, stops are spaced evenly between their neighbors.
+    pub position: Option<LengthPercentage>,
+    /// An optional color hint between this stop and the next.
+    pub hint: Option<LengthPercentage>,
+}
+
+/// A parsed CSS gradient function, such as `linear-gradient(in oklch, red 0%, blue 100%)`.
+///
+/// Supported functions are `linear-gradient()`, `radial-gradient()`, `conic-gradient()`
+/// and their `repeating-` variants. If no interpolation method is given, colors are
+/// interpolated in sRGB, which matches how browsers render gradients of legacy colors.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{CssGradient, SRgba, ToCssString};
+/// # use bevy::math::Vec2;
+/// let gradient = CssGradient::parse("linear-gradient(to right in oklch, red, blue)").unwrap();
+/// let color: SRgba = gradient.at(Vec2::new(50.0, 10.0), Vec2::new(100.0, 20.0)).into();
+/// let css = gradient.to_css_string();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssGradient {
+    pub geometry: CssGradientGeometry,
+    pub repeating: bool,
+    pub space: InterpolationSpace,
+    pub hue_interpolation: HueInterpolation,
+    pub stops: Vec<CssColorStop>,
+}
+
+impl CssGradient {
+    /// Parse a CSS gradient function.
+    pub fn parse(s: &str) -> Result<Self, CssParseError> {
+        let (name, args) = parse_function(s)
+            .ok_or_else(|| CssParseError::UnknownFunction(s.trim().to_string()))?;
+        let lower = name.to_ascii_lowercase();
+        let (repeating, base) = match lower.strip_prefix("repeating-") {
+            Some(base) => (true, base),
+            None => (false, lower.as_str()),
+        };
+        let mut geometry = match base {
+            "linear-gradient" => CssGradientGeometry::Linear(LinearGradientDirection::Angle(180.)),
+            "radial-gradient" => CssGradientGeometry::Radial {
+                shape: RadialShape::default(),
+                size: RadialSize::default(),
+                center: CssPosition::CENTER,
+            },
+            "conic-gradient" => CssGradientGeometry::Conic {
+                from: 0.,
+                center: CssPosition::CENTER,
+            },
+            _ => return Err(CssParseError::UnknownFunction(name.to_string())),
+        };
+
+        let mut args = split_top_level(args, |c| c == ',');
+        let mut space = InterpolationSpace::SRgba;
+        let mut hue_interpolation = HueInterpolation::Shorter;
+        if let Some(first) = args.first() {
+            if let Some(config) = parse_config(&split_tokens(first), &geometry) {
+                (geometry, space, hue_interpolation) = config;
+                args.remove(0);
+            }
+        }
+
+        let is_conic = matches!(geometry, CssGradientGeometry::Conic { .. });
+        let stops = parse_stops(&args, is_conic)?;
+        Ok(Self {
+            geometry,
+            repeating,
+            space,
+            hue_interpolation,
+            stops,
+        })
+    }
+
+    /// Resolve the color stops into a [`Gradient`] for a gradient line of the given length in
+    /// pixels, following the CSS rules for stops with missing or out-of-order positions.
+    /// Positions in the resulting gradient range from 0.0 at the start of the gradient line
+    /// to 1.0 at the end, but may extend past either end.
+    pub fn to_gradient(&self, line_length: f32) -> Gradient {
+        let resolve = |lp: LengthPercentage| {
+            if line_length > 0. {
+                lp.resolve(line_length) / line_length
+            } else {
+                0.
+            }
+        };
+        let count = self.stops.len();
+        let mut positions: Vec<Option<f32>> = self
+            .stops
+            .iter()
+            .map(|stop| stop.position.map(resolve))
+            .collect();
+        if let Some(first) = positions.first_mut() {
+            first.get_or_insert(0.);
+        }
+        if let Some(last) = positions.last_mut() {
+            last.get_or_insert(1.);
+        }
+
+        // Positions may not decrease.
+        let mut max = f32::NEG_INFINITY;
+        for position in positions.iter_mut().flatten() {
+            max = max.max(*position);
+            *position = max;
+        }
+
+        // Space runs of stops without positions evenly between their neighbors.
+        let mut i = 0;
+        while i < count {
+            if positions[i].is_some() {
+                i += 1;
+                continue;
+            }
+            let start = i - 1;
+            let end = (i..count).find(|&j| positions[j].is_some()).unwrap();
+            let (from, to) = (positions[start].unwrap(), positions[end].unwrap());
+            for (j, position) in positions.iter_mut().enumerate().take(end).skip(i) {
+                let factor = (j - start) as f32 / (end - start) as f32;
+                *position = Some(from + (to - from) * factor);
+            }
+            i = end;
+        }
+
+        Gradient::new(self.stops.iter().zip(positions).map(|(stop, position)| {
+            let position = position.unwrap();
+            let stop_color = ColorStop::new(stop.color.linear(), position);
+            match stop.hint {
+                Some(hint) => stop_color.with_hint(resolve(hint)),
+                None => stop_color,
+            }
+        }))
+        .with_space(self.space)
+        .with_hue_interpolation(self.hue_interpolation)
+    }
+
+    /// Compute the color of this gradient at a point within a box of the given size, where
+    /// (0, 0) is the top-left corner of the box.
+    ///
+    /// This resolves the color stops on every call, so when evaluating many points in the
+    /// same box it is cheaper to call [`CssGradient::to_gradient`] once and sample it using
+    /// [`CssGradient::gradient_position`].
+    pub fn at(&self, point: Vec2, size: Vec2) -> LinearRgba {
+        let gradient = self.to_gradient(self.geometry.line_length(size));
+        gradient.at(self.gradient_position(&gradient, point, size))
+    }
+
+    /// Compute the position within a resolved gradient of a point within a box of the given
+    /// size, taking repetition into account.
+    pub fn gradient_position(&self, gradient: &Gradient, point: Vec2, size: Vec2) -> f32 {
+        let position = self.geometry.position_of(point, size);
+        match (
+            self.repeating,
+            gradient.stops().first(),
+            gradient.stops().last(),
+        ) {
+            (true, Some(first), Some(last)) if last.position > first.position => {
+                let period = last.position - first.position;
+                first.position + (position - first.position).rem_euclid(period)
+            }
+            _ => position,
+        }
+    }
+}
+
+impl ToCssString for CssGradient {
+    fn to_css_string(&self) -> String {
+        let mut config = match self.geometry {
+            CssGradientGeometry::Linear(LinearGradientDirection::Angle(angle)) => {
+                format!("{angle}deg")
+            }
+            CssGradientGeometry::Linear(LinearGradientDirection::Corner { right, bottom }) => {
+                format!(
+                    "to {} {}",
+                    if bottom { "bottom" } else { "top" },
+                    if right { "right" } else { "left" }
+                )
+            }
+            CssGradientGeometry::Radial {
+                shape,
+                size,
+                center,
+            } => {
+                let shape_name = match shape {
+                    RadialShape::Circle => "circle",
+                    RadialShape::Ellipse => "ellipse",
+                };
+                let size = match (shape, size) {
+                    (_, RadialSize::ClosestSide) => "closest-side".to_string(),
+                    (_, RadialSize::ClosestCorner) => "closest-corner".to_string(),
+                    (_, RadialSize::FarthestSide) => "farthest-side".to_string(),
+                    (_, RadialSize::FarthestCorner) => "farthest-corner".to_string(),
+                    (RadialShape::Circle, RadialSize::Explicit(radius, _)) => {
+                        radius.to_css_string()
+                    }
+                    (RadialShape::Ellipse, RadialSize::Explicit(x, y)) => {
+                        format!("{} {}", x.to_css_string(), y.to_css_string())
+                    }
+                };
+                format!("{shape_name} {size} at {}", center.to_css_string())
+            }
+            CssGradientGeometry::Conic { from, center } => {
+                format!("from {from}deg at {}", center.to_css_string())
+            }
+        };
+        if self.space != InterpolationSpace::SRgba {
+            config.push_str(" in ");
+            config.push_str(space_name(self.space));
+            if self.space.is_polar() && self.hue_interpolation != HueInterpolation::Shorter {
+                config.push(' ');
+                config.push_str(hue_interpolation_name(self.hue_interpolation));
+                config.push_str(" hue");
+            }
+        }
+
+        let mut args = vec![config];
+        for stop in &self.stops {
+            let mut arg = stop.color.to_css_string();
+            if let Some(position) = stop.position {
+                arg.push(' ');
+                arg.push_str(&position.to_css_string());
+            }
+            args.push(arg);
+            if let Some(hint) = stop.hint {
+                args.push(hint.to_css_string());
+            }
+        }
+
+        format!(
+            "{}{}({})",
+            if self.repeating { "repeating-" } else { "" },
+            self.geometry.function_name(),
+            args.join(", ")
+        )
+    }
+}
+
+fn space_name(space: InterpolationSpace) -> &'static str {
+    match space {
+        InterpolationSpace::SRgba => "srgb",
+        InterpolationSpace::LinearRgba => "srgb-linear",
+        InterpolationSpace::Oklaba => "oklab",
+        InterpolationSpace::Oklcha => "oklch",
+        InterpolationSpace::Hsla => "hsl",
+        InterpolationSpace::Lcha => "lch",
+        InterpolationSpace::Laba => "lab",
+    }
+}
+
+fn hue_interpolation_name(hue_interpolation: HueInterpolation) -> &'static str {
+    match hue_interpolation {
+        HueInterpolation::Shorter => "shorter",
+        HueInterpolation::Longer => "longer",
+        HueInterpolation::Increasing => "increasing",
+        HueInterpolation::Decreasing => "decreasing",
+    }
+}
+
+/// Parse the first argument of a gradient function, which describes its geometry and
+/// interpolation method. Returns `None` if the argument is not a valid configuration, in
+/// which case it should be treated as a color stop.
+fn parse_config(
+    tokens: &[&str],
+    geometry: &CssGradientGeometry,
+) -> Option<(CssGradientGeometry, InterpolationSpace, HueInterpolation)> {
+    let mut geometry = *geometry;
+    let mut space = InterpolationSpace::SRgba;
+    let mut hue_interpolation = HueInterpolation::Shorter;
+    let mut lengths = Vec::new();
+    let mut explicit_ellipse = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i].to_ascii_lowercase();
+        i += 1;
+        match (token.as_str(), &mut geometry) {
+            ("in", _) => {
+                space = match tokens.get(i)?.to_ascii_lowercase().as_str() {
+                    "srgb" => InterpolationSpace::SRgba,
+                    "srgb-linear" => InterpolationSpace::LinearRgba,
+                    "oklab" => InterpolationSpace::Oklaba,
+                    "oklch" => InterpolationSpace::Oklcha,
+                    "hsl" => InterpolationSpace::Hsla,
+                    "lch" => InterpolationSpace::Lcha,
+                    "lab" => InterpolationSpace::Laba,
+                    _ => return None,
+                };
+                i += 1;
+                let hue_follows = tokens
+                    .get(i + 1)
+                    .is_some_and(|t| t.eq_ignore_ascii_case("hue"));
+                if space.is_polar() && hue_follows {
+                    hue_interpolation = match tokens[i].to_ascii_lowercase().as_str() {
+                        "shorter" => HueInterpolation::Shorter,
+                        "longer" => HueInterpolation::Longer,
+                        "increasing" => HueInterpolation::Increasing,
+                        "decreasing" => HueInterpolation::Decreasing,
+                        _ => return None,
+                    };
+                    i += 2;
+                }
+            }
+            ("to", CssGradientGeometry::Linear(direction)) => {
+                let mut right = None;
+                let mut bottom = None;
+                while let Some(side) = tokens.get(i) {
+                    match side.to_ascii_lowercase().as_str() {
+                        "left" if right.is_none() => right = Some(false),
+                        "right" if right.is_none() => right = Some(true),
+                        "top" if bottom.is_none() => bottom = Some(false),
+                        "bottom" if bottom.is_none() => bottom = Some(true),
+                        _ => break,
+                    }
+                    i += 1;
+                }
+                *direction = match (right, bottom) {
+                    (Some(right), Some(bottom)) => {
+                        LinearGradientDirection::Corner { right, bottom }
+                    }
+                    (Some(true), None) => LinearGradientDirection::Angle(90.),
+                    (Some(false), None) => LinearGradientDirection::Angle(270.),
+                    (None, Some(false)) => LinearGradientDirection::Angle(0.),
+                    (None, Some(true)) => LinearGradientDirection::Angle(180.),
+                    (None, None) => return None,
+                };
+            }
+            ("from", CssGradientGeometry::Conic { from, .. }) => {
+                *from = parse_angle(tokens.get(i)?, false)?;
+                i += 1;
+            }
+            ("at", CssGradientGeometry::Radial { center, .. })
+            | ("at", CssGradientGeometry::Conic { center, .. }) => {
+                let end = tokens[i..]
+                    .iter()
+                    .position(|t| t.eq_ignore_ascii_case("in"))
+                    .map_or(tokens.len(), |n| i + n);
+                *center = CssPosition::parse(&tokens[i..end])?;
+                i = end;
+            }
+            ("circle", CssGradientGeometry::Radial { shape, .. }) => *shape = RadialShape::Circle,
+            ("ellipse", CssGradientGeometry::Radial { shape, .. }) => {
+                *shape = RadialShape::Ellipse;
+                explicit_ellipse = true;
+            }
+            ("closest-side", CssGradientGeometry::Radial { size, .. }) => {
+                *size = RadialSize::ClosestSide;
+            }
+            ("closest-corner", CssGradientGeometry::Radial { size, .. }) => {
+                *size = RadialSize::ClosestCorner;
+            }
+            ("farthest-side", CssGradientGeometry::Radial { size, .. }) => {
+                *size = RadialSize::FarthestSide;
+            }
+            ("farthest-corner", CssGradientGeometry::Radial { size, .. }) => {
+                *size = RadialSize::FarthestCorner;
+            }
+            (_, CssGradientGeometry::Linear(direction)) => {
+                *direction = LinearGradientDirection::Angle(parse_angle(&token, false)?);
+            }
+            (_, CssGradientGeometry::Radial { .. }) => {
+                lengths.push(LengthPercentage::parse(&token)?);
+            }
+            _ => return None,
+        }
+    }
+
+    if let CssGradientGeometry::Radial { shape, size, .. } = &mut geometry {
+        match lengths[..] {
+            [] => {}
+            // A single length is a circle radius.
+            [radius] if !explicit_ellipse => {
+                *shape = RadialShape::Circle;
+                *size = RadialSize::Explicit(radius, radius);
+            }
+            [x, y] => {
+                *shape = RadialShape::Ellipse;
+                *size = RadialSize::Explicit(x, y);
+            }
+            _ => return None,
+        }
+    }
+    Some((geometry, space, hue_interpolation))
+}
+
+/// Parse the color stops and color hints of a gradient.
+fn parse_stops(args: &[&str], is_conic: bool) -> Result<Vec<CssColorStop>, CssParseError> {
+    let parse_position = |s: &str| {
+        if is_conic {
+            parse_angle(s, false)
+                .map(|angle| LengthPercentage::Percent(angle * 100. / 360.))
+                .or_else(|| {
+                    let percent = s.strip_suffix('%')?;
+                    parse_number(percent).map(LengthPercentage::Percent)
+                })
+        } else {
+            LengthPercentage::parse(s)
+        }
+        .ok_or_else(|| CssParseError::InvalidArgument(s.to_string()))
+    };
+
+    let mut stops: Vec<CssColorStop> = Vec::new();
+    for arg in args {
+        let tokens = split_tokens(arg);
+        // A lone position is a color hint for the preceding stop.
+        if let [position] = tokens[..] {
+            if parse_css_color(position).is_err() {
+                let hint = parse_position(position)?;
+                match stops.last_mut() {
+                    Some(stop) if stop.hint.is_none() => stop.hint = Some(hint),
+                    _ => return Err(CssParseError::InvalidArgument(arg.to_string())),
+                }
+                continue;
+            }
+        }
+        let (color, positions) = tokens
+            .split_first()
+            .ok_or_else(|| CssParseError::InvalidArgument(arg.to_string()))?;
+        let color = parse_css_color(color)?;
+        match positions {
+            [] => stops.push(CssColorStop {
+                color,
+                position: None,
+                hint: None,
+            }),
+            // A stop with two positions is shorthand for two stops of the same color.
+            [first, rest @ ..] if rest.len() <= 1 => {
+                for position in std::iter::once(first).chain(rest) {
+                    stops.push(CssColorStop {
+                        color,
+                        position: Some(parse_position(position)?),
+                        hint: None,
+                    });
+                }
+            }
+            _ => return Err(CssParseError::InvalidArgument(arg.to_string())),
+        }
+    }
+
+    if stops.is_empty() {
+        return Err(CssParseError::MissingColorStops);
+    }
+    if stops.last().is_some_and(|stop| stop.hint.is_some()) {
+        return Err(CssParseError::InvalidArgument(
+            "color hint after the last stop".to_string(),
+        ));
+    }
+    Ok(stops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color_difference::EuclideanDistance, testing::assert_approx_eq, SRgba};
+
+    const SIZE: Vec2 = Vec2::new(200., 100.);
+
+    fn assert_color(color: LinearRgba, expected: SRgba) {
+        let expected: LinearRgba = expected.into();
+        assert!(
+            color.distance(&expected) < 0.001,
+            "{:?} != {:?}",
+            SRgba::from(color),
+            SRgba::from(expected)
+        );
+    }
+
+    #[test]
+    fn parse_linear() {
+        let gradient = CssGradient::parse("linear-gradient(in oklch, red 0%, blue 100%)").unwrap();
+        assert_eq!(
+            gradient.geometry,
+            CssGradientGeometry::Linear(LinearGradientDirection::Angle(180.))
+        );
+        assert_eq!(gradient.space, InterpolationSpace::Oklcha);
+        assert_eq!(gradient.stops.len(), 2);
+        assert_eq!(
+            gradient.stops[1].position,
+            Some(LengthPercentage::Percent(100.))
+        );
+
+        let gradient = CssGradient::parse(
+            "linear-gradient(45deg in hsl longer hue, red, 30%, rgb(0 0 255) 10px 20px)",
+        )
+        .unwrap();
+        assert_eq!(
+            gradient.geometry,
+            CssGradientGeometry::Linear(LinearGradientDirection::Angle(45.))
+        );
+        assert_eq!(gradient.hue_interpolation, HueInterpolation::Longer);
+        assert_eq!(gradient.stops.len(), 3);
+        assert_eq!(gradient.stops[0].hint, Some(LengthPercentage::Percent(30.)));
+        assert_eq!(gradient.stops[2].position, Some(LengthPercentage::Px(20.)));
+
+        let gradient = CssGradient::parse("linear-gradient(to top left, red, blue)").unwrap();
+        assert_eq!(
+            gradient.geometry,
+            CssGradientGeometry::Linear(LinearGradientDirection::Corner {
+                right: false,
+                bottom: false
+            })
+        );
+
+        // No configuration at all.
+        let gradient = CssGradient::parse("linear-gradient(red, #00f)").unwrap();
+        assert_eq!(gradient.space, InterpolationSpace::SRgba);
+        assert_eq!(gradient.stops.len(), 2);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            CssGradient::parse("linear-gradient()"),
+            Err(CssParseError::MissingColorStops)
+        );
+        assert!(matches!(
+            CssGradient::parse("sideways-gradient(red, blue)"),
+            Err(CssParseError::UnknownFunction(_))
+        ));
+        assert!(CssGradient::parse("linear-gradient(red, blurple)").is_err());
+        assert!(CssGradient::parse("linear-gradient(red, blue, 50%)").is_err());
+        assert!(CssGradient::parse("linear-gradient(red 1em, blue)").is_err());
+    }
+
+    #[test]
+    fn parse_radial_and_conic() {
+        let gradient = CssGradient::parse(
+            "repeating-radial-gradient(circle closest-side at 25% top, red, blue)",
+        )
+        .unwrap();
+        assert!(gradient.repeating);
+        assert_eq!(
+            gradient.geometry,
+            CssGradientGeometry::Radial {
+                shape: RadialShape::Circle,
+                size: RadialSize::ClosestSide,
+                center: CssPosition {
+                    x: LengthPercentage::Percent(25.),
+                    y: LengthPercentage::Percent(0.),
+                },
+            }
+        );
+
+        let gradient = CssGradient::parse("radial-gradient(10px 20%, red, blue)").unwrap();
+        assert!(matches!(
+            gradient.geometry,
+            CssGradientGeometry::Radial {
+                shape: RadialShape::Ellipse,
+                size: RadialSize::Explicit(
+                    LengthPercentage::Px(10.),
+                    LengthPercentage::Percent(20.)
+                ),
+                ..
+            }
+        ));
+
+        let gradient =
+            CssGradient::parse("conic-gradient(from 0.25turn at left in oklab, red, blue 180deg)")
+                .unwrap();
+        assert_eq!(
+            gradient.geometry,
+            CssGradientGeometry::Conic {
+                from: 90.,
+                center: CssPosition {
+                    x: LengthPercentage::Percent(0.),
+                    y: LengthPercentage::Percent(50.),
+                },
+            }
+        );
+        assert_eq!(gradient.space, InterpolationSpace::Oklaba);
+        assert_eq!(
+            gradient.stops[1].position,
+            Some(LengthPercentage::Percent(50.))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for css in [
+            "linear-gradient(90deg, rgba(255 0 0 1) 0%, rgba(0 0 255 1) 100%)",
+            "linear-gradient(to bottom right in oklch longer hue, rgba(255 0 0 1), 25%, rgba(0 0 255 1) 10px)",
+            "repeating-radial-gradient(circle 10px at 50% 50%, rgba(255 0 0 1), rgba(0 0 255 1) 20%)",
+            "radial-gradient(ellipse farthest-side at 0% 100% in srgb-linear, rgba(255 0 0 1), rgba(0 0 255 1))",
+            "conic-gradient(from 45deg at 10px 20px in hsl, hsl(0deg 100% 50% 1), hsl(120deg 100% 50% 1))",
+            "linear-gradient(45deg in lch increasing hue, rgba(255 0 0 1), rgba(0 0 255 1))",
+            "linear-gradient(45deg in lab, rgba(255 0 0 1), rgba(0 0 255 1))",
+        ] {
+            let gradient = CssGradient::parse(css).unwrap();
+            assert_eq!(gradient.to_css_string(), css);
+            assert_eq!(CssGradient::parse(&gradient.to_css_string()), Ok(gradient));
+        }
+    }
+
+    #[test]
+    fn resolve_positions() {
+        let gradient =
+            CssGradient::parse("linear-gradient(red 20%, lime, blue 10%, white, black 60px)")
+                .unwrap();
+        let resolved = gradient.to_gradient(200.);
+        // `blue` is clamped to the position of `lime`, and the rest are evenly spaced.
+        for (stop, expected) in resolved.stops().iter().zip([0.2, 0.2, 0.2, 0.25, 0.3]) {
+            assert_approx_eq!(stop.position, expected, 0.0001);
+        }
+        assert_eq!(resolved.stops()[2].color, SRgba::BLUE.into());
+    }
+
+    #[test]
+    fn evaluate_linear() {
+        let gradient = CssGradient::parse("linear-gradient(to right, red, blue)").unwrap();
+        assert_color(gradient.at(Vec2::new(0., 50.), SIZE), SRgba::RED);
+        assert_color(gradient.at(Vec2::new(200., 0.), SIZE), SRgba::BLUE);
+        assert_color(
+            gradient.at(Vec2::new(100., 0.), SIZE),
+            SRgba::new(0.5, 0., 0.5, 1.),
+        );
+
+        // Default direction is top to bottom.
+        let gradient = CssGradient::parse("linear-gradient(red, blue 50px)").unwrap();
+        assert_color(gradient.at(Vec2::new(10., 0.), SIZE), SRgba::RED);
+        assert_color(gradient.at(Vec2::new(10., 50.), SIZE), SRgba::BLUE);
+        assert_color(gradient.at(Vec2::new(10., 90.), SIZE), SRgba::BLUE);
+
+        // Corners are reached exactly, regardless of aspect ratio.
+        let gradient = CssGradient::parse("linear-gradient(to bottom right, red, blue)").unwrap();
+        assert_color(gradient.at(Vec2::ZERO, SIZE), SRgba::RED);
+        assert_color(gradient.at(SIZE, SIZE), SRgba::BLUE);
+        assert_color(
+            gradient.at(Vec2::new(200., 0.), SIZE),
+            SRgba::new(0.5, 0., 0.5, 1.),
+        );
+    }
+
+    #[test]
+    fn evaluate_radial_and_conic() {
+        let gradient =
+            CssGradient::parse("radial-gradient(circle 50px at 100px 50px, red, blue)").unwrap();
+        assert_color(gradient.at(Vec2::new(100., 50.), SIZE), SRgba::RED);
+        assert_color(
+            gradient.at(Vec2::new(125., 50.), SIZE),
+            SRgba::new(0.5, 0., 0.5, 1.),
+        );
+        assert_color(gradient.at(Vec2::new(100., 100.), SIZE), SRgba::BLUE);
+
+        let gradient = CssGradient::parse("radial-gradient(closest-side, red, blue)").unwrap();
+        assert_color(gradient.at(Vec2::new(200., 50.), SIZE), SRgba::BLUE);
+        assert_color(
+            gradient.at(Vec2::new(100., 75.), SIZE),
+            SRgba::new(0.5, 0., 0.5, 1.),
+        );
+
+        let gradient = CssGradient::parse("conic-gradient(red, blue)").unwrap();
+        assert_color(gradient.at(Vec2::new(100., 0.), SIZE), SRgba::RED);
+        assert_color(
+            gradient.at(Vec2::new(100., 100.), SIZE),
+            SRgba::new(0.5, 0., 0.5, 1.),
+        );
+        let position = gradient.geometry.position_of(Vec2::new(200., 50.), SIZE);
+        assert_approx_eq!(position, 0.25, 0.0001);
+    }
+
+    #[test]
+    fn evaluate_repeating() {
+        let gradient =
+            CssGradient::parse("repeating-linear-gradient(to right, red 0px, blue 50px)").unwrap();
+        assert_color(
+            gradient.at(Vec2::new(25., 0.), SIZE),
+            SRgba::new(0.5, 0., 0.5, 1.),
+        );
+        assert_color(
+            gradient.at(Vec2::new(125., 0.), SIZE),
+            SRgba::new(0.5, 0., 0.5, 1.),
+        );
+        assert_color(gradient.at(Vec2::new(150., 0.), SIZE), SRgba::RED);
+    }
+}
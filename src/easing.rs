@@ -0,0 +1,406 @@
+/// Easing functions which remap an interpolation factor, used to control the rate of change
+/// along a color gradient or transition. These include the CSS
+/// [easing functions](https://www.w3.org/TR/css-easing-1/) and Robert Penner's easing
+/// equations, in the form popularized by [easings.net](https://easings.net/).
+///
+/// Every easing maps 0.0 to 0.0 and 1.0 to 1.0, but the `Back` and `Elastic` curves overshoot
+/// outside of that range in between.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Easing {
+    /// No easing, the factor is unchanged.
+    #[default]
+    Linear,
+    /// Equivalent to CSS `ease`, `cubic-bezier(0.25, 0.1, 0.25, 1)`.
+    Ease,
+    /// Equivalent to CSS `ease-in`, `cubic-bezier(0.42, 0, 1, 1)`.
+    EaseIn,
+    /// Equivalent to CSS `ease-out`, `cubic-bezier(0, 0, 0.58, 1)`.
+    EaseOut,
+    /// Equivalent to CSS `ease-in-out`, `cubic-bezier(0.42, 0, 0.58, 1)`.
+    EaseInOut,
+    /// A cubic Bézier curve from (0, 0) to (1, 1), with control points (x1, y1) and (x2, y2).
+    /// The x coordinates should be between 0.0 and 1.0.
+    CubicBezier(f32, f32, f32, f32),
+    /// Penner quadratic ease-in, `t²`.
+    QuadIn,
+    /// Penner quadratic ease-out.
+    QuadOut,
+    /// Penner quadratic ease-in-out.
+    QuadInOut,
+    /// Penner cubic ease-in, `t³`.
+    CubicIn,
+    /// Penner cubic ease-out.
+    CubicOut,
+    /// Penner cubic ease-in-out.
+    CubicInOut,
+    /// Penner quartic ease-in, `t⁴`.
+    QuartIn,
+    /// Penner quartic ease-out.
+    QuartOut,
+    /// Penner quartic ease-in-out.
+    QuartInOut,
+    /// Penner quintic ease-in, `t⁵`.
+    QuintIn,
+    /// Penner quintic ease-out.
+    QuintOut,
+    /// Penner quintic ease-in-out.
+    QuintInOut,
+    /// Penner sinusoidal ease-in.
+    SineIn,
+    /// Penner sinusoidal ease-out.
+    SineOut,
+    /// Penner sinusoidal ease-in-out.
+    SineInOut,
+    /// Penner exponential ease-in.
+    ExpoIn,
+    /// Penner exponential ease-out.
+    ExpoOut,
+    /// Penner exponential ease-in-out.
+    ExpoInOut,
+    /// Penner circular ease-in.
+    CircIn,
+    /// Penner circular ease-out.
+    CircOut,
+    /// Penner circular ease-in-out.
+    CircInOut,
+    /// Penner ease-in which first backs up slightly below 0.0.
+    BackIn,
+    /// Penner ease-out which overshoots slightly above 1.0.
+    BackOut,
+    /// Penner ease-in-out which overshoots at both ends.
+    BackInOut,
+    /// Penner ease-in with a decaying oscillation, like a spring.
+    ElasticIn,
+    /// Penner ease-out with a decaying oscillation, like a spring.
+    ElasticOut,
+    /// Penner ease-in-out with a decaying oscillation at both ends.
+    ElasticInOut,
+    /// Penner ease-in which bounces off 0.0.
+    BounceIn,
+    /// Penner ease-out which bounces off 1.0, like a dropped ball.
+    BounceOut,
+    /// Penner ease-in-out which bounces at both ends.
+    BounceInOut,
+}
+
+impl Easing {
+    /// Remap the interpolation factor `t`, which should be between 0.0 and 1.0.
+    pub fn ease(&self, t: f32) -> f32 {
+        match *self {
+            Easing::Linear => t,
+            Easing::Ease => cubic_bezier(0.25, 0.1, 0.25, 1., t),
+            Easing::EaseIn => cubic_bezier(0.42, 0., 1., 1., t),
+            Easing::EaseOut => cubic_bezier(0., 0., 0.58, 1., t),
+            Easing::EaseInOut => cubic_bezier(0.42, 0., 0.58, 1., t),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+            Easing::QuadIn => t.powi(2),
+            Easing::QuadOut => ease_out(t, |t| t.powi(2)),
+            Easing::QuadInOut => ease_in_out(t, |t| t.powi(2)),
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => ease_out(t, |t| t.powi(3)),
+            Easing::CubicInOut => ease_in_out(t, |t| t.powi(3)),
+            Easing::QuartIn => t.powi(4),
+            Easing::QuartOut => ease_out(t, |t| t.powi(4)),
+            Easing::QuartInOut => ease_in_out(t, |t| t.powi(4)),
+            Easing::QuintIn => t.powi(5),
+            Easing::QuintOut => ease_out(t, |t| t.powi(5)),
+            Easing::QuintInOut => ease_in_out(t, |t| t.powi(5)),
+            Easing::SineIn => sine_in(t),
+            Easing::SineOut => ease_out(t, sine_in),
+            Easing::SineInOut => ease_in_out(t, sine_in),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => ease_out(t, expo_in),
+            Easing::ExpoInOut => ease_in_out(t, expo_in),
+            Easing::CircIn => circ_in(t),
+            Easing::CircOut => ease_out(t, circ_in),
+            Easing::CircInOut => ease_in_out(t, circ_in),
+            Easing::BackIn => back_in(BACK_OVERSHOOT, t),
+            Easing::BackOut => ease_out(t, |t| back_in(BACK_OVERSHOOT, t)),
+            // Penner's in-out variant uses a larger overshoot, since each half is compressed.
+            Easing::BackInOut => ease_in_out(t, |t| back_in(BACK_OVERSHOOT * 1.525, t)),
+            Easing::ElasticIn => elastic_in(0.3, t),
+            Easing::ElasticOut => ease_out(t, |t| elastic_in(0.3, t)),
+            // Likewise, the in-out variant uses a longer period.
+            Easing::ElasticInOut => ease_in_out(t, |t| elastic_in(0.45, t)),
+            Easing::BounceIn => ease_out(t, bounce_out),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => ease_in_out(t, |t| ease_out(t, bounce_out)),
+        }
+    }
+
+    /// Return the easing which traverses this one backwards, such that
+    /// `easing.reversed().ease(t) == 1.0 - easing.ease(1.0 - t)`. Ease-in curves become
+    /// ease-out curves and vice versa, while symmetric curves are unchanged.
+    pub fn reversed(&self) -> Easing {
+        match *self {
+            Easing::Linear => Easing::Linear,
+            Easing::Ease => Easing::CubicBezier(0.75, 0., 0.75, 0.9),
+            Easing::EaseIn => Easing::EaseOut,
+            Easing::EaseOut => Easing::EaseIn,
+            Easing::EaseInOut => Easing::EaseInOut,
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                Easing::CubicBezier(1. - x2, 1. - y2, 1. - x1, 1. - y1)
+            }
+            Easing::QuadIn => Easing::QuadOut,
+            Easing::QuadOut => Easing::QuadIn,
+            Easing::CubicIn => Easing::CubicOut,
+            Easing::CubicOut => Easing::CubicIn,
+            Easing::QuartIn => Easing::QuartOut,
+            Easing::QuartOut => Easing::QuartIn,
+            Easing::QuintIn => Easing::QuintOut,
+            Easing::QuintOut => Easing::QuintIn,
+            Easing::SineIn => Easing::SineOut,
+            Easing::SineOut => Easing::SineIn,
+            Easing::ExpoIn => Easing::ExpoOut,
+            Easing::ExpoOut => Easing::ExpoIn,
+            Easing::CircIn => Easing::CircOut,
+            Easing::CircOut => Easing::CircIn,
+            Easing::BackIn => Easing::BackOut,
+            Easing::BackOut => Easing::BackIn,
+            Easing::ElasticIn => Easing::ElasticOut,
+            Easing::ElasticOut => Easing::ElasticIn,
+            Easing::BounceIn => Easing::BounceOut,
+            Easing::BounceOut => Easing::BounceIn,
+            Easing::QuadInOut
+            | Easing::CubicInOut
+            | Easing::QuartInOut
+            | Easing::QuintInOut
+            | Easing::SineInOut
+            | Easing::ExpoInOut
+            | Easing::CircInOut
+            | Easing::BackInOut
+            | Easing::ElasticInOut
+            | Easing::BounceInOut => *self,
+        }
+    }
+}
+
+/// The default overshoot of the Penner `Back` curves, which gives a 10% overshoot.
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+/// Derive an ease-out curve from an ease-in curve, by rotating it 180°.
+fn ease_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    1. - ease_in(1. - t)
+}
+
+/// Derive an ease-in-out curve from an ease-in curve, by applying it to the first half and
+/// its ease-out counterpart to the second half.
+fn ease_in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(t * 2.) * 0.5
+    } else {
+        1. - ease_in(2. - t * 2.) * 0.5
+    }
+}
+
+fn sine_in(t: f32) -> f32 {
+    1. - (t * std::f32::consts::FRAC_PI_2).cos()
+}
+
+fn expo_in(t: f32) -> f32 {
+    if t <= 0. {
+        0.
+    } else {
+        2f32.powf(10. * t - 10.)
+    }
+}
+
+fn circ_in(t: f32) -> f32 {
+    1. - (1. - t * t).max(0.).sqrt()
+}
+
+fn back_in(overshoot: f32, t: f32) -> f32 {
+    t * t * ((overshoot + 1.) * t - overshoot)
+}
+
+fn elastic_in(period: f32, t: f32) -> f32 {
+    if t <= 0. || t >= 1. {
+        return t;
+    }
+    let phase = period / 4.;
+    -(2f32.powf(10. * t - 10.)) * ((t - 1. - phase) * std::f32::consts::TAU / period).sin()
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Evaluate a single coordinate of a cubic Bézier curve whose endpoints are 0 and 1.
+fn bezier_coord(p1: f32, p2: f32, s: f32) -> f32 {
+    let n = 1. - s;
+    3. * n * n * s * p1 + 3. * n * s * s * p2 + s * s * s
+}
+
+/// Evaluate a CSS-style cubic Bézier timing function at `x`, by solving for the curve
+/// parameter whose x coordinate is `x` and returning the matching y coordinate.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    if x <= 0. || x >= 1. {
+        return x;
+    }
+
+    // Newton's method converges quickly for most curves.
+    let mut s = x;
+    for _ in 0..8 {
+        let err = bezier_coord(x1, x2, s) - x;
+        if err.abs() < 1e-6 {
+            return bezier_coord(y1, y2, s);
+        }
+        let n = 1. - s;
+        let slope = 3. * n * n * x1 + 6. * n * s * (x2 - x1) + 3. * s * s * (1. - x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s -= err / slope;
+    }
+
+    // Fall back to bisection, since x is monotonic in s for control points in [0, 1].
+    let (mut lo, mut hi) = (0., 1.);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier_coord(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) * 0.5;
+    }
+    bezier_coord(y1, y2, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_approx_eq;
+
+    #[test]
+    fn endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicBezier(0.1, 0.7, 0.9, 0.2),
+        ]
+        .into_iter()
+        .chain(PENNER)
+        {
+            assert_approx_eq!(easing.ease(0.), 0., 0.0001);
+            assert_approx_eq!(easing.ease(1.), 1., 0.0001);
+        }
+    }
+
+    const PENNER: [Easing; 30] = [
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::QuintIn,
+        Easing::QuintOut,
+        Easing::QuintInOut,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    #[test]
+    fn penner_curves() {
+        // Reference values from the easings.net formulas.
+        assert_approx_eq!(Easing::QuadInOut.ease(0.25), 0.125, 0.0001);
+        assert_approx_eq!(Easing::CubicOut.ease(0.5), 0.875, 0.0001);
+        assert_approx_eq!(Easing::QuartInOut.ease(0.75), 0.96875, 0.0001);
+        assert_approx_eq!(Easing::QuintIn.ease(0.5), 0.03125, 0.0001);
+        assert_approx_eq!(Easing::SineIn.ease(0.5), 0.292893, 0.0001);
+        assert_approx_eq!(Easing::SineInOut.ease(0.25), 0.146447, 0.0001);
+        assert_approx_eq!(Easing::ExpoIn.ease(0.5), 0.03125, 0.0001);
+        assert_approx_eq!(Easing::ExpoInOut.ease(0.75), 0.984375, 0.0001);
+        assert_approx_eq!(Easing::CircOut.ease(0.5), 0.866025, 0.0001);
+        assert_approx_eq!(Easing::BackIn.ease(0.5), -0.087698, 0.0001);
+        assert_approx_eq!(Easing::BackInOut.ease(0.25), -0.099682, 0.0001);
+        assert_approx_eq!(Easing::ElasticOut.ease(0.5), 1.015625, 0.0001);
+        assert_approx_eq!(Easing::ElasticInOut.ease(0.25), 0.011969, 0.0001);
+        assert_approx_eq!(Easing::BounceOut.ease(0.5), 0.765625, 0.0001);
+        assert_approx_eq!(Easing::BounceIn.ease(0.5), 0.234375, 0.0001);
+        assert_approx_eq!(Easing::BounceInOut.ease(0.75), 0.882813, 0.0001);
+    }
+
+    #[test]
+    fn reversed() {
+        for easing in [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicBezier(0.1, 0.7, 0.9, 0.2),
+        ]
+        .into_iter()
+        .chain(PENNER)
+        {
+            for t in [0.1, 0.3, 0.5, 0.8] {
+                assert_approx_eq!(easing.reversed().ease(t), 1. - easing.ease(1. - t), 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn css_curves() {
+        assert_approx_eq!(Easing::Linear.ease(0.3), 0.3, 0.0001);
+        assert_approx_eq!(Easing::EaseInOut.ease(0.5), 0.5, 0.0001);
+        assert_approx_eq!(Easing::EaseIn.ease(0.5), 0.3154, 0.001);
+        assert_approx_eq!(Easing::EaseOut.ease(0.5), 0.6846, 0.001);
+        assert_approx_eq!(Easing::Ease.ease(0.5), 0.8024, 0.001);
+        assert_approx_eq!(
+            Easing::CubicBezier(1. / 3., 1. / 3., 2. / 3., 2. / 3.).ease(0.7),
+            0.7,
+            0.0001
+        );
+    }
+
+    #[test]
+    fn monotonic() {
+        let mut prev = 0.;
+        for i in 1..=100 {
+            let value = Easing::EaseInOut.ease(i as f32 / 100.);
+            assert!(value >= prev);
+            prev = value;
+        }
+    }
+}
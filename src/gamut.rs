@@ -0,0 +1,226 @@
+use crate::{math::dot, LinearRgba, Oklaba};
+
+/// Tolerance for colors which are just outside of the sRGB gamut due to rounding errors.
+const GAMUT_EPSILON: f32 = 1e-4;
+
+/// Returns true if a color is within the sRGB gamut.
+pub(crate) fn in_srgb_gamut(color: LinearRgba) -> bool {
+    [color.red, color.green, color.blue]
+        .iter()
+        .all(|c| (-GAMUT_EPSILON..=1. + GAMUT_EPSILON).contains(c))
+}
+
+/// Convert an Oklab lightness, chroma and hue in degrees into an [`Oklaba`] color.
+pub(crate) fn oklch(l: f32, chroma: f32, hue: f32, alpha: f32) -> Oklaba {
+    let (sin, cos) = hue.to_radians().sin_cos();
+    Oklaba::new(l, chroma * cos, chroma * sin, alpha)
+}
+
+/// The Oklab chroma and hue in degrees of a color.
+pub(crate) fn chroma_hue(color: Oklaba) -> (f32, f32) {
+    (
+        color.a.hypot(color.b),
+        color.b.atan2(color.a).to_degrees().rem_euclid(360.),
+    )
+}
+
+/// Find the largest Oklab chroma, up to `limit`, for which the given lightness and hue are
+/// within the sRGB gamut.
+pub(crate) fn max_srgb_chroma(l: f32, hue: f32, limit: f32) -> f32 {
+    let fits = |chroma| in_srgb_gamut(oklch(l, chroma, hue, 1.).into());
+    if fits(limit) {
+        return limit;
+    }
+    let (mut lo, mut hi) = (0., limit);
+    for _ in 0..20 {
+        let mid = (lo + hi) * 0.5;
+        if fits(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+// The functions below are a port of Björn Ottosson's `ok_color.h`, which computes the shape
+// of the sRGB gamut in Oklab for the Okhsv and Okhsl color spaces. `a` and `b` are always
+// a normalized hue direction, with a² + b² = 1.
+
+/// The lightness and chroma of the most saturated sRGB color of a hue.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Cusp {
+    pub(crate) l: f32,
+    pub(crate) c: f32,
+}
+
+impl Cusp {
+    /// The slopes of the gamut boundary from black (S) and from white (T) to the cusp.
+    pub(crate) fn to_st(self) -> (f32, f32) {
+        (self.c / self.l, self.c / (1. - self.l))
+    }
+}
+
+/// The toe function, which maps Oklab lightness to an estimate of CIE L* (from 0 to 1).
+pub(crate) fn toe(x: f32) -> f32 {
+    const K1: f32 = 0.206;
+    const K2: f32 = 0.03;
+    const K3: f32 = (1. + K1) / (1. + K2);
+    0.5 * (K3 * x - K1 + ((K3 * x - K1) * (K3 * x - K1) + 4. * K2 * K3 * x).sqrt())
+}
+
+/// The inverse of [`toe`].
+pub(crate) fn toe_inv(x: f32) -> f32 {
+    const K1: f32 = 0.206;
+    const K2: f32 = 0.03;
+    const K3: f32 = (1. + K1) / (1. + K2);
+    (x * x + K1 * x) / (K3 * (x + K2))
+}
+
+#[allow(clippy::excessive_precision)]
+const L_FROM_AB: [f32; 2] = [0.3963377774, 0.2158037573];
+#[allow(clippy::excessive_precision)]
+const M_FROM_AB: [f32; 2] = [-0.1055613458, -0.0638541728];
+#[allow(clippy::excessive_precision)]
+const S_FROM_AB: [f32; 2] = [-0.0894841775, -1.291485548];
+#[allow(clippy::excessive_precision)]
+const RED_FROM_LMS: [f32; 3] = [4.0767416621, -3.3077115913, 0.2309699292];
+#[allow(clippy::excessive_precision)]
+const GREEN_FROM_LMS: [f32; 3] = [-1.2684380046, 2.6097574011, -0.3413193965];
+#[allow(clippy::excessive_precision)]
+const BLUE_FROM_LMS: [f32; 3] = [-0.0041960863, -0.7034186147, 1.707614701];
+
+/// The derivatives of the cube roots of LMS along the direction (a, b).
+fn lms_directions(a: f32, b: f32) -> [f32; 3] {
+    [L_FROM_AB, M_FROM_AB, S_FROM_AB].map(|k| k[0] * a + k[1] * b)
+}
+
+/// The maximum saturation (C / L) of a hue within the sRGB gamut.
+#[allow(clippy::excessive_precision)]
+fn compute_max_saturation(a: f32, b: f32) -> f32 {
+    // Select the sRGB component which reaches zero first, and a polynomial approximation of
+    // the saturation where it does.
+    let (k, w) = if -1.88170328 * a - 0.80936493 * b > 1. {
+        (
+            [1.19086277, 1.76576728, 0.59662641, 0.75515197, 0.56771245],
+            RED_FROM_LMS,
+        )
+    } else if 1.81444104 * a - 1.19445276 * b > 1. {
+        (
+            [0.73956515, -0.45954404, 0.08285427, 0.1254107, 0.14503204],
+            GREEN_FROM_LMS,
+        )
+    } else {
+        (
+            [1.35733652, -0.00915799, -1.1513021, -0.50559606, 0.00692167],
+            BLUE_FROM_LMS,
+        )
+    };
+    let saturation = k[0] + k[1] * a + k[2] * b + k[3] * a * a + k[4] * a * b;
+
+    // One step of Halley's method to refine the approximation.
+    let k_lms = lms_directions(a, b);
+    let lms_ = k_lms.map(|k| 1. + saturation * k);
+    let lms = lms_.map(|x| x * x * x);
+    let lms_ds = [0, 1, 2].map(|i| 3. * k_lms[i] * lms_[i] * lms_[i]);
+    let lms_ds2 = [0, 1, 2].map(|i| 6. * k_lms[i] * k_lms[i] * lms_[i]);
+    let f = dot(w, lms);
+    let f1 = dot(w, lms_ds);
+    let f2 = dot(w, lms_ds2);
+    saturation - f * f1 / (f1 * f1 - 0.5 * f * f2)
+}
+
+/// Find the cusp of the sRGB gamut for a hue.
+pub(crate) fn find_cusp(a: f32, b: f32) -> Cusp {
+    let s_cusp = compute_max_saturation(a, b);
+    let rgb = LinearRgba::from(Oklaba::new(1., s_cusp * a, s_cusp * b, 1.));
+    let l = (1. / rgb.red.max(rgb.green).max(rgb.blue)).cbrt();
+    Cusp { l, c: l * s_cusp }
+}
+
+/// Find the intersection of the line from (L0, 0) to (L1, C1) with the sRGB gamut boundary,
+/// returned as the fraction `t` of the way along the line.
+pub(crate) fn find_gamut_intersection(
+    a: f32,
+    b: f32,
+    l1: f32,
+    c1: f32,
+    l0: f32,
+    cusp: Cusp,
+) -> f32 {
+    if (l1 - l0) * cusp.c - (cusp.l - l0) * c1 <= 0. {
+        // The lower half of the triangle, which is exact.
+        return cusp.c * l0 / (c1 * cusp.l + cusp.c * (l0 - l1));
+    }
+
+    // The upper half, first intersecting with the triangle, then refining with one step of
+    // Halley's method for each sRGB component.
+    let mut t = cusp.c * (l0 - 1.) / (c1 * (cusp.l - 1.) + cusp.c * (l0 - l1));
+    let k_lms = lms_directions(a, b);
+    let d_l = l1 - l0;
+    let lms_dt = k_lms.map(|k| d_l + c1 * k);
+
+    let l = l0 * (1. - t) + t * l1;
+    let c = t * c1;
+    let lms_ = k_lms.map(|k| l + c * k);
+    let lms = lms_.map(|x| x * x * x);
+    let lms_dt1 = [0, 1, 2].map(|i| 3. * lms_dt[i] * lms_[i] * lms_[i]);
+    let lms_dt2 = [0, 1, 2].map(|i| 6. * lms_dt[i] * lms_dt[i] * lms_[i]);
+
+    let step = |w: [f32; 3]| {
+        let f = dot(w, lms) - 1.;
+        let f1 = dot(w, lms_dt1);
+        let f2 = dot(w, lms_dt2);
+        let u = f1 / (f1 * f1 - 0.5 * f * f2);
+        if u >= 0. {
+            -f * u
+        } else {
+            f32::MAX
+        }
+    };
+    t += step(RED_FROM_LMS)
+        .min(step(GREEN_FROM_LMS))
+        .min(step(BLUE_FROM_LMS));
+    t
+}
+
+/// A smooth approximation of the slopes (S, T) of the gamut boundary, used to keep the
+/// middle of the Okhsl saturation scale free of the sharp corner at the cusp.
+#[allow(clippy::excessive_precision)]
+fn st_mid(a: f32, b: f32) -> (f32, f32) {
+    let s = 0.11516993
+        + 1. / (7.4477897
+            + 4.1590124 * b
+            + a * (-2.19557347
+                + 1.75198401 * b
+                + a * (-2.13704948 - 10.02301043 * b
+                    + a * (-4.24894561 + 5.38770819 * b + 4.69891013 * a))));
+    let t = 0.11239642
+        + 1. / (1.6132032 - 0.68124379 * b
+            + a * (0.40370612
+                + 0.90148123 * b
+                + a * (-0.27087943
+                    + 0.6122399 * b
+                    + a * (0.00299215 - 0.45399568 * b - 0.14661872 * a))));
+    (s, t)
+}
+
+/// The chroma at Okhsl saturations of 0%, 80% and 100%, for an Oklab lightness and hue.
+pub(crate) fn okhsl_chromas(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let cusp = find_cusp(a, b);
+    let c_max = find_gamut_intersection(a, b, l, 1., l, cusp);
+    let (s_max, t_max) = cusp.to_st();
+
+    // Scale factor to compensate for the curved part of the gamut shape.
+    let k = c_max / (l * s_max).min((1. - l) * t_max);
+
+    let (s_mid, t_mid) = st_mid(a, b);
+    let c_a = l * s_mid;
+    let c_b = (1. - l) * t_mid;
+    let c_mid = 0.9 * k * (1. / (1. / c_a.powi(4) + 1. / c_b.powi(4))).sqrt().sqrt();
+
+    let c_a = l * 0.4;
+    let c_b = (1. - l) * 0.8;
+    let c_0 = (1. / (1. / (c_a * c_a) + 1. / (c_b * c_b))).sqrt();
+    (c_0, c_mid, c_max)
+}
@@ -0,0 +1,455 @@
+use crate::{AnyColorRange, Easing, Hsla, Lcha, LinearRgba, Oklaba, SRgba};
+
+/// The color space in which a [`Gradient`] interpolates between its stops.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterpolationSpace {
+    /// Interpolate the gamma-encoded sRGB components. This matches legacy CSS gradients.
+    SRgba,
+    /// Interpolate in linear light.
+    LinearRgba,
+    /// Interpolate in the Oklab perceptual color space. This is the default, as it avoids
+    /// the muddy midpoints of sRGB interpolation.
+    #[default]
+    Oklaba,
+    /// Interpolate in the polar form of Oklab (lightness, chroma, hue).
+    Oklcha,
+    /// Interpolate in hue, saturation and lightness.
+    Hsla,
+    /// Interpolate in the polar form of CIE Lab (lightness, chroma, hue).
+    Lcha,
+    /// Interpolate in CIE Lab, the rectangular form of [`Lcha`].
+    Laba,
+}
+
+impl InterpolationSpace {
+    /// Returns true if this color space has a hue component.
+    pub const fn is_polar(&self) -> bool {
+        matches!(
+            self,
+            InterpolationSpace::Oklcha | InterpolationSpace::Hsla | InterpolationSpace::Lcha
+        )
+    }
+
+    /// Convert a color into the components of this color space. The last component is
+    /// always alpha, and for polar spaces the hue is at [`InterpolationSpace::hue_index`].
+    pub(crate) fn to_components(self, color: LinearRgba) -> [f32; 4] {
+        match self {
+            InterpolationSpace::SRgba => SRgba::from(color).into(),
+            InterpolationSpace::LinearRgba => color.into(),
+            InterpolationSpace::Oklaba => {
+                let Oklaba { l, a, b, alpha } = color.into();
+                [l, a, b, alpha]
+            }
+            InterpolationSpace::Oklcha => {
+                let Oklaba { l, a, b, alpha } = color.into();
+                let chroma = a.hypot(b);
+                let hue = b.atan2(a).to_degrees().rem_euclid(360.);
+                [l, chroma, hue, alpha]
+            }
+            InterpolationSpace::Hsla => {
+                let hsla = Hsla::from(color);
+                [hsla.hue, hsla.saturation, hsla.lightness, hsla.alpha]
+            }
+            InterpolationSpace::Lcha => {
+                let lcha = Lcha::from(color);
+                [lcha.lightness, lcha.chroma, lcha.hue, lcha.alpha]
+            }
+            InterpolationSpace::Laba => {
+                let lcha = Lcha::from(color);
+                let (sin, cos) = lcha.hue.to_radians().sin_cos();
+                [
+                    lcha.lightness,
+                    lcha.chroma * cos,
+                    lcha.chroma * sin,
+                    lcha.alpha,
+                ]
+            }
+        }
+    }
+
+    /// Convert components of this color space back into a color.
+    pub(crate) fn to_color(self, [c0, c1, c2, alpha]: [f32; 4]) -> LinearRgba {
+        match self {
+            InterpolationSpace::SRgba => SRgba::new(c0, c1, c2, alpha).into(),
+            InterpolationSpace::LinearRgba => LinearRgba::new(c0, c1, c2, alpha),
+            InterpolationSpace::Oklaba => Oklaba::new(c0, c1, c2, alpha).into(),
+            InterpolationSpace::Oklcha => {
+                let (sin, cos) = c2.to_radians().sin_cos();
+                Oklaba::new(c0, c1 * cos, c1 * sin, alpha).into()
+            }
+            InterpolationSpace::Hsla => Hsla::new(c0, c1, c2, alpha).into(),
+            InterpolationSpace::Lcha => Lcha::new(c0, c1, c2, alpha).into(),
+            InterpolationSpace::Laba => {
+                let chroma = c1.hypot(c2);
+                let hue = c2.atan2(c1).to_degrees().rem_euclid(360.);
+                Lcha::new(c0, chroma, hue, alpha).into()
+            }
+        }
+    }
+
+    /// The index of the hue component, or `None` for rectangular color spaces.
+    pub(crate) const fn hue_index(&self) -> Option<usize> {
+        match self {
+            InterpolationSpace::Oklcha | InterpolationSpace::Lcha => Some(2),
+            InterpolationSpace::Hsla => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Interpolate between two sets of components of this color space.
+    pub(crate) fn lerp(
+        self,
+        mut start: [f32; 4],
+        mut end: [f32; 4],
+        hue_interpolation: HueInterpolation,
+        factor: f32,
+    ) -> [f32; 4] {
+        if let Some(hue) = self.hue_index() {
+            // The hue of an achromatic color is meaningless, so borrow the other hue instead.
+            // In every polar space, chroma or saturation is the second component.
+            if start[1].abs() < 1e-4 {
+                start[hue] = end[hue];
+            } else if end[1].abs() < 1e-4 {
+                end[hue] = start[hue];
+            }
+            (start[hue], end[hue]) = hue_interpolation.fixup(start[hue], end[hue]);
+        }
+        let mut result = [0.; 4];
+        for (i, value) in result.iter_mut().enumerate() {
+            *value = start[i] + (end[i] - start[i]) * factor;
+        }
+        if let Some(hue) = self.hue_index() {
+            result[hue] = result[hue].rem_euclid(360.);
+        }
+        result
+    }
+}
+
+/// How hue is interpolated in a polar [`InterpolationSpace`], following the CSS
+/// [hue interpolation](https://www.w3.org/TR/css-color-4/#hue-interpolation) rules.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HueInterpolation {
+    /// Take the shortest path around the hue circle.
+    #[default]
+    Shorter,
+    /// Take the longest path around the hue circle.
+    Longer,
+    /// Always move in the direction of increasing hue.
+    Increasing,
+    /// Always move in the direction of decreasing hue.
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// Adjust a pair of hue angles in degrees so that linear interpolation between them
+    /// travels in the right direction.
+    pub(crate) fn fixup(&self, mut h1: f32, mut h2: f32) -> (f32, f32) {
+        let delta = h2 - h1;
+        match self {
+            HueInterpolation::Shorter => {
+                if delta > 180. {
+                    h1 += 360.;
+                } else if delta < -180. {
+                    h2 += 360.;
+                }
+            }
+            HueInterpolation::Longer => {
+                if 0. < delta && delta < 180. {
+                    h1 += 360.;
+                } else if -180. < delta && delta <= 0. {
+                    h2 += 360.;
+                }
+            }
+            HueInterpolation::Increasing => {
+                if h2 < h1 {
+                    h2 += 360.;
+                }
+            }
+            HueInterpolation::Decreasing => {
+                if h1 < h2 {
+                    h1 += 360.;
+                }
+            }
+        }
+        (h1, h2)
+    }
+}
+
+/// A single color stop within a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    /// The color at this stop.
+    pub color: LinearRgba,
+    /// The position of this stop along the gradient.
+    pub position: f32,
+    /// Optional position of the midpoint of the segment following this stop, in the same
+    /// units as `position`. This is equivalent to a CSS color hint: at the hint position,
+    /// the color is halfway between this stop and the next.
+    pub hint: Option<f32>,
+    /// The easing function applied to the segment following this stop.
+    pub easing: Easing,
+}
+
+impl ColorStop {
+    /// Construct a new color stop at the given position.
+    pub fn new(color: impl Into<LinearRgba>, position: f32) -> Self {
+        Self {
+            color: color.into(),
+            position,
+            hint: None,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Return a copy of this stop with a color hint for the following segment.
+    pub fn with_hint(&self, hint: f32) -> Self {
+        Self {
+            hint: Some(hint),
+            ..*self
+        }
+    }
+
+    /// Return a copy of this stop with an easing function for the following segment.
+    pub fn with_easing(&self, easing: Easing) -> Self {
+        Self { easing, ..*self }
+    }
+}
+
+/// A color gradient defined by any number of [`ColorStop`]s at arbitrary positions.
+///
+/// Unlike [`crate::ColorRange`], the color space used for interpolation is chosen at runtime
+/// rather than by the type of the colors.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{ColorStop, Gradient, InterpolationSpace, SRgba};
+/// let gradient = Gradient::new([
+///     ColorStop::new(SRgba::RED, 0.0),
+///     ColorStop::new(SRgba::WHITE, 0.25),
+///     ColorStop::new(SRgba::BLUE, 1.0),
+/// ])
+/// .with_space(InterpolationSpace::Oklcha);
+/// let color: SRgba = gradient.at(0.5).into();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<ColorStop>,
+    space: InterpolationSpace,
+    hue_interpolation: HueInterpolation,
+}
+
+impl Gradient {
+    /// Construct a new gradient from a list of color stops. The stops are sorted by
+    /// position, and stops with equal positions keep their original order, producing a hard
+    /// edge.
+    pub fn new(stops: impl IntoIterator<Item = ColorStop>) -> Self {
+        let mut stops: Vec<ColorStop> = stops.into_iter().collect();
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Self {
+            stops,
+            space: InterpolationSpace::default(),
+            hue_interpolation: HueInterpolation::default(),
+        }
+    }
+
+    /// Construct a new gradient from a list of colors, evenly spaced between 0.0 and 1.0.
+    pub fn from_colors<C: Into<LinearRgba>>(colors: impl IntoIterator<Item = C>) -> Self {
+        let colors: Vec<LinearRgba> = colors.into_iter().map(Into::into).collect();
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self::new(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| ColorStop::new(color, i as f32 / last)),
+        )
+    }
+
+    /// Return a copy of this gradient which interpolates in the given color space.
+    pub fn with_space(&self, space: InterpolationSpace) -> Self {
+        Self {
+            space,
+            ..self.clone()
+        }
+    }
+
+    /// Return a copy of this gradient which uses the given hue interpolation mode. This
+    /// only has an effect for polar interpolation spaces.
+    pub fn with_hue_interpolation(&self, hue_interpolation: HueInterpolation) -> Self {
+        Self {
+            hue_interpolation,
+            ..self.clone()
+        }
+    }
+
+    /// The color stops of this gradient, sorted by position.
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    /// The color space used for interpolation.
+    pub fn space(&self) -> InterpolationSpace {
+        self.space
+    }
+
+    /// The hue interpolation mode.
+    pub fn hue_interpolation(&self) -> HueInterpolation {
+        self.hue_interpolation
+    }
+
+    /// Get the color at the given position. Positions before the first stop or after the
+    /// last stop return the color of that stop. An empty gradient is transparent.
+    pub fn at(&self, position: f32) -> LinearRgba {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return LinearRgba::new(0., 0., 0., 0.),
+        };
+        if position <= first.position {
+            return first.color;
+        }
+        if position >= last.position {
+            return last.color;
+        }
+
+        // Find the last segment that starts at or before the position.
+        let index = self
+            .stops
+            .partition_point(|stop| stop.position <= position)
+            .saturating_sub(1);
+        let start = &self.stops[index];
+        let end = &self.stops[index + 1];
+        let width = end.position - start.position;
+        if width <= 0. {
+            return end.color;
+        }
+
+        let mut factor = (position - start.position) / width;
+        if let Some(hint) = start.hint {
+            factor = apply_hint(factor, (hint - start.position) / width);
+        }
+        factor = start.easing.ease(factor);
+
+        self.space.to_color(self.space.lerp(
+            self.space.to_components(start.color),
+            self.space.to_components(end.color),
+            self.hue_interpolation,
+            factor,
+        ))
+    }
+}
+
+impl AnyColorRange for Gradient {
+    fn at_linear(&self, factor: f32) -> LinearRgba {
+        self.at(factor)
+    }
+}
+
+/// Remap a factor so that 0.5 falls at the relative hint position, as described in
+/// [CSS Images](https://www.w3.org/TR/css-images-4/#coloring-gradient-line).
+fn apply_hint(factor: f32, hint: f32) -> f32 {
+    if hint <= 0. {
+        1.
+    } else if hint >= 1. {
+        0.
+    } else {
+        factor.powf(0.5f32.ln() / hint.ln())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color_difference::EuclideanDistance, testing::assert_approx_eq, ColorRange};
+
+    #[test]
+    fn two_stops() {
+        let gradient =
+            Gradient::from_colors([SRgba::RED, SRgba::BLUE]).with_space(InterpolationSpace::SRgba);
+        let range = ColorRange::new(SRgba::RED, SRgba::BLUE);
+        for i in 0..=10 {
+            let t = i as f32 / 10.;
+            let expected: LinearRgba = range.at(t).into();
+            assert!(gradient.at(t).distance(&expected) < 0.0001, "{}", t);
+        }
+    }
+
+    #[test]
+    fn multiple_stops() {
+        let gradient = Gradient::new([
+            ColorStop::new(LinearRgba::new(0., 0., 1., 1.), 10.),
+            ColorStop::new(LinearRgba::new(1., 0., 0., 1.), 0.),
+            ColorStop::new(LinearRgba::new(0., 1., 0., 1.), 2.),
+        ])
+        .with_space(InterpolationSpace::LinearRgba);
+        assert_eq!(gradient.stops()[1].position, 2.);
+        assert_eq!(gradient.at(-1.), LinearRgba::new(1., 0., 0., 1.));
+        assert_eq!(gradient.at(1.), LinearRgba::new(0.5, 0.5, 0., 1.));
+        assert_eq!(gradient.at(2.), LinearRgba::new(0., 1., 0., 1.));
+        assert_eq!(gradient.at(6.), LinearRgba::new(0., 0.5, 0.5, 1.));
+        assert_eq!(gradient.at(20.), LinearRgba::new(0., 0., 1., 1.));
+        assert_eq!(gradient.at_linear(6.), gradient.at(6.));
+
+        // Hard edge between stops at the same position.
+        let gradient = Gradient::new([
+            ColorStop::new(SRgba::RED, 0.),
+            ColorStop::new(SRgba::RED, 0.5),
+            ColorStop::new(SRgba::BLUE, 0.5),
+            ColorStop::new(SRgba::BLUE, 1.),
+        ]);
+        assert!(gradient.at(0.499).distance(&SRgba::RED.into()) < 0.0001);
+        assert!(gradient.at(0.5).distance(&SRgba::BLUE.into()) < 0.0001);
+
+        assert_eq!(Gradient::new([]).at(0.5), LinearRgba::new(0., 0., 0., 0.));
+    }
+
+    #[test]
+    fn hue_interpolation() {
+        let red = Hsla::new(0., 1., 0.5, 1.);
+        let blue = Hsla::new(240., 1., 0.5, 1.);
+        let gradient = Gradient::from_colors([red, blue]).with_space(InterpolationSpace::Hsla);
+        let hue = |g: &Gradient| Hsla::from(g.at(0.5)).hue;
+        assert_approx_eq!(hue(&gradient), 300., 0.01);
+        let gradient = gradient.with_hue_interpolation(HueInterpolation::Longer);
+        assert_approx_eq!(hue(&gradient), 120., 0.01);
+        let gradient = gradient.with_hue_interpolation(HueInterpolation::Increasing);
+        assert_approx_eq!(hue(&gradient), 120., 0.01);
+        let gradient = gradient.with_hue_interpolation(HueInterpolation::Decreasing);
+        assert_approx_eq!(hue(&gradient), 300., 0.01);
+    }
+
+    #[test]
+    fn oklch() {
+        let gradient =
+            Gradient::from_colors([SRgba::RED, SRgba::BLUE]).with_space(InterpolationSpace::Oklcha);
+        assert!(gradient.at(0.).distance(&SRgba::RED.into()) < 0.0001);
+        assert!(gradient.at(1.).distance(&SRgba::BLUE.into()) < 0.0001);
+
+        // An achromatic stop does not introduce a hue shift.
+        let gradient = Gradient::from_colors([SRgba::WHITE, SRgba::BLUE])
+            .with_space(InterpolationSpace::Oklcha);
+        let space = InterpolationSpace::Oklcha;
+        let mid = space.to_components(gradient.at(0.5));
+        let end = space.to_components(SRgba::BLUE.into());
+        assert_approx_eq!(mid[2], end[2], 0.01);
+    }
+
+    #[test]
+    fn hints_and_easing() {
+        let black = LinearRgba::new(0., 0., 0., 1.);
+        let white = LinearRgba::new(1., 1., 1., 1.);
+        let gradient = Gradient::new([
+            ColorStop::new(black, 0.).with_hint(0.25),
+            ColorStop::new(white, 1.),
+        ])
+        .with_space(InterpolationSpace::LinearRgba);
+        assert_approx_eq!(gradient.at(0.25).red, 0.5, 0.0001);
+        assert!(gradient.at(0.75).red > 0.75);
+
+        let gradient = Gradient::new([
+            ColorStop::new(black, 0.).with_easing(Easing::EaseIn),
+            ColorStop::new(white, 1.),
+        ])
+        .with_space(InterpolationSpace::LinearRgba);
+        assert_approx_eq!(gradient.at(0.5).red, Easing::EaseIn.ease(0.5), 0.0001);
+    }
+}
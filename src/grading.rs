@@ -0,0 +1,961 @@
+//! Color grading primitives.
+//!
+//! Each primitive works on scene-linear [`LinearRgba`] and exposes its parameters, as public fields
+//! or accessors, or as a matrix, so that the same settings can drive a shader. Negative channels
+//! are passed through the power functions unchanged, rather than clamped, so that HDR and wide
+//! gamut values survive a grade. Alpha is never changed.
+
+use crate::{
+    math::{bradford, dot, matrix_multiply},
+    photometry::planckian_xy,
+    LinearRgba, Xyza,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+
+/// An operation which grades linear RGB colors.
+pub trait Grade {
+    /// Grade linear RGB values.
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3];
+
+    /// Grade a linear color. Alpha is passed through.
+    fn grade(&self, color: LinearRgba) -> LinearRgba {
+        let [red, green, blue] = self.grade_rgb([color.red, color.green, color.blue]);
+        LinearRgba::new(red, green, blue, color.alpha)
+    }
+
+    /// Grade a slice of linear colors, writing into `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than `colors`.
+    fn grade_slice(&self, colors: &[LinearRgba], out: &mut [LinearRgba]) {
+        assert!(out.len() >= colors.len(), "output buffer is too small");
+        for (color, out) in colors.iter().zip(out.iter_mut()) {
+            *out = self.grade(*color);
+        }
+    }
+}
+
+/// Rec. 709 luma weights, which the ASC CDL saturation uses.
+const REC709_WEIGHTS: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Raise non-negative values to a power, and pass negative values through.
+#[inline]
+fn power(value: f32, exponent: f32) -> f32 {
+    if value > 0. {
+        value.powf(exponent)
+    } else {
+        value
+    }
+}
+
+/// An ASC Color Decision List correction: slope, offset and power per channel, followed by a
+/// saturation.
+///
+/// This follows the unclamped style of the transform, as OpenColorIO's `noClamp` does, so
+/// values above 1.0 keep their meaning.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct AscCdl {
+    pub slope: [f32; 3],
+    pub offset: [f32; 3],
+    pub power: [f32; 3],
+    pub saturation: f32,
+}
+
+impl AscCdl {
+    /// The correction which leaves colors unchanged.
+    pub const IDENTITY: Self = Self {
+        slope: [1.; 3],
+        offset: [0.; 3],
+        power: [1.; 3],
+        saturation: 1.,
+    };
+}
+
+impl Default for AscCdl {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Grade for AscCdl {
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let sop = [0, 1, 2].map(|i| power(rgb[i] * self.slope[i] + self.offset[i], self.power[i]));
+        let luma = dot(sop, REC709_WEIGHTS);
+        sop.map(|c| luma + self.saturation * (c - luma))
+    }
+}
+
+/// An error from reading ASC CDL XML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CdlError {
+    /// The document does not contain any `ColorCorrection` elements.
+    NoCorrections,
+    /// An element is not closed.
+    UnclosedElement(String),
+    /// An element does not hold the expected number of values.
+    InvalidValue(String),
+}
+
+impl fmt::Display for CdlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CdlError::NoCorrections => write!(f, "CDL document has no ColorCorrection elements"),
+            CdlError::UnclosedElement(name) => write!(f, "CDL element {name} is not closed"),
+            CdlError::InvalidValue(name) => write!(f, "Invalid value for CDL element {name}"),
+        }
+    }
+}
+
+impl std::error::Error for CdlError {}
+
+/// The XML namespace of ASC CDL version 1.01 documents.
+const CDL_NAMESPACE: &str = "urn:ASC:CDL:v1.01";
+
+/// An [`AscCdl`] with the optional id which identifies it in a CDL document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct ColorCorrection {
+    pub id: Option<String>,
+    pub cdl: AscCdl,
+}
+
+impl ColorCorrection {
+    /// Construct a new [`ColorCorrection`].
+    pub fn new(id: Option<String>, cdl: AscCdl) -> Self {
+        Self { id, cdl }
+    }
+
+    /// Read every `ColorCorrection` element of an ASC CDL document, in document order. This
+    /// accepts the single correction `.cc`, collection `.ccc` and decision list `.cdl`
+    /// formats. Missing `SOPNode` or `SatNode` values are neutral. References to corrections
+    /// in other files (`ColorCorrectionRef`) are ignored.
+    pub fn parse_xml(xml: &str) -> Result<Vec<Self>, CdlError> {
+        let xml = strip_comments(xml);
+        let corrections = elements(&xml, "ColorCorrection")?
+            .into_iter()
+            .map(|(attributes, content)| {
+                let mut cdl = AscCdl::IDENTITY;
+                for (name, values) in [
+                    ("Slope", &mut cdl.slope),
+                    ("Offset", &mut cdl.offset),
+                    ("Power", &mut cdl.power),
+                ] {
+                    if let Some(text) = element_text(content, name)? {
+                        *values = parse_values(text, name)?;
+                    }
+                }
+                if let Some(text) = element_text(content, "Saturation")? {
+                    let [saturation] = parse_values(text, "Saturation")?;
+                    cdl.saturation = saturation;
+                }
+                let id = attribute(attributes, "id").map(unescape);
+                Ok(Self::new(id, cdl))
+            })
+            .collect::<Result<Vec<_>, CdlError>>()?;
+        if corrections.is_empty() {
+            return Err(CdlError::NoCorrections);
+        }
+        Ok(corrections)
+    }
+
+    /// Write this correction as a `.cc` document.
+    pub fn to_cc(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        write_correction(&mut xml, self, 0, true);
+        xml
+    }
+
+    /// Write corrections as a `.ccc` collection document.
+    pub fn write_ccc(corrections: &[Self]) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(xml, "<ColorCorrectionCollection xmlns=\"{CDL_NAMESPACE}\">").unwrap();
+        for correction in corrections {
+            write_correction(&mut xml, correction, 1, false);
+        }
+        xml.push_str("</ColorCorrectionCollection>\n");
+        xml
+    }
+
+    /// Write corrections as a `.cdl` decision list document, with one `ColorDecision` per
+    /// correction.
+    pub fn write_cdl(corrections: &[Self]) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(xml, "<ColorDecisionList xmlns=\"{CDL_NAMESPACE}\">").unwrap();
+        for correction in corrections {
+            xml.push_str("    <ColorDecision>\n");
+            write_correction(&mut xml, correction, 2, false);
+            xml.push_str("    </ColorDecision>\n");
+        }
+        xml.push_str("</ColorDecisionList>\n");
+        xml
+    }
+}
+
+fn write_correction(xml: &mut String, correction: &ColorCorrection, depth: usize, namespace: bool) {
+    let indent = "    ".repeat(depth);
+    let values = |values: [f32; 3]| format!("{} {} {}", values[0], values[1], values[2]);
+    let cdl = &correction.cdl;
+    xml.push_str(&indent);
+    xml.push_str("<ColorCorrection");
+    if let Some(id) = &correction.id {
+        write!(xml, " id=\"{}\"", escape(id)).unwrap();
+    }
+    if namespace {
+        write!(xml, " xmlns=\"{CDL_NAMESPACE}\"").unwrap();
+    }
+    xml.push_str(">\n");
+    writeln!(xml, "{indent}    <SOPNode>").unwrap();
+    writeln!(xml, "{indent}        <Slope>{}</Slope>", values(cdl.slope)).unwrap();
+    writeln!(
+        xml,
+        "{indent}        <Offset>{}</Offset>",
+        values(cdl.offset)
+    )
+    .unwrap();
+    writeln!(xml, "{indent}        <Power>{}</Power>", values(cdl.power)).unwrap();
+    writeln!(xml, "{indent}    </SOPNode>").unwrap();
+    writeln!(xml, "{indent}    <SatNode>").unwrap();
+    writeln!(
+        xml,
+        "{indent}        <Saturation>{}</Saturation>",
+        cdl.saturation
+    )
+    .unwrap();
+    writeln!(xml, "{indent}    </SatNode>").unwrap();
+    writeln!(xml, "{indent}</ColorCorrection>").unwrap();
+}
+
+fn strip_comments(xml: &str) -> String {
+    let mut result = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find("<!--") {
+        result.push_str(&rest[..start]);
+        rest = match rest[start..].find("-->") {
+            Some(end) => &rest[start + end + 3..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Find the elements with a tag name, returning their attributes and contents. Elements
+/// with the same name are not expected to nest.
+fn elements<'a>(xml: &'a str, name: &str) -> Result<Vec<(&'a str, &'a str)>, CdlError> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+    let mut result = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        // Skip longer names which share the prefix, such as `ColorCorrectionRef`.
+        if !rest.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+        let end = rest
+            .find('>')
+            .ok_or_else(|| CdlError::UnclosedElement(name.to_string()))?;
+        let attributes = &rest[..end];
+        rest = &rest[end + 1..];
+        if let Some(attributes) = attributes.strip_suffix('/') {
+            result.push((attributes, ""));
+            continue;
+        }
+        let end = rest
+            .find(&close)
+            .ok_or_else(|| CdlError::UnclosedElement(name.to_string()))?;
+        result.push((attributes, &rest[..end]));
+        rest = &rest[end + close.len()..];
+    }
+    Ok(result)
+}
+
+fn element_text<'a>(xml: &'a str, name: &str) -> Result<Option<&'a str>, CdlError> {
+    Ok(elements(xml, name)?.first().map(|(_, content)| *content))
+}
+
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(start) = rest.find(name) {
+        let preceded_by_space = rest[..start].ends_with(|c: char| c.is_whitespace());
+        rest = &rest[start + name.len()..];
+        let Some(value) = rest.trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        let value = &value[1..];
+        if let (true, Some(end)) = (preceded_by_space, value.find(quote)) {
+            return Some(&value[..end]);
+        }
+    }
+    None
+}
+
+fn parse_values<const N: usize>(text: &str, name: &str) -> Result<[f32; N], CdlError> {
+    let invalid = || CdlError::InvalidValue(name.to_string());
+    let mut values = [0.; N];
+    let mut parts = text.split_whitespace();
+    for value in values.iter_mut() {
+        *value = parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(invalid)?;
+    }
+    match parts.next() {
+        Some(_) => Err(invalid()),
+        None => Ok(values),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Lift, gamma and gain per channel, the three-way color corrector of grading software.
+///
+/// Lift raises the blacks while leaving white fixed, gain scales the whole signal, and gamma
+/// bends the midtones, brightening them for values above 1.0:
+/// `(gain * (c + lift * (1 - c)))^(1 / gamma)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct LiftGammaGain {
+    pub lift: [f32; 3],
+    pub gamma: [f32; 3],
+    pub gain: [f32; 3],
+}
+
+impl LiftGammaGain {
+    /// The correction which leaves colors unchanged.
+    pub const IDENTITY: Self = Self {
+        lift: [0.; 3],
+        gamma: [1.; 3],
+        gain: [1.; 3],
+    };
+}
+
+impl Default for LiftGammaGain {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Grade for LiftGammaGain {
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|i| {
+            let c = rgb[i];
+            power(
+                self.gain[i] * (c + self.lift[i] * (1. - c)),
+                1. / self.gamma[i],
+            )
+        })
+    }
+}
+
+/// Contrast around a pivot, as a power curve on linear values: `pivot * (c / pivot)^contrast`.
+/// The pivot, usually middle gray, and black are left unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Contrast {
+    pub contrast: f32,
+    pub pivot: f32,
+}
+
+impl Contrast {
+    /// Construct a new [`Contrast`] around middle gray, 0.18.
+    pub const fn new(contrast: f32) -> Self {
+        Self {
+            contrast,
+            pivot: 0.18,
+        }
+    }
+
+    /// Return a copy with the given pivot.
+    pub const fn with_pivot(self, pivot: f32) -> Self {
+        Self { pivot, ..self }
+    }
+}
+
+impl Default for Contrast {
+    fn default() -> Self {
+        Self::new(1.)
+    }
+}
+
+impl Grade for Contrast {
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        rgb.map(|c| self.pivot * power(c / self.pivot, self.contrast))
+    }
+}
+
+/// A saturation adjustment, which scales each color's distance from its luma. A saturation
+/// of 0.0 gives grays, and values above 1.0 increase saturation. Luma is preserved.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Saturation {
+    pub saturation: f32,
+    pub weights: [f32; 3],
+}
+
+impl Saturation {
+    /// Rec. 709 and sRGB luma weights.
+    pub const REC709_WEIGHTS: [f32; 3] = REC709_WEIGHTS;
+    /// Rec. 2020 luma weights.
+    pub const REC2020_WEIGHTS: [f32; 3] = [0.2627, 0.6780, 0.0593];
+
+    /// Construct a new [`Saturation`] with Rec. 709 luma weights.
+    pub const fn new(saturation: f32) -> Self {
+        Self {
+            saturation,
+            weights: REC709_WEIGHTS,
+        }
+    }
+
+    /// Return a copy with the given luma weights, which should sum to 1.0.
+    pub const fn with_weights(self, weights: [f32; 3]) -> Self {
+        Self { weights, ..self }
+    }
+
+    /// The matrix which applies this adjustment to a column vector of linear RGB, as rows.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        let s = self.saturation;
+        [0, 1, 2].map(|row| {
+            [0, 1, 2].map(|column| {
+                let diagonal = if row == column { s } else { 0. };
+                (1. - s) * self.weights[column] + diagonal
+            })
+        })
+    }
+}
+
+impl Default for Saturation {
+    fn default() -> Self {
+        Self::new(1.)
+    }
+}
+
+impl Grade for Saturation {
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        matrix_multiply(&self.matrix(), rgb)
+    }
+}
+
+/// White balance by color temperature and tint.
+///
+/// `temperature` is the color temperature in Kelvin of the light in the scene, and `tint`
+/// is how far that light lies from the black body locus, as Δuv in the CIE 1960 UCS, with
+/// positive values towards green and typical values within ±0.02. The light's color is
+/// adapted with a Bradford transform to the black body color at 6504 K, which is close to
+/// the D65 white of sRGB. The default of 6504 K and no tint is the identity, so a lower
+/// temperature corrects for warm light and makes the image cooler.
+///
+/// The adaptation matrix is computed when the white balance is constructed, so the fields
+/// are private and changed with [`WhiteBalance::with_temperature`] and
+/// [`WhiteBalance::with_tint`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(from = "WhiteBalanceSettings", into = "WhiteBalanceSettings")]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub struct WhiteBalance {
+    temperature: f32,
+    tint: f32,
+    matrix: [[f32; 3]; 3],
+}
+
+/// The serialized form of a [`WhiteBalance`], without its derived matrix.
+#[derive(Serialize, Deserialize)]
+struct WhiteBalanceSettings {
+    temperature: f32,
+    tint: f32,
+}
+
+impl From<WhiteBalanceSettings> for WhiteBalance {
+    fn from(value: WhiteBalanceSettings) -> Self {
+        Self::new(value.temperature, value.tint)
+    }
+}
+
+impl From<WhiteBalance> for WhiteBalanceSettings {
+    fn from(value: WhiteBalance) -> Self {
+        Self {
+            temperature: value.temperature,
+            tint: value.tint,
+        }
+    }
+}
+
+impl WhiteBalance {
+    /// The neutral color temperature, which is closest to the D65 white of sRGB.
+    pub const NEUTRAL_TEMPERATURE: f32 = 6504.;
+
+    /// Construct a new [`WhiteBalance`].
+    pub fn new(temperature: f32, tint: f32) -> Self {
+        Self {
+            temperature,
+            tint,
+            matrix: Self::compute_matrix(temperature, tint),
+        }
+    }
+
+    /// The color temperature of the light in the scene, in Kelvin.
+    pub fn temperature(&self) -> f32 {
+        self.temperature
+    }
+
+    /// The offset of the light from the black body locus, as Δuv in the CIE 1960 UCS.
+    pub fn tint(&self) -> f32 {
+        self.tint
+    }
+
+    /// Return this white balance with a different color temperature.
+    pub fn with_temperature(self, temperature: f32) -> Self {
+        Self::new(temperature, self.tint)
+    }
+
+    /// Return this white balance with a different tint.
+    pub fn with_tint(self, tint: f32) -> Self {
+        Self::new(self.temperature, tint)
+    }
+
+    /// The matrix which applies this adjustment to a column vector of linear RGB, as rows.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        self.matrix
+    }
+
+    fn compute_matrix(temperature: f32, tint: f32) -> [[f32; 3]; 3] {
+        let source = white_xyz(temperature, tint);
+        let destination = white_xyz(Self::NEUTRAL_TEMPERATURE, 0.);
+        let adaptation = bradford(source, destination);
+        let basis = |i: usize| {
+            let mut rgb = [0.; 3];
+            rgb[i] = 1.;
+            let Xyza { x, y, z, .. } = Xyza::from(LinearRgba::new(rgb[0], rgb[1], rgb[2], 1.));
+            let [x, y, z] = matrix_multiply(&adaptation, [x, y, z]);
+            let LinearRgba {
+                red, green, blue, ..
+            } = LinearRgba::from(Xyza::new(x, y, z, 1.));
+            [red, green, blue]
+        };
+        let columns = [basis(0), basis(1), basis(2)];
+        [0, 1, 2].map(|row| columns.map(|column| column[row]))
+    }
+}
+
+impl Default for WhiteBalance {
+    fn default() -> Self {
+        Self::new(Self::NEUTRAL_TEMPERATURE, 0.)
+    }
+}
+
+impl Grade for WhiteBalance {
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        matrix_multiply(&self.matrix, rgb)
+    }
+}
+
+/// The XYZ white, with a luminance of 1.0, of a black body offset from the locus by `tint`.
+fn white_xyz(temperature: f32, tint: f32) -> [f32; 3] {
+    let uv = |kelvin: f32| {
+        let [x, y] = planckian_xy(kelvin);
+        let d = -2. * x + 12. * y + 3.;
+        [4. * x / d, 6. * y / d]
+    };
+    let [u, v] = uv(temperature);
+    let (before, after) = (uv(temperature - 1.), uv(temperature + 1.));
+    let tangent = [after[0] - before[0], after[1] - before[1]];
+    let length = (tangent[0] * tangent[0] + tangent[1] * tangent[1]).sqrt();
+    // The normal to the locus which points towards green, with increasing v.
+    let mut normal = [-tangent[1] / length, tangent[0] / length];
+    if normal[1] < 0. {
+        normal = [-normal[0], -normal[1]];
+    }
+    let (u, v) = (u + tint * normal[0], v + tint * normal[1]);
+    let d = 2. * u - 8. * v + 4.;
+    let (x, y) = (3. * u / d, 2. * v / d);
+    [x / y, 1., (1. - x - y) / y]
+}
+
+/// One step of a [`ColorGrade`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum GradeOp {
+    WhiteBalance(WhiteBalance),
+    Cdl(AscCdl),
+    LiftGammaGain(LiftGammaGain),
+    Contrast(Contrast),
+    Saturation(Saturation),
+}
+
+impl Grade for GradeOp {
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            GradeOp::WhiteBalance(op) => op.grade_rgb(rgb),
+            GradeOp::Cdl(op) => op.grade_rgb(rgb),
+            GradeOp::LiftGammaGain(op) => op.grade_rgb(rgb),
+            GradeOp::Contrast(op) => op.grade_rgb(rgb),
+            GradeOp::Saturation(op) => op.grade_rgb(rgb),
+        }
+    }
+}
+
+impl From<WhiteBalance> for GradeOp {
+    fn from(value: WhiteBalance) -> Self {
+        GradeOp::WhiteBalance(value)
+    }
+}
+
+impl From<AscCdl> for GradeOp {
+    fn from(value: AscCdl) -> Self {
+        GradeOp::Cdl(value)
+    }
+}
+
+impl From<LiftGammaGain> for GradeOp {
+    fn from(value: LiftGammaGain) -> Self {
+        GradeOp::LiftGammaGain(value)
+    }
+}
+
+impl From<Contrast> for GradeOp {
+    fn from(value: Contrast) -> Self {
+        GradeOp::Contrast(value)
+    }
+}
+
+impl From<Saturation> for GradeOp {
+    fn from(value: Saturation) -> Self {
+        GradeOp::Saturation(value)
+    }
+}
+
+/// A sequence of grading operations, applied in order.
+///
+/// ```
+/// # use bevy_color::*;
+/// let grade = ColorGrade::new()
+///     .then(WhiteBalance::new(5000., 0.))
+///     .then(Contrast::new(1.2))
+///     .then(Saturation::new(0.9));
+/// let graded = grade.grade(LinearRgba::new(0.5, 0.4, 0.3, 1.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct ColorGrade {
+    pub ops: Vec<GradeOp>,
+}
+
+impl ColorGrade {
+    /// Construct an empty [`ColorGrade`], which leaves colors unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return this grade with an operation appended.
+    pub fn then(mut self, op: impl Into<GradeOp>) -> Self {
+        self.ops.push(op.into());
+        self
+    }
+}
+
+impl Grade for ColorGrade {
+    fn grade_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        self.ops.iter().fold(rgb, |rgb, op| op.grade_rgb(rgb))
+    }
+
+    fn grade_slice(&self, colors: &[LinearRgba], out: &mut [LinearRgba]) {
+        assert!(out.len() >= colors.len(), "output buffer is too small");
+        let out = &mut out[..colors.len()];
+        out.copy_from_slice(colors);
+        // Apply one operation at a time, so that each matrix is only computed once.
+        for op in &self.ops {
+            match op {
+                GradeOp::WhiteBalance(balance) => transform_all(out, &balance.matrix()),
+                GradeOp::Saturation(saturation) => transform_all(out, &saturation.matrix()),
+                _ => {
+                    for color in out.iter_mut() {
+                        *color = op.grade(*color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Apply a matrix to each color of a slice in place.
+fn transform_all(colors: &mut [LinearRgba], matrix: &[[f32; 3]; 3]) {
+    for color in colors.iter_mut() {
+        let [red, green, blue] = matrix_multiply(matrix, [color.red, color.green, color.blue]);
+        *color = LinearRgba::new(red, green, blue, color.alpha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{kelvin_to_linear_rgba, testing::assert_approx_eq, LuminanceOps};
+
+    fn assert_rgb(actual: LinearRgba, expected: [f32; 3]) {
+        assert_approx_eq!(actual.red, expected[0], 0.0001);
+        assert_approx_eq!(actual.green, expected[1], 0.0001);
+        assert_approx_eq!(actual.blue, expected[2], 0.0001);
+    }
+
+    const COLOR: LinearRgba = LinearRgba::new(0.5, 0.25, 0.1, 0.5);
+
+    #[test]
+    fn asc_cdl() {
+        assert_rgb(
+            AscCdl::IDENTITY.grade(COLOR),
+            [COLOR.red, COLOR.green, COLOR.blue],
+        );
+        let cdl = AscCdl {
+            slope: [1.2, 1.0, 0.8],
+            offset: [0.01, 0.0, -0.02],
+            power: [1.0, 1.1, 0.9],
+            saturation: 0.8,
+        };
+        let graded = cdl.grade(COLOR);
+        assert_rgb(graded, [0.546216, 0.232326, 0.121811]);
+        assert_eq!(graded.alpha, 0.5);
+
+        // Negative values are not raised to the power.
+        let negative = AscCdl {
+            power: [2.0; 3],
+            ..AscCdl::IDENTITY
+        };
+        assert_eq!(negative.grade_rgb([-0.5, 0.5, 2.0]), [-0.5, 0.25, 4.0]);
+    }
+
+    #[test]
+    fn parse_xml() {
+        let ccc = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ColorCorrectionCollection xmlns="urn:ASC:CDL:v1.01">
+                <!-- <ColorCorrection id="commented out"></ColorCorrection> -->
+                <ColorCorrection id="shot &amp; take">
+                    <Description>Warm</Description>
+                    <SOPNode>
+                        <Slope>1.2 1.0 0.8</Slope>
+                        <Offset> 0.01 0 -0.02 </Offset>
+                        <Power>1 1.1 0.9</Power>
+                    </SOPNode>
+                    <SatNode><Saturation>0.8</Saturation></SatNode>
+                </ColorCorrection>
+                <ColorCorrection>
+                    <SatNode><Saturation>0</Saturation></SatNode>
+                </ColorCorrection>
+            </ColorCorrectionCollection>"#;
+        let corrections = ColorCorrection::parse_xml(ccc).unwrap();
+        assert_eq!(corrections.len(), 2);
+        assert_eq!(corrections[0].id.as_deref(), Some("shot & take"));
+        assert_eq!(corrections[0].cdl.slope, [1.2, 1.0, 0.8]);
+        assert_eq!(corrections[0].cdl.offset, [0.01, 0.0, -0.02]);
+        assert_eq!(corrections[0].cdl.power, [1.0, 1.1, 0.9]);
+        assert_eq!(corrections[0].cdl.saturation, 0.8);
+        assert_eq!(corrections[1].id, None);
+        assert_eq!(
+            corrections[1].cdl,
+            AscCdl {
+                saturation: 0.0,
+                ..AscCdl::IDENTITY
+            }
+        );
+
+        let cdl = r#"<ColorDecisionList xmlns="urn:ASC:CDL:v1.01">
+            <ColorDecision>
+                <ColorCorrectionRef ref="elsewhere"/>
+            </ColorDecision>
+            <ColorDecision>
+                <ColorCorrection id='a'><SOPNode><Slope>2 2 2</Slope></SOPNode></ColorCorrection>
+            </ColorDecision>
+        </ColorDecisionList>"#;
+        let corrections = ColorCorrection::parse_xml(cdl).unwrap();
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].id.as_deref(), Some("a"));
+        assert_eq!(corrections[0].cdl.slope, [2.0; 3]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            ColorCorrection::parse_xml("<ColorDecisionList/>"),
+            Err(CdlError::NoCorrections)
+        );
+        assert_eq!(
+            ColorCorrection::parse_xml("<ColorCorrection><SOPNode>"),
+            Err(CdlError::UnclosedElement("ColorCorrection".into()))
+        );
+        assert_eq!(
+            ColorCorrection::parse_xml(
+                "<ColorCorrection><SOPNode><Slope>1 1</Slope></SOPNode></ColorCorrection>"
+            ),
+            Err(CdlError::InvalidValue("Slope".into()))
+        );
+        assert_eq!(
+            ColorCorrection::parse_xml(
+                "<ColorCorrection><SatNode><Saturation>x</Saturation></SatNode></ColorCorrection>"
+            ),
+            Err(CdlError::InvalidValue("Saturation".into()))
+        );
+    }
+
+    #[test]
+    fn write_xml() {
+        let corrections = vec![
+            ColorCorrection::new(
+                Some("a<b>\"c\"".into()),
+                AscCdl {
+                    slope: [1.2, 1.0, 0.8],
+                    offset: [0.01, 0.0, -0.02],
+                    power: [1.0, 1.1, 0.9],
+                    saturation: 0.8,
+                },
+            ),
+            ColorCorrection::default(),
+        ];
+        let ccc = ColorCorrection::write_ccc(&corrections);
+        assert!(ccc.contains("<Slope>1.2 1 0.8</Slope>"));
+        assert_eq!(ColorCorrection::parse_xml(&ccc).unwrap(), corrections);
+        let cdl = ColorCorrection::write_cdl(&corrections);
+        assert_eq!(cdl.matches("<ColorDecision>").count(), 2);
+        assert_eq!(ColorCorrection::parse_xml(&cdl).unwrap(), corrections);
+        let cc = corrections[0].to_cc();
+        assert_eq!(
+            ColorCorrection::parse_xml(&cc).unwrap(),
+            vec![corrections[0].clone()]
+        );
+    }
+
+    #[test]
+    fn lift_gamma_gain() {
+        assert_eq!(LiftGammaGain::IDENTITY.grade(COLOR), COLOR);
+        let grade = LiftGammaGain {
+            lift: [0.1; 3],
+            gamma: [2.0; 3],
+            gain: [1.5; 3],
+        };
+        assert_approx_eq!(grade.grade_rgb([0.25; 3])[0], 0.698212, 0.0001);
+        // Lift leaves white in place.
+        let lift = LiftGammaGain {
+            lift: [0.2, 0.0, -0.1],
+            ..LiftGammaGain::IDENTITY
+        };
+        assert_eq!(lift.grade_rgb([1.0; 3]), [1.0; 3]);
+        assert_rgb(lift.grade(LinearRgba::new(0., 0., 0., 1.)), [0.2, 0., -0.1]);
+    }
+
+    #[test]
+    fn contrast() {
+        let contrast = Contrast::new(1.5);
+        assert_approx_eq!(contrast.grade_rgb([0.18; 3])[0], 0.18, 0.00001);
+        assert_eq!(contrast.grade_rgb([0.0; 3]), [0.0; 3]);
+        let [bright, dark, _] = contrast.grade_rgb([0.36, 0.09, 0.]);
+        assert_approx_eq!(bright, 0.18 * 2f32.powf(1.5), 0.0001);
+        assert_approx_eq!(dark, 0.18 * 0.5f32.powf(1.5), 0.0001);
+        let pivoted = Contrast::new(2.0).with_pivot(0.5);
+        assert_approx_eq!(pivoted.grade_rgb([0.25; 3])[0], 0.125, 0.0001);
+        assert_eq!(Contrast::default().grade(COLOR), COLOR);
+    }
+
+    #[test]
+    fn saturation() {
+        let gray = Saturation::new(0.0).grade(COLOR);
+        assert_approx_eq!(gray.red, COLOR.luminance(), 0.0001);
+        assert_approx_eq!(gray.green, gray.red, 0.0001);
+        assert_approx_eq!(gray.blue, gray.red, 0.0001);
+        let boosted = Saturation::new(1.5).grade(COLOR);
+        assert_approx_eq!(boosted.luminance(), COLOR.luminance(), 0.0001);
+        assert!(boosted.red > COLOR.red && boosted.blue < COLOR.blue);
+
+        let rec2020 = Saturation::new(0.0).with_weights(Saturation::REC2020_WEIGHTS);
+        assert_approx_eq!(
+            rec2020.grade_rgb([1.0, 0.0, 0.0])[1],
+            Saturation::REC2020_WEIGHTS[0],
+            0.0001
+        );
+        assert_rgb(
+            Saturation::default().grade(COLOR),
+            [COLOR.red, COLOR.green, COLOR.blue],
+        );
+    }
+
+    #[test]
+    fn white_balance() {
+        assert_rgb(
+            WhiteBalance::default().grade(COLOR),
+            [COLOR.red, COLOR.green, COLOR.blue],
+        );
+
+        // Balancing for a warm light gives it the color of a 6504 K light.
+        let warm = kelvin_to_linear_rgba(3200.0);
+        let daylight = kelvin_to_linear_rgba(WhiteBalance::NEUTRAL_TEMPERATURE);
+        let balanced = WhiteBalance::new(3200.0, 0.0).grade(warm);
+        assert_approx_eq!(balanced.green / balanced.red, daylight.green, 0.01);
+        assert_approx_eq!(balanced.blue / balanced.red, daylight.blue, 0.01);
+
+        // A positive tint is a green light, and is corrected towards magenta.
+        let tinted = WhiteBalance::new(6504.0, 0.01).grade(LinearRgba::new(1., 1., 1., 1.));
+        assert!(tinted.green < tinted.red && tinted.green < tinted.blue);
+
+        // The cached matrix follows the settings, and is not serialized.
+        let balance = WhiteBalance::default()
+            .with_temperature(5000.)
+            .with_tint(0.002);
+        assert_eq!(balance, WhiteBalance::new(5000., 0.002));
+        let json = serde_json::to_string(&balance).unwrap();
+        assert_eq!(json, r#"{"temperature":5000.0,"tint":0.002}"#);
+        assert_eq!(
+            serde_json::from_str::<WhiteBalance>(&json).unwrap(),
+            balance
+        );
+    }
+
+    #[test]
+    fn color_grade() {
+        let grade = ColorGrade::new()
+            .then(WhiteBalance::new(5000.0, 0.002))
+            .then(AscCdl {
+                slope: [1.1, 1.0, 0.9],
+                ..AscCdl::IDENTITY
+            })
+            .then(LiftGammaGain {
+                gamma: [1.2; 3],
+                ..LiftGammaGain::IDENTITY
+            })
+            .then(Contrast::new(1.2))
+            .then(Saturation::new(0.8));
+        assert_eq!(grade.ops.len(), 5);
+
+        let mut expected = COLOR;
+        for op in &grade.ops {
+            expected = op.grade(expected);
+        }
+        assert_eq!(grade.grade(COLOR), expected);
+
+        let colors = [COLOR, LinearRgba::new(2.0, 0.0, 0.5, 1.0)];
+        let mut out = [LinearRgba::default(); 3];
+        grade.grade_slice(&colors, &mut out);
+        for (color, out) in colors.iter().zip(out.iter()) {
+            let single = grade.grade(*color);
+            assert_rgb(*out, [single.red, single.green, single.blue]);
+            assert_eq!(out.alpha, color.alpha);
+        }
+        assert_eq!(out[2], LinearRgba::default());
+        assert_eq!(ColorGrade::new().grade(COLOR), COLOR);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    gamut::{chroma_hue, max_srgb_chroma, oklch},
+    Hsla, LinearRgba, Oklaba,
+};
+
+/// The hue wheel used to rotate hues when generating a [`Harmony`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HarmonySpace {
+    /// Rotate the HSL hue. This is the traditional color wheel of most design tools, where
+    /// the complement of red is cyan.
+    Hsl,
+    /// Rotate the Oklch hue, which is perceptually more even. The complement of red is a
+    /// blue-green.
+    #[default]
+    Oklch,
+}
+
+/// A color harmony scheme, which generates a palette from a single seed color.
+///
+/// The first color of every palette has the seed's hue. All colors share the seed's Oklab
+/// lightness, and a common chroma: the seed's chroma, reduced if necessary so that every
+/// color, including the first, is within the sRGB gamut. The first color is therefore the
+/// seed itself unless one of the other hues needs a lower chroma. Only the hue is taken from
+/// the [`HarmonySpace`], so lightness and chroma stay perceptually consistent in either space.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{Harmony, HarmonySpace, SRgba};
+/// let palette = Harmony::Triadic.generate(SRgba::hex("#e63946").unwrap(), HarmonySpace::Oklch);
+/// assert_eq!(palette.len(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Harmony {
+    /// The seed and the color opposite it on the hue wheel.
+    Complementary,
+    /// The seed and the two colors on either side of its complement, 150° and 210° away.
+    SplitComplementary,
+    /// The seed and its neighbors 30° to either side.
+    Analogous,
+    /// Three colors evenly spaced around the hue wheel.
+    Triadic,
+    /// Two complementary pairs 60° apart, forming a rectangle on the hue wheel.
+    Tetradic,
+    /// Four colors evenly spaced around the hue wheel.
+    Square,
+    /// The seed followed by two darker shades and two lighter tints of the same hue. The
+    /// chroma of each, including the seed, is reduced as needed to stay within the sRGB
+    /// gamut.
+    Monochromatic,
+}
+
+impl Harmony {
+    /// All of the harmony schemes.
+    pub const ALL: [Harmony; 7] = [
+        Harmony::Complementary,
+        Harmony::SplitComplementary,
+        Harmony::Analogous,
+        Harmony::Triadic,
+        Harmony::Tetradic,
+        Harmony::Square,
+        Harmony::Monochromatic,
+    ];
+
+    /// The hue rotations in degrees of each color in the palette, relative to the seed.
+    pub const fn hue_offsets(self) -> &'static [f32] {
+        match self {
+            Harmony::Complementary => &[0., 180.],
+            Harmony::SplitComplementary => &[0., 150., 210.],
+            Harmony::Analogous => &[0., -30., 30.],
+            Harmony::Triadic => &[0., 120., 240.],
+            Harmony::Tetradic => &[0., 60., 180., 240.],
+            Harmony::Square => &[0., 90., 180., 270.],
+            Harmony::Monochromatic => &[0., 0., 0., 0., 0.],
+        }
+    }
+
+    /// Generate a palette from a seed color, in any color type which can be converted to
+    /// and from [`Oklaba`]. The alpha of the seed is kept for every color.
+    pub fn generate<T>(self, seed: T, space: HarmonySpace) -> Vec<T>
+    where
+        T: Into<Oklaba> + From<Oklaba>,
+    {
+        let oklab: Oklaba = seed.into();
+        let (chroma, hue) = chroma_hue(oklab);
+        let l = oklab.l;
+
+        if self == Harmony::Monochromatic {
+            let mut palette = Vec::with_capacity(5);
+            for lightness in [
+                l,
+                l / 3.,
+                l * 2. / 3.,
+                l + (1. - l) / 3.,
+                l + (1. - l) * 2. / 3.,
+            ] {
+                let chroma = max_srgb_chroma(lightness, hue, chroma);
+                palette.push(oklch(lightness, chroma, hue, oklab.alpha).into());
+            }
+            return palette;
+        }
+
+        let hues: Vec<f32> = std::iter::once(hue)
+            .chain(
+                self.hue_offsets()[1..]
+                    .iter()
+                    .map(|offset| rotate_hue(oklab, *offset, space)),
+            )
+            .collect();
+        let chroma = hues
+            .iter()
+            .fold(chroma, |chroma, hue| max_srgb_chroma(l, *hue, chroma));
+        hues.into_iter()
+            .map(|hue| oklch(l, chroma, hue, oklab.alpha).into())
+            .collect()
+    }
+}
+
+/// Rotate the hue of a color on the given hue wheel, returning the resulting Oklch hue.
+fn rotate_hue(color: Oklaba, degrees: f32, space: HarmonySpace) -> f32 {
+    match space {
+        HarmonySpace::Oklch => (chroma_hue(color).1 + degrees).rem_euclid(360.),
+        HarmonySpace::Hsl => {
+            let hsla = Hsla::from(color);
+            let rotated = Hsla {
+                hue: (hsla.hue + degrees).rem_euclid(360.),
+                ..hsla
+            };
+            chroma_hue(Oklaba::from(LinearRgba::from(rotated))).1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gamut::in_srgb_gamut, testing::assert_approx_eq, SRgba};
+
+    fn hue_distance(a: f32, b: f32) -> f32 {
+        let d = (a - b).rem_euclid(360.);
+        d.min(360. - d)
+    }
+
+    #[test]
+    fn palette_sizes() {
+        let seed = SRgba::hex("#3a86ff").unwrap();
+        for harmony in Harmony::ALL {
+            for space in [HarmonySpace::Hsl, HarmonySpace::Oklch] {
+                let palette = harmony.generate(seed, space);
+                assert_eq!(palette.len(), harmony.hue_offsets().len());
+                for color in palette {
+                    assert!(in_srgb_gamut(color.into()), "{:?} {:?}", harmony, color);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn oklch_hues() {
+        let seed = oklch(0.7, 0.1, 40., 1.);
+        let palette = Harmony::SplitComplementary.generate(seed, HarmonySpace::Oklch);
+        let (_, h1) = chroma_hue(palette[1]);
+        let (_, h2) = chroma_hue(palette[2]);
+        assert_approx_eq!(hue_distance(h1, 190.), 0., 0.01);
+        assert_approx_eq!(hue_distance(h2, 250.), 0., 0.01);
+
+        let palette = Harmony::Analogous.generate(seed, HarmonySpace::Oklch);
+        assert_approx_eq!(hue_distance(chroma_hue(palette[1]).1, 10.), 0., 0.01);
+    }
+
+    #[test]
+    fn consistent_lightness_and_chroma() {
+        let seed = SRgba::hex("#e63946").unwrap();
+        for space in [HarmonySpace::Hsl, HarmonySpace::Oklch] {
+            let palette: Vec<Oklaba> = Harmony::Square
+                .generate(seed, space)
+                .into_iter()
+                .map(Oklaba::from)
+                .collect();
+            let (chroma, _) = chroma_hue(palette[0]);
+            assert!(chroma <= chroma_hue(seed.into()).0 + 0.001);
+            for color in &palette[1..] {
+                assert_approx_eq!(color.l, palette[0].l, 0.002);
+                assert_approx_eq!(chroma_hue(*color).0, chroma, 0.002);
+            }
+        }
+    }
+
+    #[test]
+    fn seed_chroma() {
+        // A muted seed fits at every hue, so it is kept as is.
+        let seed = oklch(0.7, 0.05, 40., 1.);
+        let palette = Harmony::Triadic.generate(seed, HarmonySpace::Oklch);
+        assert_approx_eq!(palette[0].l, seed.l, 0.0001);
+        assert_approx_eq!(palette[0].a, seed.a, 0.0001);
+        assert_approx_eq!(palette[0].b, seed.b, 0.0001);
+
+        // A vivid seed is reduced to the common chroma, keeping its lightness and hue.
+        let seed = Oklaba::from(SRgba::RED);
+        let (seed_chroma, seed_hue) = chroma_hue(seed);
+        let palette = Harmony::Triadic.generate(seed, HarmonySpace::Oklch);
+        let (chroma, hue) = chroma_hue(palette[0]);
+        assert!(chroma < seed_chroma - 0.01);
+        assert_approx_eq!(chroma, chroma_hue(palette[1]).0, 0.0001);
+        assert_approx_eq!(hue, seed_hue, 0.01);
+        assert_approx_eq!(palette[0].l, seed.l, 0.0001);
+
+        // The same applies to a monochromatic seed outside the sRGB gamut.
+        let seed = oklch(0.7, 0.4, 40., 1.);
+        let palette = Harmony::Monochromatic.generate(seed, HarmonySpace::Oklch);
+        assert!(in_srgb_gamut(palette[0].into()));
+        assert_approx_eq!(palette[0].l, seed.l, 0.0001);
+        assert_approx_eq!(chroma_hue(palette[0]).1, 40., 0.01);
+    }
+
+    #[test]
+    fn hsl_wheel() {
+        // On the HSL wheel, the complement of pure red is cyan.
+        let palette = Harmony::Complementary.generate(SRgba::RED, HarmonySpace::Hsl);
+        let cyan = chroma_hue(Oklaba::from(SRgba::new(0., 1., 1., 1.))).1;
+        assert_approx_eq!(hue_distance(chroma_hue(palette[1].into()).1, cyan), 0., 0.5);
+
+        let palette = Harmony::Complementary.generate(SRgba::RED, HarmonySpace::Oklch);
+        let red = chroma_hue(Oklaba::from(SRgba::RED)).1;
+        let (_, hue) = chroma_hue(palette[1].into());
+        assert_approx_eq!(hue_distance(hue, red), 180., 0.5);
+    }
+
+    #[test]
+    fn monochromatic() {
+        let seed = Oklaba::from(SRgba::hex("#2a9d8f").unwrap());
+        let palette = Harmony::Monochromatic.generate(seed, HarmonySpace::Oklch);
+        let mut lightness: Vec<f32> = palette.iter().map(|c| c.l).collect();
+        assert_eq!(lightness[0], seed.l);
+        lightness.sort_by(f32::total_cmp);
+        assert_eq!(lightness[2], seed.l);
+        for color in &palette[1..] {
+            assert_approx_eq!(
+                hue_distance(chroma_hue(*color).1, chroma_hue(seed).1),
+                0.,
+                0.01
+            );
+        }
+    }
+}
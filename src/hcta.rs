@@ -0,0 +1,427 @@
+use crate::{
+    cam16::{lstar_from_y, y_from_lstar, Cam16, ViewingConditions},
+    math::matrix_multiply,
+    LinearRgba, Oklaba, SRgba, WithAlpha,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the Material Design HCT color space (hue, chroma, tone), with alpha.
+///
+/// Hue and chroma are those of the CAM16 color appearance model, and tone is CIE L*. Tone
+/// is directly related to contrast: two colors whose tones differ by 40 or 50 have a
+/// guaranteed minimum contrast ratio, regardless of their hues. This makes HCT the basis of
+/// the Material Design 3 dynamic color system, see [`crate::TonalPalette`].
+///
+/// Not every combination of hue, chroma and tone is within the sRGB gamut. When converting
+/// to RGB, the hue and tone are preserved and the chroma is reduced if necessary, matching
+/// the solver in Material Color Utilities.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Hcta {
+    pub hue: f32,
+    pub chroma: f32,
+    pub tone: f32,
+    pub alpha: f32,
+}
+
+impl Hcta {
+    /// Construct a new [`Hcta`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue` - Hue channel. [0.0, 360.0]
+    /// * `chroma` - Chroma channel. [0.0, ~150.0], the maximum depends on hue and tone.
+    /// * `tone` - Tone channel. [0.0, 100.0]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(hue: f32, chroma: f32, tone: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            chroma,
+            tone,
+            alpha,
+        }
+    }
+
+    /// Convert the Hcta color to a tuple of components (hue, chroma, tone, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.hue, self.chroma, self.tone, self.alpha)
+    }
+
+    /// Construct a new [`Hcta`] color from a tuple of components (hue, chroma, tone, alpha).
+    #[inline]
+    pub const fn from_components((hue, chroma, tone, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(hue, chroma, tone, alpha)
+    }
+
+    /// Construct an [`Hcta`] color from linear RGB components on a scale of 0 to 100.
+    fn from_linear_rgb([red, green, blue]: [f64; 3], alpha: f32) -> Self {
+        let xyz = matrix_multiply(&SRGB_TO_XYZ, [red, green, blue]);
+        let cam = Cam16::from_xyz(xyz, alpha, &ViewingConditions::default());
+        Self::new(
+            cam.hue as f32,
+            cam.chroma as f32,
+            lstar_from_y(xyz[1]) as f32,
+            alpha,
+        )
+    }
+}
+
+impl Default for Hcta {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl WithAlpha for Hcta {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl From<SRgba> for Hcta {
+    fn from(value: SRgba) -> Self {
+        Hcta::from_linear_rgb(
+            [value.red, value.green, value.blue].map(linearized),
+            value.alpha,
+        )
+    }
+}
+
+impl From<LinearRgba> for Hcta {
+    fn from(value: LinearRgba) -> Self {
+        Hcta::from_linear_rgb(
+            [value.red, value.green, value.blue].map(|c| c as f64 * 100.),
+            value.alpha,
+        )
+    }
+}
+
+impl From<Oklaba> for Hcta {
+    fn from(value: Oklaba) -> Self {
+        Hcta::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Hcta> for SRgba {
+    fn from(value: Hcta) -> Self {
+        let [red, green, blue] = solve_to_linear_rgb(value).map(delinearized);
+        SRgba::new(red, green, blue, value.alpha)
+    }
+}
+
+impl From<Hcta> for LinearRgba {
+    fn from(value: Hcta) -> Self {
+        let [red, green, blue] =
+            solve_to_linear_rgb(value).map(|c| (c / 100.).clamp(0., 1.) as f32);
+        LinearRgba::new(red, green, blue, value.alpha)
+    }
+}
+
+impl From<Hcta> for Oklaba {
+    fn from(value: Hcta) -> Self {
+        Oklaba::from(LinearRgba::from(value))
+    }
+}
+
+const SRGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.41233895, 0.35762064, 0.18051042],
+    [0.2126, 0.7152, 0.0722],
+    [0.01932141, 0.11916382, 0.95034478],
+];
+
+/// Convert an sRGB component (0 to 1) into linear light (0 to 100).
+fn linearized(component: f32) -> f64 {
+    let normalized = component as f64;
+    100. * if normalized <= 0.040449936 {
+        normalized / 12.92
+    } else {
+        ((normalized + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert linear light (0 to 100) into an sRGB component (0 to 255), without rounding.
+fn true_delinearized(component: f64) -> f64 {
+    let normalized = component / 100.;
+    255. * if normalized <= 0.0031308 {
+        normalized * 12.92
+    } else {
+        1.055 * normalized.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// Convert linear light (0 to 100) into a clamped sRGB component (0 to 1).
+fn delinearized(component: f64) -> f32 {
+    (true_delinearized(component) / 255.).clamp(0., 1.) as f32
+}
+
+// The HCT solver below is a port of `HctSolver` from Material Color Utilities, which finds
+// the in-gamut color with a given hue and tone whose chroma is closest to the target.
+
+const SCALED_DISCOUNT_FROM_LINRGB: [[f64; 3]; 3] = [
+    [
+        0.001200833568784504,
+        0.002389694492170889,
+        0.0002795742885861124,
+    ],
+    [
+        0.0005891086651375999,
+        0.0029785502573438758,
+        0.0003270666104008398,
+    ],
+    [
+        0.00010146692491640572,
+        0.0005364214359186694,
+        0.0032979401770712076,
+    ],
+];
+
+const LINRGB_FROM_SCALED_DISCOUNT: [[f64; 3]; 3] = [
+    [1373.2198709594231, -1100.4251190754821, -7.278681089101213],
+    [-271.815969077903, 559.6580465940733, -32.46047482791194],
+    [1.9622899599665666, -57.173814538844006, 308.7233197812385],
+];
+
+const Y_FROM_LINRGB: [f64; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Find the linear RGB color (0 to 100) for an HCT color, reducing chroma if it is out of
+/// gamut.
+fn solve_to_linear_rgb(hct: Hcta) -> [f64; 3] {
+    let chroma = hct.chroma as f64;
+    let lstar = hct.tone as f64;
+    let y = y_from_lstar(lstar);
+    if chroma < 0.0001 || !(0.0001..=99.9999).contains(&lstar) {
+        // Round trip through 8 bits like the reference, so that achromatic tones match.
+        let gray = (true_delinearized(y).round().clamp(0., 255.) / 255.) as f32;
+        return [linearized(gray); 3];
+    }
+    let hue = (hct.hue as f64).rem_euclid(360.).to_radians();
+    find_result_by_j(hue, chroma, y).unwrap_or_else(|| bisect_to_limit(y, hue))
+}
+
+fn chromatic_adaptation(component: f64) -> f64 {
+    let af = component.abs().powf(0.42);
+    component.signum() * 400. * af / (af + 27.13)
+}
+
+fn inverse_chromatic_adaptation(adapted: f64) -> f64 {
+    let adapted_abs = adapted.abs();
+    let base = (27.13 * adapted_abs / (400. - adapted_abs)).max(0.);
+    adapted.signum() * base.powf(1. / 0.42)
+}
+
+/// The CAM16 hue in radians of a linear RGB color.
+fn hue_of(linrgb: [f64; 3]) -> f64 {
+    let [r_a, g_a, b_a] =
+        matrix_multiply(&SCALED_DISCOUNT_FROM_LINRGB, linrgb).map(chromatic_adaptation);
+    let a = (11. * r_a - 12. * g_a + b_a) / 11.;
+    let b = (r_a + g_a - 2. * b_a) / 9.;
+    b.atan2(a)
+}
+
+fn sanitize_radians(angle: f64) -> f64 {
+    (angle + std::f64::consts::PI * 8.) % std::f64::consts::TAU
+}
+
+fn are_in_cyclic_order(a: f64, b: f64, c: f64) -> bool {
+    sanitize_radians(b - a) < sanitize_radians(c - a)
+}
+
+fn set_coordinate(source: [f64; 3], coordinate: f64, target: [f64; 3], axis: usize) -> [f64; 3] {
+    let t = (coordinate - source[axis]) / (target[axis] - source[axis]);
+    [0, 1, 2].map(|i| source[i] + (target[i] - source[i]) * t)
+}
+
+/// Returns the nth vertex of the polygon formed by intersecting the plane of constant Y
+/// with the RGB cube, or `None` if that edge of the cube doesn't intersect the plane.
+fn nth_vertex(y: f64, n: usize) -> Option<[f64; 3]> {
+    let [k_r, k_g, k_b] = Y_FROM_LINRGB;
+    let coord_a = if n % 4 <= 1 { 0. } else { 100. };
+    let coord_b = if n % 2 == 1 { 100. } else { 0. };
+    let bounded = |x: f64| (0. ..=100.).contains(&x);
+    if n < 4 {
+        let (g, b) = (coord_a, coord_b);
+        let r = (y - g * k_g - b * k_b) / k_r;
+        bounded(r).then_some([r, g, b])
+    } else if n < 8 {
+        let (b, r) = (coord_a, coord_b);
+        let g = (y - r * k_r - b * k_b) / k_g;
+        bounded(g).then_some([r, g, b])
+    } else {
+        let (r, g) = (coord_a, coord_b);
+        let b = (y - r * k_r - g * k_g) / k_b;
+        bounded(b).then_some([r, g, b])
+    }
+}
+
+/// Find the edge of the constant-Y polygon which contains the target hue.
+fn bisect_to_segment(y: f64, target_hue: f64) -> ([f64; 3], [f64; 3]) {
+    let mut segment: Option<([f64; 3], f64, [f64; 3], f64)> = None;
+    let mut uncut = true;
+    for n in 0..12 {
+        let Some(mid) = nth_vertex(y, n) else {
+            continue;
+        };
+        let mid_hue = hue_of(mid);
+        let Some((left, left_hue, right, right_hue)) = &mut segment else {
+            segment = Some((mid, mid_hue, mid, mid_hue));
+            continue;
+        };
+        if uncut || are_in_cyclic_order(*left_hue, mid_hue, *right_hue) {
+            uncut = false;
+            if are_in_cyclic_order(*left_hue, target_hue, mid_hue) {
+                *right = mid;
+                *right_hue = mid_hue;
+            } else {
+                *left = mid;
+                *left_hue = mid_hue;
+            }
+        }
+    }
+    let (left, _, right, _) = segment.unwrap_or(([0.; 3], 0., [0.; 3], 0.));
+    (left, right)
+}
+
+fn critical_plane_below(x: f64) -> i32 {
+    (x - 0.5).floor() as i32
+}
+
+fn critical_plane_above(x: f64) -> i32 {
+    (x - 0.5).ceil() as i32
+}
+
+/// The linear RGB value (0 to 100) halfway between two adjacent 8-bit sRGB values.
+fn critical_plane(index: i32) -> f64 {
+    linearized((index as f32 + 0.5) / 255.)
+}
+
+/// Find the color on the boundary of the gamut with the given Y and hue.
+fn bisect_to_limit(y: f64, target_hue: f64) -> [f64; 3] {
+    let (mut left, mut right) = bisect_to_segment(y, target_hue);
+    let mut left_hue = hue_of(left);
+    for axis in 0..3 {
+        if left[axis] == right[axis] {
+            continue;
+        }
+        let (mut l_plane, mut r_plane) = if left[axis] < right[axis] {
+            (
+                critical_plane_below(true_delinearized(left[axis])),
+                critical_plane_above(true_delinearized(right[axis])),
+            )
+        } else {
+            (
+                critical_plane_above(true_delinearized(left[axis])),
+                critical_plane_below(true_delinearized(right[axis])),
+            )
+        };
+        for _ in 0..8 {
+            if (r_plane - l_plane).abs() <= 1 {
+                break;
+            }
+            let m_plane = (l_plane + r_plane).div_euclid(2);
+            let mid = set_coordinate(left, critical_plane(m_plane), right, axis);
+            let mid_hue = hue_of(mid);
+            if are_in_cyclic_order(left_hue, target_hue, mid_hue) {
+                right = mid;
+                r_plane = m_plane;
+            } else {
+                left = mid;
+                left_hue = mid_hue;
+                l_plane = m_plane;
+            }
+        }
+    }
+    [0, 1, 2].map(|i| (left[i] + right[i]) / 2.)
+}
+
+/// Solve for the color with exactly the given hue, chroma and Y using Newton's method on J,
+/// returning `None` if it is out of gamut.
+fn find_result_by_j(hue: f64, chroma: f64, y: f64) -> Option<[f64; 3]> {
+    let vc = ViewingConditions::default();
+    let mut j = y.sqrt() * 11.;
+    let t_inner_coeff = 1. / (1.64 - 0.29f64.powf(vc.n)).powf(0.73);
+    let e_hue = 0.25 * ((hue + 2.).cos() + 3.8);
+    let p1 = e_hue * (50000. / 13.) * vc.nc * vc.ncb;
+    let (h_sin, h_cos) = hue.sin_cos();
+    for iteration in 0..5 {
+        let j_normalized = j / 100.;
+        let alpha = if chroma == 0. || j == 0. {
+            0.
+        } else {
+            chroma / j_normalized.sqrt()
+        };
+        let t = (alpha * t_inner_coeff).powf(1. / 0.9);
+        let ac = vc.aw * j_normalized.powf(1. / vc.c / vc.z);
+        let p2 = ac / vc.nbb;
+        let gamma = 23. * (p2 + 0.305) * t / (23. * p1 + 11. * t * h_cos + 108. * t * h_sin);
+        let a = gamma * h_cos;
+        let b = gamma * h_sin;
+        let rgb_a = [
+            (460. * p2 + 451. * a + 288. * b) / 1403.,
+            (460. * p2 - 891. * a - 261. * b) / 1403.,
+            (460. * p2 - 220. * a - 6300. * b) / 1403.,
+        ];
+        let linrgb = matrix_multiply(
+            &LINRGB_FROM_SCALED_DISCOUNT,
+            rgb_a.map(inverse_chromatic_adaptation),
+        );
+        if linrgb.iter().any(|c| *c < 0.) {
+            return None;
+        }
+        let fnj = Y_FROM_LINRGB[0] * linrgb[0]
+            + Y_FROM_LINRGB[1] * linrgb[1]
+            + Y_FROM_LINRGB[2] * linrgb[2];
+        if fnj <= 0. {
+            return None;
+        }
+        if iteration == 4 || (fnj - y).abs() < 0.002 {
+            if linrgb.iter().any(|c| *c > 100.01) {
+                return None;
+            }
+            return Some(linrgb);
+        }
+        // Newton's method, using 2 * fn(j) / j as the approximation of fn'(j).
+        j -= (fnj - y) * j / (2. * fnj);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_approx_eq;
+
+    #[test]
+    fn round_trip() {
+        for hex in ["ff0000", "00ff00", "0000ff", "6750a4", "808080", "123456"] {
+            let color = SRgba::hex(hex).unwrap();
+            let hct = Hcta::from(color);
+            let back = SRgba::from(hct);
+            assert_approx_eq!(back.red, color.red, 0.002);
+            assert_approx_eq!(back.green, color.green, 0.002);
+            assert_approx_eq!(back.blue, color.blue, 0.002);
+        }
+
+        let hct = Hcta::from(SRgba::hex("0000ff").unwrap());
+        assert_approx_eq!(hct.hue, 282.788, 0.001);
+        assert_approx_eq!(hct.chroma, 87.230, 0.001);
+        assert_approx_eq!(hct.tone, 32.302, 0.001);
+    }
+
+    #[test]
+    fn out_of_gamut() {
+        // The chroma is reduced, but the tone and hue are preserved.
+        let color = SRgba::from(Hcta::new(120.0, 200.0, 50.0, 1.0));
+        let hct = Hcta::from(color);
+        assert_approx_eq!(hct.tone, 50.0, 0.5);
+        assert_approx_eq!(hct.hue, 120.0, 1.0);
+        assert!(hct.chroma < 200.0);
+
+        assert_eq!(
+            SRgba::from(Hcta::new(0.0, 0.0, 100.0, 1.0)),
+            SRgba::new(1.0, 1.0, 1.0, 1.0)
+        );
+    }
+}
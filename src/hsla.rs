@@ -1,4 +1,7 @@
-use crate::{to_css_string::*, LinearRgba, LuminanceOps, Mix, SRgba, WithAlpha};
+use crate::{
+    math::EuclideanModulo, to_css_string::*, LinearRgba, LuminanceOps, Mix, Oklaba, SRgba,
+    WithAlpha,
+};
 use bevy::render::color::{Color, HslRepresentation};
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
@@ -68,14 +71,7 @@ impl Mix for Hsla {
     #[inline]
     fn mix(&self, other: &Self, factor: f32) -> Self {
         let n_factor = 1.0 - factor;
-        // TODO: Refactor this into EuclideanModulo::lerp_modulo
-        let shortest_angle = ((((other.hue - self.hue) % 360.) + 540.) % 360.) - 180.;
-        let mut hue = self.hue + shortest_angle * factor;
-        if hue < 0. {
-            hue += 360.;
-        } else if hue >= 360. {
-            hue -= 360.;
-        }
+        let hue = self.hue.lerp_modulo(other.hue, factor, 360.);
         Self {
             hue,
             saturation: self.saturation * n_factor + other.saturation * factor,
@@ -131,6 +127,12 @@ impl From<LinearRgba> for Hsla {
     }
 }
 
+impl From<Oklaba> for Hsla {
+    fn from(value: Oklaba) -> Self {
+        Hsla::from(SRgba::from(value))
+    }
+}
+
 impl From<Hsla> for Color {
     fn from(value: Hsla) -> Self {
         Color::Hsla {
@@ -0,0 +1,348 @@
+use crate::{
+    luva::{EPSILON, KAPPA, XYZ_TO_LINEAR_RGB},
+    math::EuclideanModulo,
+    Lchuva, LinearRgba, Mix, SRgba, WithAlpha,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the HSLuv color space (hue, saturation, lightness), with alpha.
+///
+/// HSLuv is a human-friendly alternative to [`crate::Hsla`] built on [`Lchuva`]: the hue is
+/// the LCh(uv) hue, lightness is CIE L\*, and saturation is scaled so that 1.0 is the edge of
+/// the sRGB gamut for that hue and lightness. Every combination of components is inside the
+/// sRGB gamut.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Hsluva {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub alpha: f32,
+}
+
+/// Color in the HPLuv color space (hue, saturation, lightness), with alpha.
+///
+/// HPLuv is the pastel variant of [`Hsluva`]. Saturation is scaled to the largest chroma
+/// that is in the sRGB gamut for all hues at that lightness, so colors of equal saturation
+/// and lightness have the same chroma. Only saturations up to 1.0 are guaranteed to be in
+/// gamut.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Hpluva {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub alpha: f32,
+}
+
+macro_rules! impl_hsluv_common {
+    ($ty:ident) => {
+        impl $ty {
+            #[doc = concat!("Construct a new [`", stringify!($ty), "`] color from components.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `hue` - Hue channel. [0.0, 360.0]
+            /// * `saturation` - Saturation channel. [0.0, 1.0]
+            /// * `lightness` - Lightness channel. [0.0, 1.0]
+            /// * `alpha` - Alpha channel. [0.0, 1.0]
+            pub const fn new(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+                Self {
+                    hue,
+                    saturation,
+                    lightness,
+                    alpha,
+                }
+            }
+
+            /// Convert the color to a tuple of components (hue, saturation, lightness, alpha).
+            #[inline]
+            pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+                (self.hue, self.saturation, self.lightness, self.alpha)
+            }
+
+            /// Construct a new color from a tuple of components (hue, saturation, lightness,
+            /// alpha).
+            #[inline]
+            pub const fn from_components(
+                (hue, saturation, lightness, alpha): (f32, f32, f32, f32),
+            ) -> Self {
+                Self::new(hue, saturation, lightness, alpha)
+            }
+        }
+
+        impl Default for $ty {
+            fn default() -> Self {
+                Self::new(0., 0., 0., 1.)
+            }
+        }
+
+        impl Mix for $ty {
+            #[inline]
+            fn mix(&self, other: &Self, factor: f32) -> Self {
+                let n_factor = 1.0 - factor;
+                let hue = self.hue.lerp_modulo(other.hue, factor, 360.);
+                Self {
+                    hue,
+                    saturation: self.saturation * n_factor + other.saturation * factor,
+                    lightness: self.lightness * n_factor + other.lightness * factor,
+                    alpha: self.alpha * n_factor + other.alpha * factor,
+                }
+            }
+        }
+
+        impl WithAlpha for $ty {
+            #[inline]
+            fn with_alpha(&self, alpha: f32) -> Self {
+                Self { alpha, ..*self }
+            }
+        }
+
+        impl From<$ty> for Lchuva {
+            fn from(value: $ty) -> Self {
+                let $ty {
+                    hue,
+                    saturation,
+                    lightness,
+                    alpha,
+                } = value;
+                if !(1e-10..=1. - 1e-6).contains(&lightness) {
+                    return Lchuva::new(lightness.clamp(0., 1.), 0., hue, alpha);
+                }
+                let chroma = $ty::max_chroma(lightness, hue) * saturation;
+                Lchuva::new(lightness, chroma, hue, alpha)
+            }
+        }
+
+        impl From<Lchuva> for $ty {
+            fn from(value: Lchuva) -> Self {
+                let Lchuva {
+                    lightness,
+                    chroma,
+                    hue,
+                    alpha,
+                } = value;
+                if !(1e-10..=1. - 1e-6).contains(&lightness) {
+                    return $ty::new(hue, 0., lightness.clamp(0., 1.), alpha);
+                }
+                let saturation = chroma / $ty::max_chroma(lightness, hue);
+                $ty::new(hue, saturation, lightness, alpha)
+            }
+        }
+
+        impl From<LinearRgba> for $ty {
+            fn from(value: LinearRgba) -> Self {
+                $ty::from(Lchuva::from(value))
+            }
+        }
+
+        impl From<$ty> for LinearRgba {
+            fn from(value: $ty) -> Self {
+                LinearRgba::from(Lchuva::from(value))
+            }
+        }
+
+        impl From<SRgba> for $ty {
+            fn from(value: SRgba) -> Self {
+                $ty::from(Lchuva::from(value))
+            }
+        }
+
+        impl From<$ty> for SRgba {
+            fn from(value: $ty) -> Self {
+                SRgba::from(Lchuva::from(value))
+            }
+        }
+    };
+}
+
+impl_hsluv_common!(Hsluva);
+impl_hsluv_common!(Hpluva);
+
+impl Hsluva {
+    /// The largest in-gamut LCh(uv) chroma for a lightness and hue.
+    fn max_chroma(lightness: f32, hue: f32) -> f32 {
+        let (sin, cos) = (hue as f64).to_radians().sin_cos();
+        gamut_bounds(lightness)
+            .into_iter()
+            .map(|(slope, intercept)| intercept / (sin - slope * cos))
+            .filter(|length| *length >= 0.)
+            .fold(f64::INFINITY, f64::min) as f32
+    }
+}
+
+impl Hpluva {
+    /// The largest LCh(uv) chroma that is in gamut for every hue at a lightness.
+    fn max_chroma(lightness: f32, _hue: f32) -> f32 {
+        gamut_bounds(lightness)
+            .into_iter()
+            .map(|(slope, intercept)| intercept.abs() / slope.hypot(1.))
+            .fold(f64::INFINITY, f64::min) as f32
+    }
+}
+
+/// The six lines, as (slope, intercept) pairs in the (u, v) plane, where one of the linear
+/// sRGB channels is 0 or 1 at a lightness. The sRGB gamut at that lightness is the polygon
+/// they enclose. Intercepts are scaled like [`crate::Luva`] components.
+fn gamut_bounds(lightness: f32) -> [(f64, f64); 6] {
+    let l = lightness as f64 * 100.;
+    let sub1 = (l + 16.).powi(3) / 1_560_896.;
+    let sub2 = if sub1 > EPSILON as f64 {
+        sub1
+    } else {
+        l / KAPPA as f64
+    };
+    let mut bounds = [(0., 0.); 6];
+    for (channel, row) in XYZ_TO_LINEAR_RGB.iter().enumerate() {
+        let [m1, m2, m3] = row.map(f64::from);
+        for t in 0..2 {
+            let t = t as f64;
+            let top1 = (284_517. * m1 - 94_839. * m3) * sub2;
+            let top2 =
+                (838_422. * m3 + 769_860. * m2 + 731_718. * m1) * l * sub2 - 769_860. * t * l;
+            let bottom = (632_260. * m3 - 126_452. * m2) * sub2 + 126_452. * t;
+            bounds[channel * 2 + t as usize] = (top1 / bottom, top2 / bottom / 100.);
+        }
+    }
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq, Luva};
+
+    /// Snapshot of the HSLuv reference implementation, in the format of the official
+    /// `snapshot-rev4.json`. See `data/README.md` for how it was made.
+    const SNAPSHOT: &str = include_str!("../data/hsluv_snapshot.json");
+
+    fn assert_triple(name: &str, space: &str, actual: [f32; 3], expected: &serde_json::Value) {
+        let expected: Vec<f32> = expected[space]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_f64().unwrap() as f32)
+            .collect();
+        // The reference is computed in `f64`, so allow for the rounding of `f32`, relative to
+        // the magnitude of the value as HPLuv saturation goes far beyond 100.
+        for i in 0..3 {
+            assert!(
+                (actual[i] - expected[i]).abs() <= 1e-5 * expected[i].abs().max(100.),
+                "{} {}: {:?} != {:?}",
+                name,
+                space,
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn snapshot() {
+        let snapshot: serde_json::Value = serde_json::from_str(SNAPSHOT).unwrap();
+        let entries = snapshot.as_object().unwrap();
+        assert!(!entries.is_empty());
+        for (hex, expected) in entries {
+            let rgb = SRgba::hex(hex).unwrap();
+            let luv = Luva::from(rgb);
+            let lch = Lchuva::from(rgb);
+            let hsluv = Hsluva::from(rgb);
+            let hpluv = Hpluva::from(rgb);
+            // The hue of grays is meaningless, so only compare it for chromatic colors.
+            let hue = |h: f32| {
+                if lch.chroma < 1e-4 {
+                    expected["lchuv"][2].as_f64().unwrap() as f32
+                } else {
+                    h
+                }
+            };
+            assert_triple(
+                hex,
+                "luv",
+                [luv.l * 100., luv.u * 100., luv.v * 100.],
+                expected,
+            );
+            assert_triple(
+                hex,
+                "lchuv",
+                [lch.lightness * 100., lch.chroma * 100., hue(lch.hue)],
+                expected,
+            );
+            let saturation = |s: f32| if lch.chroma < 1e-4 { 0. } else { s * 100. };
+            assert_triple(
+                hex,
+                "hsluv",
+                [
+                    hue(hsluv.hue),
+                    saturation(hsluv.saturation),
+                    hsluv.lightness * 100.,
+                ],
+                expected,
+            );
+            assert_triple(
+                hex,
+                "hpluv",
+                [
+                    hue(hpluv.hue),
+                    saturation(hpluv.saturation),
+                    hpluv.lightness * 100.,
+                ],
+                expected,
+            );
+
+            // And back to sRGB.
+            let hsluv = [
+                expected["hsluv"][0].as_f64().unwrap() as f32,
+                expected["hsluv"][1].as_f64().unwrap() as f32 / 100.,
+                expected["hsluv"][2].as_f64().unwrap() as f32 / 100.,
+            ];
+            let back = SRgba::from(Hsluva::new(hsluv[0], hsluv[1], hsluv[2], 1.));
+            assert_approx_eq!(back.red, rgb.red, 0.0001);
+            assert_approx_eq!(back.green, rgb.green, 0.0001);
+            assert_approx_eq!(back.blue, rgb.blue, 0.0001);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for color in TEST_COLORS.iter() {
+            for back in [
+                SRgba::from(Hsluva::from(color.rgb)),
+                SRgba::from(Hpluva::from(color.rgb)),
+            ] {
+                assert_approx_eq!(back.red, color.rgb.red, 0.001);
+                assert_approx_eq!(back.green, color.rgb.green, 0.001);
+                assert_approx_eq!(back.blue, color.rgb.blue, 0.001);
+                assert_approx_eq!(back.alpha, color.rgb.alpha, 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn gamut_edges() {
+        for color in [
+            SRgba::RED,
+            SRgba::GREEN,
+            SRgba::BLUE,
+            SRgba::new(1., 1., 0., 1.),
+            SRgba::new(0., 1., 1., 1.),
+            SRgba::new(1., 0., 1., 1.),
+        ] {
+            assert_approx_eq!(Hsluva::from(color).saturation, 1.0, 0.0001);
+            assert!(Hpluva::from(color).saturation > 1.);
+        }
+        for hue in (0..360).step_by(15) {
+            for lightness in [0.05, 0.3, 0.5, 0.7, 0.95] {
+                for color in [
+                    LinearRgba::from(Hsluva::new(hue as f32, 1., lightness, 1.)),
+                    LinearRgba::from(Hpluva::new(hue as f32, 1., lightness, 1.)),
+                ] {
+                    for channel in [color.red, color.green, color.blue] {
+                        assert!((-0.0001..=1.0001).contains(&channel), "{:?}", color);
+                    }
+                }
+            }
+        }
+    }
+}
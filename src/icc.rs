@@ -0,0 +1,537 @@
+//! Parsing of ICC color profiles.
+//!
+//! Only matrix/TRC ("matrix-shaper") RGB profiles are supported, which covers the profiles
+//! usually embedded in images for display color spaces such as Display P3 or Adobe RGB.
+//! Such a profile maps device RGB to the D50 profile connection space with a tone
+//! reproduction curve (TRC) per channel followed by a 3x3 matrix.
+
+use crate::{
+    math::{bradford, invert, matrix_multiply, multiply, Matrix3},
+    LinearRgba, Xyza,
+};
+use std::fmt;
+
+/// An error which occurs when parsing an ICC profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IccError {
+    /// The data is shorter than its header claims, or a tag points outside of it.
+    Truncated,
+    /// The data does not have the `acsp` signature of an ICC profile.
+    NotAProfile,
+    /// The profile major version is not 2 or 4.
+    UnsupportedVersion(u8),
+    /// The data color space of the profile is not RGB, or its connection space is not XYZ.
+    UnsupportedColorSpace(String),
+    /// The profile converts colors with lookup tables rather than a matrix and curves.
+    LutBased,
+    /// A tag required for a matrix/TRC profile is missing.
+    MissingTag(String),
+    /// A tag has an unsupported type or malformed contents.
+    InvalidTag(String),
+}
+
+impl fmt::Display for IccError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IccError::Truncated => write!(f, "ICC profile data is truncated"),
+            IccError::NotAProfile => write!(f, "Data is not an ICC profile"),
+            IccError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported ICC profile version: {version}")
+            }
+            IccError::UnsupportedColorSpace(space) => {
+                write!(f, "Unsupported ICC profile color space: {space}")
+            }
+            IccError::LutBased => write!(
+                f,
+                "LUT-based ICC profiles are not supported, only matrix/TRC profiles"
+            ),
+            IccError::MissingTag(tag) => write!(f, "ICC profile is missing the {tag} tag"),
+            IccError::InvalidTag(tag) => write!(f, "Invalid ICC profile tag: {tag}"),
+        }
+    }
+}
+
+impl std::error::Error for IccError {}
+
+/// A tone reproduction curve, which maps an encoded device value to a linear one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToneCurve {
+    /// A pure power function, `y = x^gamma`. A gamma of 1.0 is the identity.
+    Gamma(f32),
+    /// An ICC `parametricCurveType` function. `function` is the function type, from 0 to 4,
+    /// and `params` holds its parameters (g, a, b, c, d, e, f), with unused ones set to 0.
+    Parametric { function: u16, params: [f32; 7] },
+    /// Samples evenly spaced over [0, 1], linearly interpolated.
+    Table(Vec<f32>),
+}
+
+impl ToneCurve {
+    /// Map an encoded value in [0, 1] to a linear value.
+    pub fn eval(&self, x: f32) -> f32 {
+        let x = x.clamp(0., 1.);
+        match self {
+            ToneCurve::Gamma(gamma) => x.powf(*gamma),
+            ToneCurve::Parametric { function, params } => {
+                let [g, a, b, c, d, e, f] = *params;
+                let power = |x: f32| (a * x + b).max(0.).powf(g);
+                match function {
+                    0 => x.powf(g),
+                    1 if x >= -b / a => power(x),
+                    1 => 0.,
+                    2 if x >= -b / a => power(x) + c,
+                    2 => c,
+                    3 if x >= d => power(x),
+                    3 => c * x,
+                    _ if x >= d => power(x) + e,
+                    _ => c * x + f,
+                }
+            }
+            ToneCurve::Table(table) => {
+                let position = x * (table.len() - 1) as f32;
+                let index = (position as usize).min(table.len() - 2);
+                let t = position - index as f32;
+                table[index] * (1. - t) + table[index + 1] * t
+            }
+        }
+    }
+
+    /// Map a linear value to an encoded value in [0, 1]. The curve must be monotonically
+    /// increasing, which it is in any usable profile.
+    pub fn eval_inverse(&self, y: f32) -> f32 {
+        if let ToneCurve::Gamma(gamma) = self {
+            return y.clamp(0., 1.).powf(1. / gamma);
+        }
+        let (mut low, mut high) = (0., 1.);
+        for _ in 0..32 {
+            let mid = (low + high) * 0.5;
+            if self.eval(mid) < y {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low + high) * 0.5
+    }
+}
+
+/// The D50 illuminant of the ICC profile connection space.
+const D50_WHITE: [f32; 3] = [0.9642, 1.0, 0.8249];
+
+/// A parsed matrix/TRC RGB profile, which converts between device RGB values and
+/// [`Xyza`] or [`LinearRgba`].
+///
+/// The profile connection space is relative to D50, and is adapted to the D65 white of
+/// [`Xyza`] with the Bradford transform, so conversions are relative colorimetric: the
+/// white of the profile maps to the white of sRGB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IccProfile {
+    version: (u8, u8),
+    description: Option<String>,
+    media_white_point: Xyza,
+    chromatic_adaptation: Option<Matrix3>,
+    colorants: Matrix3,
+    curves: [ToneCurve; 3],
+    to_xyz: Matrix3,
+    from_xyz: Matrix3,
+}
+
+impl IccProfile {
+    /// Parse an ICC v2 or v4 matrix/TRC RGB profile.
+    pub fn parse(data: &[u8]) -> Result<Self, IccError> {
+        let reader = Reader(data);
+        if data.len() < 132 || (reader.u32(0)? as usize) > data.len() {
+            return Err(IccError::Truncated);
+        }
+        if reader.signature(36)? != *b"acsp" {
+            return Err(IccError::NotAProfile);
+        }
+        let version = (data[8], data[9] >> 4);
+        if version.0 != 2 && version.0 != 4 {
+            return Err(IccError::UnsupportedVersion(version.0));
+        }
+        let color_space = reader.signature(16)?;
+        let connection_space = reader.signature(20)?;
+        if color_space != *b"RGB " {
+            return Err(IccError::UnsupportedColorSpace(signature_name(color_space)));
+        }
+
+        let mut tags = Vec::new();
+        let count = reader.u32(128)? as usize;
+        for i in 0..count {
+            let entry = 132 + i * 12;
+            let offset = reader.u32(entry + 4)? as usize;
+            let size = reader.u32(entry + 8)? as usize;
+            let tag = data
+                .get(offset..offset.checked_add(size).ok_or(IccError::Truncated)?)
+                .ok_or(IccError::Truncated)?;
+            tags.push((reader.signature(entry)?, tag));
+        }
+        let find = |name: &[u8; 4]| tags.iter().find(|(sig, _)| sig == name).map(|t| t.1);
+        let required =
+            |name: &[u8; 4]| find(name).ok_or_else(|| IccError::MissingTag(signature_name(*name)));
+
+        let has_lut = [b"A2B0", b"A2B1", b"B2A0"]
+            .into_iter()
+            .any(|name| find(name).is_some());
+        if connection_space == *b"Lab " || (has_lut && find(b"rXYZ").is_none()) {
+            return Err(IccError::LutBased);
+        }
+        if connection_space != *b"XYZ " {
+            return Err(IccError::UnsupportedColorSpace(signature_name(
+                connection_space,
+            )));
+        }
+
+        let mut colorants = [[0.; 3]; 3];
+        for (channel, name) in [b"rXYZ", b"gXYZ", b"bXYZ"].into_iter().enumerate() {
+            let xyz = parse_xyz(required(name)?, name)?;
+            for (row, value) in xyz.into_iter().enumerate() {
+                colorants[row][channel] = value;
+            }
+        }
+        let curves = [
+            parse_curve(required(b"rTRC")?, b"rTRC")?,
+            parse_curve(required(b"gTRC")?, b"gTRC")?,
+            parse_curve(required(b"bTRC")?, b"bTRC")?,
+        ];
+        let chromatic_adaptation = find(b"chad").map(parse_chad).transpose()?;
+        let media_white_point = match chromatic_adaptation {
+            // Version 4 profiles store D50 as the white point, and the media white is
+            // recovered from the adaptation matrix.
+            Some(chad) if version.0 == 4 || find(b"wtpt").is_none() => {
+                matrix_multiply(&invert(&chad), D50_WHITE)
+            }
+            _ => match find(b"wtpt") {
+                Some(tag) => parse_xyz(tag, b"wtpt")?,
+                None => D50_WHITE,
+            },
+        };
+        let description = find(b"desc").and_then(parse_description);
+
+        let to_xyz = multiply(&bradford(D50_WHITE, D65_WHITE), &colorants);
+        let [x, y, z] = media_white_point;
+        Ok(Self {
+            version,
+            description,
+            media_white_point: Xyza::new(x, y, z, 1.),
+            chromatic_adaptation,
+            colorants,
+            curves,
+            to_xyz,
+            from_xyz: invert(&to_xyz),
+        })
+    }
+
+    /// The profile version, as (major, minor).
+    pub fn version(&self) -> (u8, u8) {
+        self.version
+    }
+
+    /// The profile description, if it has one in a supported text format.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The absolute white point of the device, as XYZ with a luminance of 1.0. Note that it
+    /// is not adapted to D65.
+    pub fn media_white_point(&self) -> Xyza {
+        self.media_white_point
+    }
+
+    /// The chromatic adaptation (`chad`) matrix from the media white to D50, if any.
+    pub fn chromatic_adaptation(&self) -> Option<[[f32; 3]; 3]> {
+        self.chromatic_adaptation
+    }
+
+    /// The matrix from linear device RGB to the D50 connection space, whose columns are the
+    /// red, green and blue colorants.
+    pub fn colorants(&self) -> [[f32; 3]; 3] {
+        self.colorants
+    }
+
+    /// The tone reproduction curves of the red, green and blue channels.
+    pub fn curves(&self) -> &[ToneCurve; 3] {
+        &self.curves
+    }
+
+    /// Convert encoded device RGB values and alpha to XYZ.
+    pub fn device_to_xyza(&self, [red, green, blue, alpha]: [f32; 4]) -> Xyza {
+        let linear = [
+            self.curves[0].eval(red),
+            self.curves[1].eval(green),
+            self.curves[2].eval(blue),
+        ];
+        let [x, y, z] = matrix_multiply(&self.to_xyz, linear);
+        Xyza::new(x, y, z, alpha)
+    }
+
+    /// Convert XYZ to encoded device RGB values and alpha. Colors outside of the gamut of
+    /// the profile are clamped.
+    pub fn xyza_to_device(&self, color: Xyza) -> [f32; 4] {
+        let [red, green, blue] = matrix_multiply(&self.from_xyz, [color.x, color.y, color.z]);
+        [
+            self.curves[0].eval_inverse(red),
+            self.curves[1].eval_inverse(green),
+            self.curves[2].eval_inverse(blue),
+            color.alpha,
+        ]
+    }
+
+    /// Convert encoded device RGB values and alpha to linear sRGB. The result is not
+    /// clamped, so colors outside of the sRGB gamut have components outside of [0, 1].
+    pub fn device_to_linear_rgba(&self, device: [f32; 4]) -> LinearRgba {
+        LinearRgba::from(self.device_to_xyza(device))
+    }
+
+    /// Convert linear sRGB to encoded device RGB values and alpha. Colors outside of the
+    /// gamut of the profile are clamped.
+    pub fn linear_rgba_to_device(&self, color: LinearRgba) -> [f32; 4] {
+        self.xyza_to_device(Xyza::from(color))
+    }
+}
+
+const D65_WHITE: [f32; 3] = [Xyza::D65_WHITE.x, Xyza::D65_WHITE.y, Xyza::D65_WHITE.z];
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N], IccError> {
+        self.0
+            .get(offset..offset + N)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(IccError::Truncated)
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, IccError> {
+        self.bytes(offset).map(u16::from_be_bytes)
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, IccError> {
+        self.bytes(offset).map(u32::from_be_bytes)
+    }
+
+    fn s15_fixed16(&self, offset: usize) -> Result<f32, IccError> {
+        self.bytes(offset)
+            .map(|bytes| i32::from_be_bytes(bytes) as f32 / 65536.)
+    }
+
+    fn signature(&self, offset: usize) -> Result<[u8; 4], IccError> {
+        self.bytes(offset)
+    }
+}
+
+fn signature_name(signature: [u8; 4]) -> String {
+    String::from_utf8_lossy(&signature).trim_end().to_string()
+}
+
+fn invalid(name: &[u8; 4]) -> IccError {
+    IccError::InvalidTag(signature_name(*name))
+}
+
+fn parse_xyz(tag: &[u8], name: &[u8; 4]) -> Result<[f32; 3], IccError> {
+    let reader = Reader(tag);
+    if reader.signature(0)? != *b"XYZ " {
+        return Err(invalid(name));
+    }
+    Ok([
+        reader.s15_fixed16(8)?,
+        reader.s15_fixed16(12)?,
+        reader.s15_fixed16(16)?,
+    ])
+}
+
+fn parse_chad(tag: &[u8]) -> Result<Matrix3, IccError> {
+    let reader = Reader(tag);
+    if reader.signature(0)? != *b"sf32" {
+        return Err(invalid(b"chad"));
+    }
+    let mut matrix = [[0.; 3]; 3];
+    for (i, value) in matrix.iter_mut().flatten().enumerate() {
+        *value = reader.s15_fixed16(8 + i * 4)?;
+    }
+    Ok(matrix)
+}
+
+fn parse_curve(tag: &[u8], name: &[u8; 4]) -> Result<ToneCurve, IccError> {
+    let reader = Reader(tag);
+    match &reader.signature(0)? {
+        b"curv" => match reader.u32(8)? {
+            0 => Ok(ToneCurve::Gamma(1.)),
+            1 => Ok(ToneCurve::Gamma(reader.u16(12)? as f32 / 256.)),
+            count => (0..count as usize)
+                .map(|i| reader.u16(12 + i * 2).map(|v| v as f32 / 65535.))
+                .collect::<Result<_, _>>()
+                .map(ToneCurve::Table),
+        },
+        b"para" => {
+            let function = reader.u16(8)?;
+            let count = match function {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => return Err(invalid(name)),
+            };
+            let mut params = [0.; 7];
+            for (i, param) in params.iter_mut().take(count).enumerate() {
+                *param = reader.s15_fixed16(12 + i * 4)?;
+            }
+            Ok(ToneCurve::Parametric { function, params })
+        }
+        _ => Err(invalid(name)),
+    }
+}
+
+/// Read a `textDescriptionType` (v2) or the first record of a `multiLocalizedUnicodeType`
+/// (v4) description.
+fn parse_description(tag: &[u8]) -> Option<String> {
+    let reader = Reader(tag);
+    match &reader.signature(0).ok()? {
+        b"desc" => {
+            let length = reader.u32(8).ok()? as usize;
+            let text = tag.get(12..12 + length)?;
+            let text = text.split(|b| *b == 0).next()?;
+            Some(String::from_utf8_lossy(text).into_owned())
+        }
+        b"mluc" => {
+            if reader.u32(8).ok()? == 0 {
+                return None;
+            }
+            let length = reader.u32(20).ok()? as usize;
+            let offset = reader.u32(24).ok()? as usize;
+            let units: Vec<u16> = tag
+                .get(offset..offset + length)?
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16(&units).ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq, SRgba};
+
+    // Minimal profiles built from the published primaries and transfer functions of each
+    // color space, one for each kind of tag this parser supports.
+    const DISPLAY_P3: &[u8] = include_bytes!("../data/icc/display_p3_v4.icc");
+    const ADOBE_RGB: &[u8] = include_bytes!("../data/icc/adobe_rgb_v2.icc");
+    const SRGB_TABLE: &[u8] = include_bytes!("../data/icc/srgb_table_v2.icc");
+    const LUT_BASED: &[u8] = include_bytes!("../data/icc/lut_based_v4.icc");
+
+    fn assert_linear_eq(actual: LinearRgba, expected: [f32; 3], tolerance: f32) {
+        assert_approx_eq!(actual.red, expected[0], tolerance);
+        assert_approx_eq!(actual.green, expected[1], tolerance);
+        assert_approx_eq!(actual.blue, expected[2], tolerance);
+    }
+
+    #[test]
+    fn display_p3() {
+        let profile = IccProfile::parse(DISPLAY_P3).unwrap();
+        assert_eq!(profile.version(), (4, 3));
+        assert_eq!(profile.description(), Some("Display P3"));
+        assert!(matches!(
+            profile.curves()[0],
+            ToneCurve::Parametric { function: 3, .. }
+        ));
+        let white = profile.media_white_point();
+        assert_approx_eq!(white.x, 0.9505, 0.001);
+        assert_approx_eq!(white.z, 1.0888, 0.001);
+
+        assert_linear_eq(
+            profile.device_to_linear_rgba([1., 1., 1., 1.]),
+            [1., 1., 1.],
+            0.001,
+        );
+        assert_linear_eq(
+            profile.device_to_linear_rgba([1., 0., 0., 1.]),
+            [1.2249, -0.0421, -0.0196],
+            0.001,
+        );
+        let gray = profile.device_to_linear_rgba([0.5, 0.5, 0.5, 0.5]);
+        assert_linear_eq(gray, [0.214, 0.214, 0.214], 0.001);
+        assert_eq!(gray.alpha, 0.5);
+    }
+
+    #[test]
+    fn adobe_rgb() {
+        let profile = IccProfile::parse(ADOBE_RGB).unwrap();
+        assert_eq!(profile.version(), (2, 1));
+        assert_eq!(profile.description(), Some("Adobe RGB (1998) compatible"));
+        assert_eq!(profile.curves()[1], ToneCurve::Gamma(563. / 256.));
+        assert_eq!(profile.chromatic_adaptation(), None);
+        assert_approx_eq!(profile.media_white_point().x, 0.9505, 0.001);
+
+        assert_linear_eq(
+            profile.device_to_linear_rgba([1., 0., 0., 1.]),
+            [1.3983, 0., 0.],
+            0.001,
+        );
+        assert_linear_eq(
+            profile.device_to_linear_rgba([0.5, 0.5, 0.5, 1.]),
+            [0.2176, 0.2176, 0.2176],
+            0.001,
+        );
+        let xyz = profile.device_to_xyza([1., 1., 1., 1.]);
+        assert_approx_eq!(xyz.x, Xyza::D65_WHITE.x, 0.001);
+        assert_approx_eq!(xyz.y, Xyza::D65_WHITE.y, 0.001);
+        assert_approx_eq!(xyz.z, Xyza::D65_WHITE.z, 0.001);
+    }
+
+    #[test]
+    fn table_curves() {
+        let profile = IccProfile::parse(SRGB_TABLE).unwrap();
+        assert!(matches!(&profile.curves()[2], ToneCurve::Table(t) if t.len() == 256));
+        for color in TEST_COLORS.iter() {
+            let SRgba {
+                red, green, blue, ..
+            } = color.rgb;
+            let linear = profile.device_to_linear_rgba([red, green, blue, 1.]);
+            let expected = color.linear_rgb;
+            assert_linear_eq(linear, [expected.red, expected.green, expected.blue], 0.002);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for data in [DISPLAY_P3, ADOBE_RGB, SRGB_TABLE] {
+            let profile = IccProfile::parse(data).unwrap();
+            for device in [[0., 0., 0., 1.], [1., 1., 1., 1.], [0.2, 0.5, 0.8, 0.3]] {
+                let back = profile.linear_rgba_to_device(profile.device_to_linear_rgba(device));
+                for (back, device) in back.into_iter().zip(device) {
+                    assert_approx_eq!(back, device, 0.001);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(IccProfile::parse(LUT_BASED), Err(IccError::LutBased));
+        assert_eq!(IccProfile::parse(&[0; 64]), Err(IccError::Truncated));
+        assert_eq!(
+            IccProfile::parse(&ADOBE_RGB[..200]),
+            Err(IccError::Truncated)
+        );
+        let mut not_a_profile = ADOBE_RGB.to_vec();
+        not_a_profile[36..40].copy_from_slice(b"nope");
+        assert_eq!(
+            IccProfile::parse(&not_a_profile),
+            Err(IccError::NotAProfile)
+        );
+        let mut cmyk = ADOBE_RGB.to_vec();
+        cmyk[16..20].copy_from_slice(b"CMYK");
+        assert_eq!(
+            IccProfile::parse(&cmyk),
+            Err(IccError::UnsupportedColorSpace("CMYK".into()))
+        );
+        assert_eq!(
+            IccError::LutBased.to_string(),
+            "LUT-based ICC profiles are not supported, only matrix/TRC profiles"
+        );
+    }
+}
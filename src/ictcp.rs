@@ -0,0 +1,195 @@
+use crate::{
+    math::matrix_multiply,
+    pq_eotf, pq_inverse_eotf,
+    transfer::{BT2020_TO_BT709, BT709_TO_BT2020},
+    LinearRgba, Mix, SRgba, WithAlpha, SDR_REFERENCE_WHITE,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the ICtCp color space of ITU-R BT.2100, with alpha.
+///
+/// ICtCp separates intensity (I) from blue-yellow (Ct) and red-green (Cp) chroma, and is
+/// designed for HDR signals with the PQ transfer function and BT.2020 primaries. It is
+/// defined on absolute luminance. The [`From`] conversions place white at
+/// [`SDR_REFERENCE_WHITE`]; use [`Ictcpa::from_linear_rgba`] to choose another reference
+/// white.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Ictcpa {
+    pub i: f32,
+    pub ct: f32,
+    pub cp: f32,
+    pub alpha: f32,
+}
+
+impl Ictcpa {
+    /// Construct a new [`Ictcpa`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Intensity channel. [0.0, 1.0], where 1.0 is 10000 nits.
+    /// * `ct` - Blue-yellow channel. [-0.5, 0.5]
+    /// * `cp` - Red-green channel. [-0.5, 0.5]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(i: f32, ct: f32, cp: f32, alpha: f32) -> Self {
+        Self { i, ct, cp, alpha }
+    }
+
+    /// Convert the Ictcpa color to a tuple of components (i, ct, cp, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.i, self.ct, self.cp, self.alpha)
+    }
+
+    /// Construct a new [`Ictcpa`] color from a tuple of components (i, ct, cp, alpha).
+    #[inline]
+    pub const fn from_components((i, ct, cp, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(i, ct, cp, alpha)
+    }
+
+    /// Convert a linear RGB color, where white has a luminance of `reference_white` nits.
+    pub fn from_linear_rgba(color: LinearRgba, reference_white: f32) -> Self {
+        let rgb = [color.red, color.green, color.blue];
+        let rgb2020 = matrix_multiply(&BT709_TO_BT2020, rgb).map(|c| c * reference_white);
+        let lms = matrix_multiply(&BT2020_TO_LMS, rgb2020).map(pq_inverse_eotf);
+        let [i, ct, cp] = matrix_multiply(&LMS_TO_ICTCP, lms);
+        Self::new(i, ct, cp, color.alpha)
+    }
+
+    /// Convert into a linear RGB color, where white has a luminance of `reference_white`
+    /// nits. The result is not clamped.
+    pub fn to_linear_rgba(self, reference_white: f32) -> LinearRgba {
+        let lms = matrix_multiply(&ICTCP_TO_LMS, [self.i, self.ct, self.cp]).map(pq_eotf);
+        let rgb2020 = matrix_multiply(&LMS_TO_BT2020, lms).map(|c| c / reference_white);
+        let [red, green, blue] = matrix_multiply(&BT2020_TO_BT709, rgb2020);
+        LinearRgba::new(red, green, blue, self.alpha)
+    }
+
+    /// The color difference ΔE<sub>ITP</sub> of ITU-R BT.2124 between this and another
+    /// color. A difference of 1.0 is roughly a just noticeable difference.
+    pub fn difference(&self, other: &Self) -> f32 {
+        720. * ((self.i - other.i).powi(2)
+            + (0.5 * (self.ct - other.ct)).powi(2)
+            + (self.cp - other.cp).powi(2))
+        .sqrt()
+    }
+}
+
+impl Default for Ictcpa {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Ictcpa {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            i: self.i * n_factor + other.i * factor,
+            ct: self.ct * n_factor + other.ct * factor,
+            cp: self.cp * n_factor + other.cp * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Ictcpa {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl From<LinearRgba> for Ictcpa {
+    fn from(value: LinearRgba) -> Self {
+        Ictcpa::from_linear_rgba(value, SDR_REFERENCE_WHITE)
+    }
+}
+
+impl From<Ictcpa> for LinearRgba {
+    fn from(value: Ictcpa) -> Self {
+        value.to_linear_rgba(SDR_REFERENCE_WHITE)
+    }
+}
+
+impl From<SRgba> for Ictcpa {
+    fn from(value: SRgba) -> Self {
+        Ictcpa::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Ictcpa> for SRgba {
+    fn from(value: Ictcpa) -> Self {
+        SRgba::from(LinearRgba::from(value))
+    }
+}
+
+const BT2020_TO_LMS: [[f32; 3]; 3] = [
+    [1688. / 4096., 2146. / 4096., 262. / 4096.],
+    [683. / 4096., 2951. / 4096., 462. / 4096.],
+    [99. / 4096., 309. / 4096., 3688. / 4096.],
+];
+
+const LMS_TO_BT2020: [[f32; 3]; 3] = [
+    [3.436_606_7, -2.506_452, 0.069_845_42],
+    [-0.791_329_6, 1.983_600_5, -0.192_270_9],
+    [-0.025_949_9, -0.098_913_71, 1.124_863_6],
+];
+
+const LMS_TO_ICTCP: [[f32; 3]; 3] = [
+    [2048. / 4096., 2048. / 4096., 0.],
+    [6610. / 4096., -13613. / 4096., 7003. / 4096.],
+    [17933. / 4096., -17390. / 4096., -543. / 4096.],
+];
+
+const ICTCP_TO_LMS: [[f32; 3]; 3] = [
+    [1., 0.008_609_037, 0.111_029_625],
+    [1., -0.008_609_037, -0.111_029_625],
+    [1., 0.560_031_3, -0.320_627_18],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn reference_values() {
+        let white = Ictcpa::from(SRgba::WHITE);
+        assert_approx_eq!(white.i, 0.580689, 0.00001);
+        assert_approx_eq!(white.ct, 0., 0.00001);
+        assert_approx_eq!(white.cp, 0., 0.00001);
+
+        let red = Ictcpa::from(SRgba::RED);
+        assert_approx_eq!(red.i, 0.427880, 0.00001);
+        assert_approx_eq!(red.ct, -0.115704, 0.00001);
+        assert_approx_eq!(red.cp, 0.278729, 0.00001);
+
+        let bright = Ictcpa::from_linear_rgba(LinearRgba::new(1., 1., 1., 1.), 1000.);
+        assert_approx_eq!(bright.i, 0.751827, 0.00001);
+    }
+
+    #[test]
+    fn round_trip() {
+        for reference_white in [SDR_REFERENCE_WHITE, 80., 1000.] {
+            for color in TEST_COLORS.iter() {
+                let ictcp = Ictcpa::from_linear_rgba(color.linear_rgb, reference_white);
+                let back = ictcp.to_linear_rgba(reference_white);
+                assert_approx_eq!(back.red, color.linear_rgb.red, 0.001);
+                assert_approx_eq!(back.green, color.linear_rgb.green, 0.001);
+                assert_approx_eq!(back.blue, color.linear_rgb.blue, 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn difference() {
+        let gray = Ictcpa::from(SRgba::new(0.5, 0.5, 0.5, 1.));
+        let near = Ictcpa::from(SRgba::new(0.5, 0.5, 0.51, 1.));
+        assert_eq!(gray.difference(&gray), 0.);
+        let delta = gray.difference(&near);
+        assert!(delta > 0.5 && delta < 5., "{}", delta);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{
+    color_difference::EuclideanDistance,
+    math::{matrix_multiply, EuclideanModulo},
+    LinearRgba, Mix, SRgba, WithAlpha, Xyza,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// The luminance in nits (cd/m²) of SDR white in an HDR signal, from ITU-R BT.2408. This is
+/// the default reference white used to convert relative colors such as [`LinearRgba`] into
+/// the absolute color spaces [`Jzazbza`], [`Jzczhza`] and [`crate::Ictcpa`].
+pub const SDR_REFERENCE_WHITE: f32 = 203.0;
+
+/// Color in the Jzazbz color space, with alpha.
+///
+/// Jzazbz (Safdar et al. 2017) is a perceptually uniform color space for HDR and wide gamut
+/// imagery. Unlike [`crate::Oklaba`], it is defined on absolute luminance, so a color's
+/// coordinates depend on how bright it is in nits. The [`From`] conversions place white at
+/// [`SDR_REFERENCE_WHITE`]; use [`Jzazbza::from_linear_rgba`] to choose another reference
+/// white.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Jzazbza {
+    pub jz: f32,
+    pub az: f32,
+    pub bz: f32,
+    pub alpha: f32,
+}
+
+impl Jzazbza {
+    /// Construct a new [`Jzazbza`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `jz` - Lightness channel. [0.0, 1.0], where 1.0 is 10000 nits.
+    /// * `az` - Green-red channel. [~-0.5, ~0.5]
+    /// * `bz` - Blue-yellow channel. [~-0.5, ~0.5]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(jz: f32, az: f32, bz: f32, alpha: f32) -> Self {
+        Self { jz, az, bz, alpha }
+    }
+
+    /// Convert the Jzazbza color to a tuple of components (jz, az, bz, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.jz, self.az, self.bz, self.alpha)
+    }
+
+    /// Construct a new [`Jzazbza`] color from a tuple of components (jz, az, bz, alpha).
+    #[inline]
+    pub const fn from_components((jz, az, bz, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(jz, az, bz, alpha)
+    }
+
+    /// Convert a relative XYZ color, where a Y of 1.0 has a luminance of `reference_white`
+    /// nits.
+    pub fn from_xyza(color: Xyza, reference_white: f32) -> Self {
+        let [x, y, z] = [color.x, color.y, color.z].map(|c| c * reference_white);
+        let x_prime = B * x - (B - 1.) * z;
+        let y_prime = G * y - (G - 1.) * x;
+        let lms = matrix_multiply(&XYZ_TO_LMS, [x_prime, y_prime, z]).map(perceptual_quantize);
+        let [iz, az, bz] = matrix_multiply(&LMS_TO_IAB, lms);
+        let jz = (1. + D) * iz / (1. + D * iz) - D0;
+        Self::new(jz, az, bz, color.alpha)
+    }
+
+    /// Convert into a relative XYZ color, where a Y of 1.0 has a luminance of
+    /// `reference_white` nits.
+    pub fn to_xyza(self, reference_white: f32) -> Xyza {
+        let jz = self.jz + D0;
+        let iz = jz / (1. + D - D * jz);
+        let lms = matrix_multiply(&IAB_TO_LMS, [iz, self.az, self.bz]).map(perceptual_dequantize);
+        let [x_prime, y_prime, z] = matrix_multiply(&LMS_TO_XYZ, lms);
+        let x = (x_prime + (B - 1.) * z) / B;
+        let y = (y_prime + (G - 1.) * x) / G;
+        let [x, y, z] = [x, y, z].map(|c| c / reference_white);
+        Xyza::new(x, y, z, self.alpha)
+    }
+
+    /// Convert a linear RGB color, where white has a luminance of `reference_white` nits.
+    pub fn from_linear_rgba(color: LinearRgba, reference_white: f32) -> Self {
+        Self::from_xyza(color.into(), reference_white)
+    }
+
+    /// Convert into a linear RGB color, where white has a luminance of `reference_white`
+    /// nits. The result is not clamped.
+    pub fn to_linear_rgba(self, reference_white: f32) -> LinearRgba {
+        self.to_xyza(reference_white).into()
+    }
+
+    /// The color difference ΔEz between this and another color. This is the same as the
+    /// difference of [`Jzczhza`] colors.
+    pub fn difference(&self, other: &Self) -> f32 {
+        self.distance(other)
+    }
+}
+
+impl Default for Jzazbza {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Jzazbza {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            jz: self.jz * n_factor + other.jz * factor,
+            az: self.az * n_factor + other.az * factor,
+            bz: self.bz * n_factor + other.bz * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Jzazbza {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl EuclideanDistance for Jzazbza {
+    #[inline]
+    fn distance_squared(&self, other: &Self) -> f32 {
+        (self.jz - other.jz).powi(2) + (self.az - other.az).powi(2) + (self.bz - other.bz).powi(2)
+    }
+}
+
+impl From<LinearRgba> for Jzazbza {
+    fn from(value: LinearRgba) -> Self {
+        Jzazbza::from_linear_rgba(value, SDR_REFERENCE_WHITE)
+    }
+}
+
+impl From<Jzazbza> for LinearRgba {
+    fn from(value: Jzazbza) -> Self {
+        value.to_linear_rgba(SDR_REFERENCE_WHITE)
+    }
+}
+
+impl From<SRgba> for Jzazbza {
+    fn from(value: SRgba) -> Self {
+        Jzazbza::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Jzazbza> for SRgba {
+    fn from(value: Jzazbza) -> Self {
+        SRgba::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Jzczhza> for Jzazbza {
+    fn from(value: Jzczhza) -> Self {
+        let (sin, cos) = value.hue.to_radians().sin_cos();
+        Jzazbza::new(
+            value.jz,
+            value.chroma * cos,
+            value.chroma * sin,
+            value.alpha,
+        )
+    }
+}
+
+/// Color in JzCzhz, the polar form of [`Jzazbza`], with alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Jzczhza {
+    pub jz: f32,
+    pub chroma: f32,
+    pub hue: f32,
+    pub alpha: f32,
+}
+
+impl Jzczhza {
+    /// Construct a new [`Jzczhza`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `jz` - Lightness channel. [0.0, 1.0], where 1.0 is 10000 nits.
+    /// * `chroma` - Chroma channel. [0.0, ~0.5]
+    /// * `hue` - Hue channel. [0.0, 360.0]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(jz: f32, chroma: f32, hue: f32, alpha: f32) -> Self {
+        Self {
+            jz,
+            chroma,
+            hue,
+            alpha,
+        }
+    }
+
+    /// Convert the Jzczhza color to a tuple of components (jz, chroma, hue, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.jz, self.chroma, self.hue, self.alpha)
+    }
+
+    /// Construct a new [`Jzczhza`] color from a tuple of components (jz, chroma, hue,
+    /// alpha).
+    #[inline]
+    pub const fn from_components((jz, chroma, hue, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(jz, chroma, hue, alpha)
+    }
+
+    /// Convert a linear RGB color, where white has a luminance of `reference_white` nits.
+    pub fn from_linear_rgba(color: LinearRgba, reference_white: f32) -> Self {
+        Jzazbza::from_linear_rgba(color, reference_white).into()
+    }
+
+    /// Convert into a linear RGB color, where white has a luminance of `reference_white`
+    /// nits. The result is not clamped.
+    pub fn to_linear_rgba(self, reference_white: f32) -> LinearRgba {
+        Jzazbza::from(self).to_linear_rgba(reference_white)
+    }
+
+    /// The color difference ΔEz between this and another color, from the lightness, chroma
+    /// and hue differences.
+    pub fn difference(&self, other: &Self) -> f32 {
+        let delta_hue = (other.hue - self.hue).to_radians();
+        let delta_h = 2. * (self.chroma * other.chroma).sqrt() * (delta_hue / 2.).sin();
+        ((self.jz - other.jz).powi(2) + (self.chroma - other.chroma).powi(2) + delta_h.powi(2))
+            .sqrt()
+    }
+}
+
+impl Default for Jzczhza {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Jzczhza {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        let hue = self.hue.lerp_modulo(other.hue, factor, 360.);
+        Self {
+            jz: self.jz * n_factor + other.jz * factor,
+            chroma: self.chroma * n_factor + other.chroma * factor,
+            hue,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Jzczhza {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl From<Jzazbza> for Jzczhza {
+    fn from(value: Jzazbza) -> Self {
+        Jzczhza::new(
+            value.jz,
+            value.az.hypot(value.bz),
+            value.bz.atan2(value.az).to_degrees().rem_euclid(360.),
+            value.alpha,
+        )
+    }
+}
+
+impl From<LinearRgba> for Jzczhza {
+    fn from(value: LinearRgba) -> Self {
+        Jzazbza::from(value).into()
+    }
+}
+
+impl From<Jzczhza> for LinearRgba {
+    fn from(value: Jzczhza) -> Self {
+        Jzazbza::from(value).into()
+    }
+}
+
+impl From<SRgba> for Jzczhza {
+    fn from(value: SRgba) -> Self {
+        Jzazbza::from(value).into()
+    }
+}
+
+impl From<Jzczhza> for SRgba {
+    fn from(value: Jzczhza) -> Self {
+        Jzazbza::from(value).into()
+    }
+}
+
+const B: f32 = 1.15;
+const G: f32 = 0.66;
+const D: f32 = -0.56;
+const D0: f32 = 1.629_55e-11;
+
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_LMS: [[f32; 3]; 3] = [
+    [0.41478972, 0.579999, 0.014648],
+    [-0.20151, 1.120649, 0.0531008],
+    [-0.0166008, 0.2648, 0.6684799],
+];
+
+const LMS_TO_XYZ: [[f32; 3]; 3] = [
+    [1.924_226_4, -1.004_792_3, 0.037_651_405],
+    [0.350_316_76, 0.726_481_2, -0.065_384_42],
+    [-0.090_982_81, -0.312_728_3, 1.522_766_6],
+];
+
+const LMS_TO_IAB: [[f32; 3]; 3] = [
+    [0.5, 0.5, 0.],
+    [3.524, -4.066708, 0.542708],
+    [0.199076, 1.096799, -1.295875],
+];
+
+const IAB_TO_LMS: [[f32; 3]; 3] = [
+    [1., 0.138_605_04, 0.058_047_316],
+    [1., -0.138_605_04, -0.058_047_316],
+    [1., -0.096_019_24, -0.811_891_9],
+];
+
+// Jzazbz uses the PQ curve of SMPTE ST 2084 with a larger exponent.
+const C1: f32 = 3424. / 4096.;
+const C2: f32 = 2413. / 128.;
+const C3: f32 = 2392. / 128.;
+const N: f32 = 2610. / 16384.;
+const P: f32 = 1.7 * 2523. / 32.;
+
+/// Encode an absolute luminance in nits.
+fn perceptual_quantize(nits: f32) -> f32 {
+    let x = (nits.max(0.) / 10000.).powf(N);
+    ((C1 + C2 * x) / (1. + C3 * x)).powf(P)
+}
+
+/// Decode an absolute luminance in nits.
+fn perceptual_dequantize(value: f32) -> f32 {
+    let v = value.max(0.).powf(1. / P);
+    10000. * ((C1 - v) / (C3 * v - C2)).max(0.).powf(1. / N)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn reference_values() {
+        let white = Jzazbza::from(SRgba::WHITE);
+        assert_approx_eq!(white.jz, 0.222065, 0.00001);
+        assert_approx_eq!(white.az, -0.000153, 0.00001);
+        assert_approx_eq!(white.bz, -0.000094, 0.00001);
+
+        let red = Jzazbza::from(SRgba::RED);
+        assert_approx_eq!(red.jz, 0.134394, 0.00001);
+        assert_approx_eq!(red.az, 0.117889, 0.00001);
+        assert_approx_eq!(red.bz, 0.111883, 0.00001);
+
+        // The same color is lighter when it is brighter.
+        let bright = Jzazbza::from_linear_rgba(LinearRgba::new(1., 0., 0., 1.), 1000.);
+        assert!(bright.jz > red.jz);
+    }
+
+    #[test]
+    fn round_trip() {
+        for reference_white in [SDR_REFERENCE_WHITE, 80., 1000.] {
+            for color in TEST_COLORS.iter() {
+                let jzczhz = Jzczhza::from_linear_rgba(color.linear_rgb, reference_white);
+                let back = jzczhz.to_linear_rgba(reference_white);
+                assert_approx_eq!(back.red, color.linear_rgb.red, 0.001);
+                assert_approx_eq!(back.green, color.linear_rgb.green, 0.001);
+                assert_approx_eq!(back.blue, color.linear_rgb.blue, 0.001);
+                assert_approx_eq!(back.alpha, color.linear_rgb.alpha, 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn difference() {
+        let a = Jzazbza::from(SRgba::hex("#3a86ff").unwrap());
+        let b = Jzazbza::from(SRgba::hex("#ff006e").unwrap());
+        assert_eq!(a.difference(&a), 0.);
+        assert_approx_eq!(
+            a.difference(&b),
+            Jzczhza::from(a).difference(&Jzczhza::from(b)),
+            0.00001
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     to_css_string::{RoundToDecimalPlaces, ToCssString},
-    LinearRgba, LuminanceOps, Mix, SRgba, WithAlpha,
+    LinearRgba, LuminanceOps, Mix, Oklaba, SRgba, WithAlpha,
 };
 use bevy::render::color::{Color, LchRepresentation};
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
@@ -144,6 +144,12 @@ impl From<Lcha> for LinearRgba {
     }
 }
 
+impl From<Oklaba> for Lcha {
+    fn from(value: Oklaba) -> Self {
+        Lcha::from(LinearRgba::from(value))
+    }
+}
+
 impl From<Lcha> for Color {
     fn from(value: Lcha) -> Self {
         Color::Lcha {
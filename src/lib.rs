@@ -27,25 +27,85 @@
 //! println!("Hsla: {:?}", hsla);
 //! ```
 
+mod blend;
+mod cam16;
+mod cmyka;
 pub mod color_difference;
+mod color_mean;
 mod color_ops;
 mod color_range;
 mod color_representation;
+mod colormap;
+mod css;
+mod css_gradient;
+mod easing;
+mod gamut;
+mod gradient;
+mod grading;
+mod harmony;
+mod hcta;
 mod hsla;
+mod hsluva;
+mod icc;
+mod ictcp;
+mod jzazbz;
 mod lcha;
 mod linear_rgba;
+mod lut;
+mod luva;
+mod material;
+mod math;
+mod okhsla;
+mod okhsva;
 mod oklaba;
+mod photometry;
+mod spline;
 mod srgba;
 mod test_colors;
 mod testing;
 mod to_css_string;
+mod tonal_scale;
+mod tonemapping;
+mod transfer;
+mod xyza;
+mod ycbcr;
+mod ycocg;
 
+pub use blend::*;
+pub use cam16::*;
+pub use cmyka::*;
+pub use color_mean::*;
 pub use color_ops::*;
 pub use color_range::*;
 pub use color_representation::*;
+pub use colormap::*;
+pub use css::CssParseError;
+pub use css_gradient::*;
+pub use easing::*;
+pub use gradient::*;
+pub use grading::*;
+pub use harmony::*;
+pub use hcta::*;
 pub use hsla::*;
+pub use hsluva::*;
+pub use icc::*;
+pub use ictcp::*;
+pub use jzazbz::*;
 pub use lcha::*;
 pub use linear_rgba::*;
+pub use lut::*;
+pub use luva::*;
+pub use material::*;
+pub use okhsla::*;
+pub use okhsva::*;
 pub use oklaba::*;
+pub use photometry::*;
+pub use spline::*;
 pub use srgba::*;
 pub use to_css_string::*;
+pub use tonal_scale::*;
+pub use tonemapping::*;
+pub use transfer::*;
+pub use xyza::*;
+pub use ycbcr::*;
+pub use ycocg::*;
@@ -1,6 +1,6 @@
 use crate::{
-    color_difference::EuclideanDistance, oklaba::Oklaba, to_css_string::ToCssString, Hsla,
-    LuminanceOps, Mix, SRgba, WithAlpha,
+    color_difference::EuclideanDistance, color_ops::impl_componentwise_ops, oklaba::Oklaba,
+    to_css_string::ToCssString, Hsla, LuminanceOps, Mix, SRgba, WithAlpha,
 };
 use bevy::{
     math::Vec4,
@@ -132,6 +132,8 @@ impl Mix for LinearRgba {
     }
 }
 
+impl_componentwise_ops!(LinearRgba, [red, green, blue, alpha]);
+
 impl WithAlpha for LinearRgba {
     #[inline]
     fn with_alpha(&self, alpha: f32) -> Self {
@@ -270,6 +272,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn arithmetic() {
+        let a = LinearRgba::new(0.5, 0.25, 1.0, 1.0);
+        let b = LinearRgba::new(0.25, 0.5, 0.5, 0.5);
+        assert_eq!(a + b, LinearRgba::new(0.75, 0.75, 1.5, 1.5));
+        assert_eq!(a - b, LinearRgba::new(0.25, -0.25, 0.5, 0.5));
+        assert_eq!(a * b, LinearRgba::new(0.125, 0.125, 0.5, 0.5));
+        assert_eq!(a * 2.0, LinearRgba::new(1.0, 0.5, 2.0, 2.0));
+        assert_eq!(2.0 * a, a * 2.0);
+        assert_eq!(a / 2.0, LinearRgba::new(0.25, 0.125, 0.5, 0.5));
+        assert_eq!(-a, LinearRgba::new(-0.5, -0.25, -1.0, -1.0));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        c *= 4.0;
+        c /= 2.0;
+        assert_eq!(c, b * 2.0);
+        c *= a;
+        assert_eq!(c, b * a * 2.0);
+
+        assert_eq!([a, b].iter().sum::<LinearRgba>(), a + b);
+        assert_eq!(
+            std::iter::empty::<LinearRgba>().sum::<LinearRgba>(),
+            LinearRgba::new(0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
     #[test]
     fn darker_lighter() {
         // Darker and lighter should be commutative.
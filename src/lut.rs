@@ -0,0 +1,1083 @@
+use crate::{AnyColorRange, LinearRgba, SRgba};
+use bevy::render::{
+    color::SrgbColorSpace,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::Image,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use half::f16;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+
+/// The texel format of a baked color lookup table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LutFormat {
+    /// 8-bit sRGB-encoded color with linear alpha, [`TextureFormat::Rgba8UnormSrgb`].
+    #[default]
+    Srgba8,
+    /// 16-bit float linear color, [`TextureFormat::Rgba16Float`].
+    Rgba16Float,
+    /// 32-bit float linear color, [`TextureFormat::Rgba32Float`].
+    Rgba32Float,
+}
+
+impl LutFormat {
+    /// The texture format which matches this texel format.
+    pub fn texture_format(self) -> TextureFormat {
+        match self {
+            LutFormat::Srgba8 => TextureFormat::Rgba8UnormSrgb,
+            LutFormat::Rgba16Float => TextureFormat::Rgba16Float,
+            LutFormat::Rgba32Float => TextureFormat::Rgba32Float,
+        }
+    }
+}
+
+/// Baking of a color range into a one-dimensional lookup table, for use by shaders which
+/// sample a color ramp.
+///
+/// Texel `i` of an `n`-texel table holds the color at factor `(i + 0.5) / n`, which is the
+/// texel center, so a shader can use the interpolation factor directly as the texture
+/// coordinate. This is implemented for every [`AnyColorRange`], including trait objects.
+pub trait BakeLut {
+    /// Bake into linear floating point texels.
+    fn bake_f32(&self, texels: usize) -> Vec<[f32; 4]>;
+
+    /// Bake into linear half-precision texels.
+    fn bake_f16(&self, texels: usize) -> Vec<[f16; 4]> {
+        self.bake_f32(texels)
+            .into_iter()
+            .map(|texel| texel.map(f16::from_f32))
+            .collect()
+    }
+
+    /// Bake into 8-bit texels whose color channels are sRGB-encoded and whose alpha is
+    /// linear. If `dither` is true, noise of up to half a quantization step is added before
+    /// rounding, which trades banding in smooth ramps for a fine, even grain.
+    fn bake_srgba8(&self, texels: usize, dither: bool) -> Vec<[u8; 4]> {
+        self.bake_f32(texels)
+            .into_iter()
+            .enumerate()
+            .map(|(i, [red, green, blue, alpha])| {
+                let encoded = [
+                    red.linear_to_nonlinear_srgb(),
+                    green.linear_to_nonlinear_srgb(),
+                    blue.linear_to_nonlinear_srgb(),
+                    alpha,
+                ];
+                let mut texel = [0; 4];
+                for (channel, value) in encoded.into_iter().enumerate() {
+                    let noise = if dither {
+                        dither_noise(i as u32, channel as u32)
+                    } else {
+                        0.
+                    };
+                    texel[channel] = (value * 255. + noise).round().clamp(0., 255.) as u8;
+                }
+                texel
+            })
+            .collect()
+    }
+
+    /// Bake into a `texels` x 1 texture with [`TextureDimension::D1`], in the given format.
+    /// Dithering only applies to [`LutFormat::Srgba8`].
+    fn bake_image(&self, texels: usize, format: LutFormat, dither: bool) -> Image {
+        let data: Vec<u8> = match format {
+            LutFormat::Srgba8 => self.bake_srgba8(texels, dither).concat(),
+            LutFormat::Rgba16Float => self
+                .bake_f16(texels)
+                .into_iter()
+                .flatten()
+                .flat_map(f16::to_le_bytes)
+                .collect(),
+            LutFormat::Rgba32Float => self
+                .bake_f32(texels)
+                .into_iter()
+                .flatten()
+                .flat_map(f32::to_le_bytes)
+                .collect(),
+        };
+        Image::new(
+            Extent3d {
+                width: texels as u32,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D1,
+            data,
+            format.texture_format(),
+        )
+    }
+}
+
+impl<T: AnyColorRange + ?Sized> BakeLut for T {
+    fn bake_f32(&self, texels: usize) -> Vec<[f32; 4]> {
+        (0..texels)
+            .map(|i| {
+                let LinearRgba {
+                    red,
+                    green,
+                    blue,
+                    alpha,
+                } = self.at_linear((i as f32 + 0.5) / texels as f32);
+                [red, green, blue, alpha]
+            })
+            .collect()
+    }
+}
+
+/// Deterministic noise in the range [-0.5, 0.5) for a given texel and channel, so that
+/// baking the same range twice gives identical results.
+fn dither_noise(texel: u32, channel: u32) -> f32 {
+    // A PCG-style integer hash.
+    let mut state = texel
+        .wrapping_mul(4)
+        .wrapping_add(channel)
+        .wrapping_mul(747796405)
+        .wrapping_add(2891336453);
+    state = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    state = (state >> 22) ^ state;
+    (state >> 8) as f32 / (1 << 24) as f32 - 0.5
+}
+
+/// How a [`Lut3d`] interpolates between its texels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Hash, Serialize, Deserialize)]
+pub enum LutInterpolation {
+    /// Interpolation between the 8 texels of the enclosing cell, as GPUs filter 3D textures.
+    #[default]
+    Trilinear,
+    /// Interpolation between the 4 texels of the enclosing tetrahedron of the cell. This is
+    /// what grading software uses, and it keeps the neutral axis of the table free of color
+    /// shifts from off-axis texels.
+    Tetrahedral,
+}
+
+/// An error from parsing a `.cube` lookup table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CubeError {
+    /// A line, counted from 1, is not a keyword, a comment or three numbers.
+    InvalidLine(usize),
+    /// Neither `LUT_1D_SIZE` nor `LUT_3D_SIZE` is given.
+    MissingSize,
+    /// The size is out of range: 2 to 65536 for 1D tables, or 2 to 256 for 3D tables.
+    InvalidSize(usize),
+    /// The number of table entries does not match the size.
+    EntryCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for CubeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CubeError::InvalidLine(line) => write!(f, "Invalid .cube line {line}"),
+            CubeError::MissingSize => write!(f, "The .cube file has no LUT size"),
+            CubeError::InvalidSize(size) => write!(f, "Invalid .cube LUT size: {size}"),
+            CubeError::EntryCount { expected, found } => write!(
+                f,
+                "The .cube file has {found} entries, but its size requires {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CubeError {}
+
+/// A lookup table read from an Adobe or Resolve `.cube` file, which holds either a 1D or a
+/// 3D table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CubeLut {
+    Lut1d(Lut1d),
+    Lut3d(Lut3d),
+}
+
+impl CubeLut {
+    /// Parse a `.cube` file. `TITLE`, `DOMAIN_MIN` and `DOMAIN_MAX` are read, as are
+    /// Resolve's `LUT_1D_INPUT_RANGE` and `LUT_3D_INPUT_RANGE`. Files with both a 1D shaper
+    /// and a 3D table are not supported.
+    pub fn parse(text: &str) -> Result<Self, CubeError> {
+        let mut title = None;
+        let mut size_1d = None;
+        let mut size_3d = None;
+        let mut domain_min = [0.; 3];
+        let mut domain_max = [1.; 3];
+        let mut entries = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let invalid = || CubeError::InvalidLine(index + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+            let numbers = || -> Result<Vec<f32>, CubeError> {
+                rest.split_whitespace()
+                    .map(|part| part.parse().map_err(|_| invalid()))
+                    .collect()
+            };
+            let triple = || match numbers()?.as_slice() {
+                [red, green, blue] => Ok([*red, *green, *blue]),
+                _ => Err(invalid()),
+            };
+            match keyword {
+                "TITLE" => {
+                    let quoted = rest.strip_prefix('"').and_then(|r| r.strip_suffix('"'));
+                    title = Some(quoted.unwrap_or(rest).to_string());
+                }
+                "LUT_1D_SIZE" | "LUT_3D_SIZE" => {
+                    let size: usize = rest.parse().map_err(|_| invalid())?;
+                    if size_1d.is_some() || size_3d.is_some() {
+                        return Err(invalid());
+                    }
+                    if keyword == "LUT_1D_SIZE" {
+                        size_1d = Some(size);
+                    } else {
+                        size_3d = Some(size);
+                    }
+                }
+                "DOMAIN_MIN" => domain_min = triple()?,
+                "DOMAIN_MAX" => domain_max = triple()?,
+                "LUT_1D_INPUT_RANGE" | "LUT_3D_INPUT_RANGE" => match numbers()?.as_slice() {
+                    [min, max] => {
+                        domain_min = [*min; 3];
+                        domain_max = [*max; 3];
+                    }
+                    _ => return Err(invalid()),
+                },
+                _ => {
+                    let value: f32 = keyword.parse().map_err(|_| invalid())?;
+                    match numbers()?.as_slice() {
+                        [green, blue] => entries.push([value, *green, *blue]),
+                        _ => return Err(invalid()),
+                    }
+                }
+            }
+        }
+        let (expected, max_size) = match (size_1d, size_3d) {
+            (Some(size), _) => (size, 65536),
+            (_, Some(size)) => (size.saturating_pow(3), 256),
+            (None, None) => return Err(CubeError::MissingSize),
+        };
+        let size = size_1d.or(size_3d).unwrap_or_default();
+        if !(2..=max_size).contains(&size) {
+            return Err(CubeError::InvalidSize(size));
+        }
+        if entries.len() != expected {
+            return Err(CubeError::EntryCount {
+                expected,
+                found: entries.len(),
+            });
+        }
+        Ok(match size_1d {
+            Some(_) => CubeLut::Lut1d(Lut1d {
+                title,
+                domain_min,
+                domain_max,
+                entries,
+            }),
+            None => CubeLut::Lut3d(Lut3d {
+                title,
+                size,
+                domain_min,
+                domain_max,
+                texels: entries,
+            }),
+        })
+    }
+
+    /// Write the table as a `.cube` file.
+    pub fn to_cube(&self) -> String {
+        match self {
+            CubeLut::Lut1d(lut) => lut.to_cube(),
+            CubeLut::Lut3d(lut) => lut.to_cube(),
+        }
+    }
+}
+
+/// Write the header and entries of a `.cube` file.
+fn write_cube(
+    title: Option<&str>,
+    size_keyword: &str,
+    size: usize,
+    domain: ([f32; 3], [f32; 3]),
+    entries: &[[f32; 3]],
+) -> String {
+    let mut cube = String::new();
+    if let Some(title) = title {
+        writeln!(cube, "TITLE \"{title}\"").unwrap();
+    }
+    writeln!(cube, "{size_keyword} {size}").unwrap();
+    if domain != ([0.; 3], [1.; 3]) {
+        let ([r0, g0, b0], [r1, g1, b1]) = domain;
+        writeln!(cube, "DOMAIN_MIN {r0} {g0} {b0}").unwrap();
+        writeln!(cube, "DOMAIN_MAX {r1} {g1} {b1}").unwrap();
+    }
+    for [red, green, blue] in entries {
+        writeln!(cube, "{red:.6} {green:.6} {blue:.6}").unwrap();
+    }
+    cube
+}
+
+/// The position of a value within a domain, from 0.0 at `min` to 1.0 at `max`, clamped.
+#[inline]
+fn normalize(value: f32, min: f32, max: f32) -> f32 {
+    ((value - min) / (max - min)).clamp(0., 1.)
+}
+
+/// A one-dimensional lookup table, which maps each channel separately.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut1d {
+    title: Option<String>,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    entries: Vec<[f32; 3]>,
+}
+
+impl Lut1d {
+    /// Construct from evenly spaced entries over the domain [0.0, 1.0].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than 2 entries.
+    pub fn new(entries: Vec<[f32; 3]>) -> Self {
+        assert!(entries.len() >= 2, "lookup table is too small");
+        Self {
+            title: None,
+            domain_min: [0.; 3],
+            domain_max: [1.; 3],
+            entries,
+        }
+    }
+
+    /// Return this table with a title.
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Return this table with the input values which map to the first and last entries.
+    pub fn with_domain(self, domain_min: [f32; 3], domain_max: [f32; 3]) -> Self {
+        Self {
+            domain_min,
+            domain_max,
+            ..self
+        }
+    }
+
+    /// The title, if any.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The input values which map to the first entry.
+    pub fn domain_min(&self) -> [f32; 3] {
+        self.domain_min
+    }
+
+    /// The input values which map to the last entry.
+    pub fn domain_max(&self) -> [f32; 3] {
+        self.domain_max
+    }
+
+    /// The entries of the table.
+    pub fn entries(&self) -> &[[f32; 3]] {
+        &self.entries
+    }
+
+    /// Map each channel through the table, with linear interpolation. Input outside the
+    /// domain is clamped to it.
+    pub fn sample(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let last = self.entries.len() - 1;
+        [0, 1, 2].map(|channel| {
+            let position = normalize(
+                rgb[channel],
+                self.domain_min[channel],
+                self.domain_max[channel],
+            ) * last as f32;
+            let i = (position.floor() as usize).min(last - 1);
+            let t = position - i as f32;
+            let (a, b) = (self.entries[i][channel], self.entries[i + 1][channel]);
+            a + (b - a) * t
+        })
+    }
+
+    /// Map the channels of a linear color through the table. Alpha is passed through.
+    pub fn sample_linear_rgba(&self, color: LinearRgba) -> LinearRgba {
+        let [red, green, blue] = self.sample([color.red, color.green, color.blue]);
+        LinearRgba::new(red, green, blue, color.alpha)
+    }
+
+    /// Map the gamma-encoded channels of an sRGB color through the table. Alpha is passed
+    /// through.
+    pub fn sample_srgba(&self, color: SRgba) -> SRgba {
+        let [red, green, blue] = self.sample([color.red, color.green, color.blue]);
+        SRgba::new(red, green, blue, color.alpha)
+    }
+
+    /// Write the table as a `.cube` file.
+    pub fn to_cube(&self) -> String {
+        write_cube(
+            self.title(),
+            "LUT_1D_SIZE",
+            self.entries.len(),
+            (self.domain_min, self.domain_max),
+            &self.entries,
+        )
+    }
+}
+
+/// A three-dimensional color lookup table, as delivered by colorists in `.cube` files.
+///
+/// The table holds `size³` RGB texels with red varying fastest and blue slowest, evenly
+/// spaced over a domain which defaults to [0.0, 1.0]. It is applied to the channel values as
+/// they are, so a table made for gamma-encoded input should be sampled with
+/// [`Lut3d::sample_srgba`], and one made for linear input with [`Lut3d::sample_linear_rgba`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    title: Option<String>,
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    texels: Vec<[f32; 3]>,
+}
+
+impl Lut3d {
+    /// Construct from a `size` x `size` x `size` table of RGB texels, with red varying
+    /// fastest and blue slowest, over the domain [0.0, 1.0].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2, or if `texels` does not hold `size³` texels.
+    pub fn new(size: usize, texels: Vec<[f32; 3]>) -> Self {
+        assert!(size >= 2, "lookup table is too small");
+        assert_eq!(
+            texels.len(),
+            size * size * size,
+            "texel count does not match the table size"
+        );
+        Self {
+            title: None,
+            size,
+            domain_min: [0.; 3],
+            domain_max: [1.; 3],
+            texels,
+        }
+    }
+
+    /// The table which maps every color to itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2.
+    pub fn identity(size: usize) -> Self {
+        Self::bake(size, |color| color)
+    }
+
+    /// Bake a color function into a table over the domain [0.0, 1.0], such as a
+    /// [`crate::ColorGrade`] or a [`crate::Tonemap`] operator. The function is called with
+    /// the texel coordinates as a [`LinearRgba`] with an alpha of 1.0.
+    ///
+    /// ```
+    /// # use bevy_color::*;
+    /// let grade = ColorGrade::new().then(Saturation::new(0.5));
+    /// let lut = Lut3d::bake(33, |color| grade.grade(color));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2.
+    pub fn bake(size: usize, function: impl Fn(LinearRgba) -> LinearRgba) -> Self {
+        Self::bake_in_domain(size, [0.; 3], [1.; 3], function)
+    }
+
+    /// Bake a color function into a table over the given domain. Input values are spaced
+    /// linearly, so for HDR input a wide domain leaves few texels for the darker values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2.
+    pub fn bake_in_domain(
+        size: usize,
+        domain_min: [f32; 3],
+        domain_max: [f32; 3],
+        function: impl Fn(LinearRgba) -> LinearRgba,
+    ) -> Self {
+        assert!(size >= 2, "lookup table is too small");
+        let last = (size - 1) as f32;
+        let coordinate = |i: usize, channel: usize| {
+            let t = i as f32 / last;
+            domain_min[channel] + (domain_max[channel] - domain_min[channel]) * t
+        };
+        let mut texels = Vec::with_capacity(size * size * size);
+        for blue in 0..size {
+            for green in 0..size {
+                for red in 0..size {
+                    let input = LinearRgba::new(
+                        coordinate(red, 0),
+                        coordinate(green, 1),
+                        coordinate(blue, 2),
+                        1.,
+                    );
+                    let output = function(input);
+                    texels.push([output.red, output.green, output.blue]);
+                }
+            }
+        }
+        Self::new(size, texels).with_domain(domain_min, domain_max)
+    }
+
+    /// Return this table with a title.
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Return this table with the input values which map to the first and last texels.
+    pub fn with_domain(self, domain_min: [f32; 3], domain_max: [f32; 3]) -> Self {
+        Self {
+            domain_min,
+            domain_max,
+            ..self
+        }
+    }
+
+    /// The title, if any.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The number of texels along each side of the table.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The input values which map to the first texel.
+    pub fn domain_min(&self) -> [f32; 3] {
+        self.domain_min
+    }
+
+    /// The input values which map to the last texel.
+    pub fn domain_max(&self) -> [f32; 3] {
+        self.domain_max
+    }
+
+    /// The texels of the table, with red varying fastest and blue slowest.
+    pub fn texels(&self) -> &[[f32; 3]] {
+        &self.texels
+    }
+
+    #[inline]
+    fn texel(&self, red: usize, green: usize, blue: usize) -> [f32; 3] {
+        self.texels[(blue * self.size + green) * self.size + red]
+    }
+
+    /// Look up RGB values in the table. Input outside the domain is clamped to it.
+    pub fn sample(&self, rgb: [f32; 3], interpolation: LutInterpolation) -> [f32; 3] {
+        let last = (self.size - 1) as f32;
+        let [(r, fr), (g, fg), (b, fb)] = [0, 1, 2].map(|channel| {
+            let position = normalize(
+                rgb[channel],
+                self.domain_min[channel],
+                self.domain_max[channel],
+            ) * last;
+            let i = (position.floor() as usize).min(self.size - 2);
+            (i, position - i as f32)
+        });
+        let corner = |dr: usize, dg: usize, db: usize| self.texel(r + dr, g + dg, b + db);
+        match interpolation {
+            LutInterpolation::Trilinear => {
+                let lerp =
+                    |a: [f32; 3], b: [f32; 3], t: f32| [0, 1, 2].map(|i| a[i] + (b[i] - a[i]) * t);
+                let plane = |db: usize| {
+                    lerp(
+                        lerp(corner(0, 0, db), corner(1, 0, db), fr),
+                        lerp(corner(0, 1, db), corner(1, 1, db), fr),
+                        fg,
+                    )
+                };
+                lerp(plane(0), plane(1), fb)
+            }
+            LutInterpolation::Tetrahedral => {
+                // Pick the tetrahedron by the order of the fractions, then weight its corners.
+                let (second, third, weights) = if fr > fg {
+                    if fg > fb {
+                        (
+                            corner(1, 0, 0),
+                            corner(1, 1, 0),
+                            [1. - fr, fr - fg, fg - fb, fb],
+                        )
+                    } else if fr > fb {
+                        (
+                            corner(1, 0, 0),
+                            corner(1, 0, 1),
+                            [1. - fr, fr - fb, fb - fg, fg],
+                        )
+                    } else {
+                        (
+                            corner(0, 0, 1),
+                            corner(1, 0, 1),
+                            [1. - fb, fb - fr, fr - fg, fg],
+                        )
+                    }
+                } else if fb > fg {
+                    (
+                        corner(0, 0, 1),
+                        corner(0, 1, 1),
+                        [1. - fb, fb - fg, fg - fr, fr],
+                    )
+                } else if fb > fr {
+                    (
+                        corner(0, 1, 0),
+                        corner(0, 1, 1),
+                        [1. - fg, fg - fb, fb - fr, fr],
+                    )
+                } else {
+                    (
+                        corner(0, 1, 0),
+                        corner(1, 1, 0),
+                        [1. - fg, fg - fr, fr - fb, fb],
+                    )
+                };
+                let first = corner(0, 0, 0);
+                let fourth = corner(1, 1, 1);
+                [0, 1, 2].map(|i| {
+                    first[i] * weights[0]
+                        + second[i] * weights[1]
+                        + third[i] * weights[2]
+                        + fourth[i] * weights[3]
+                })
+            }
+        }
+    }
+
+    /// Look up the channels of a linear color in the table. Alpha is passed through.
+    pub fn sample_linear_rgba(
+        &self,
+        color: LinearRgba,
+        interpolation: LutInterpolation,
+    ) -> LinearRgba {
+        let [red, green, blue] = self.sample([color.red, color.green, color.blue], interpolation);
+        LinearRgba::new(red, green, blue, color.alpha)
+    }
+
+    /// Look up the gamma-encoded channels of an sRGB color in the table. Alpha is passed
+    /// through.
+    pub fn sample_srgba(&self, color: SRgba, interpolation: LutInterpolation) -> SRgba {
+        let [red, green, blue] = self.sample([color.red, color.green, color.blue], interpolation);
+        SRgba::new(red, green, blue, color.alpha)
+    }
+
+    /// Write the table as a `.cube` file. The domain is only written if it is not the
+    /// default [0.0, 1.0].
+    pub fn to_cube(&self) -> String {
+        write_cube(
+            self.title(),
+            "LUT_3D_SIZE",
+            self.size,
+            (self.domain_min, self.domain_max),
+            &self.texels,
+        )
+    }
+
+    /// Read the table from a cubic 3D texture, in the [`TextureFormat::Rgba8UnormSrgb`],
+    /// [`TextureFormat::Rgb9e5Ufloat`], [`TextureFormat::Rgba16Float`] or
+    /// [`TextureFormat::Rgba32Float`] format. Returns `None` if the image is not a cube of at
+    /// least 2 texels per side in one of these formats. The domain is [0.0, 1.0].
+    pub fn from_image(image: &Image) -> Option<Self> {
+        let descriptor = &image.texture_descriptor;
+        let size = descriptor.size.width as usize;
+        if descriptor.dimension != TextureDimension::D3
+            || size < 2
+            || descriptor.size.height as usize != size
+            || descriptor.size.depth_or_array_layers as usize != size
+        {
+            return None;
+        }
+        let count = size * size * size;
+        let texels: Vec<[f32; 3]> = match descriptor.format {
+            TextureFormat::Rgba8UnormSrgb => image
+                .data
+                .chunks_exact(4)
+                .take(count)
+                .map(|bytes| [0, 1, 2].map(|i| (bytes[i] as f32 / 255.).nonlinear_to_linear_srgb()))
+                .collect(),
+            TextureFormat::Rgb9e5Ufloat => image
+                .data
+                .chunks_exact(4)
+                .take(count)
+                .map(|bytes| {
+                    let bits = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    let scale = 2f32.powi((bits >> 27) as i32 - 15 - 9);
+                    [0, 9, 18].map(|shift| ((bits >> shift) & 0x1ff) as f32 * scale)
+                })
+                .collect(),
+            TextureFormat::Rgba16Float => image
+                .data
+                .chunks_exact(8)
+                .take(count)
+                .map(|bytes| {
+                    [0, 2, 4].map(|i| f16::from_le_bytes([bytes[i], bytes[i + 1]]).to_f32())
+                })
+                .collect(),
+            TextureFormat::Rgba32Float => image
+                .data
+                .chunks_exact(16)
+                .take(count)
+                .map(|bytes| {
+                    [0, 4, 8].map(|i| {
+                        f32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]])
+                    })
+                })
+                .collect(),
+            _ => return None,
+        };
+        (texels.len() == count).then(|| Self::new(size, texels))
+    }
+
+    /// Export the table as a `size` x `size` x `size` texture with [`TextureDimension::D3`],
+    /// in the given format, with an alpha of 1.0. [`LutFormat::Srgba8`] clamps the texels to
+    /// [0.0, 1.0]. To match [`Lut3d::sample`] with hardware filtering, a shader should map
+    /// an input `c` to the texture coordinate `t * (size - 1) / size + 0.5 / size`, where
+    /// `t` is the position of `c` within the domain.
+    pub fn to_image(&self, format: LutFormat) -> Image {
+        let data: Vec<u8> = match format {
+            LutFormat::Srgba8 => self
+                .texels
+                .iter()
+                .flat_map(|texel| {
+                    let [red, green, blue] = texel.map(|c| {
+                        (c.linear_to_nonlinear_srgb() * 255.)
+                            .round()
+                            .clamp(0., 255.) as u8
+                    });
+                    [red, green, blue, 255]
+                })
+                .collect(),
+            LutFormat::Rgba16Float => self
+                .texels
+                .iter()
+                .flat_map(|&[red, green, blue]| [red, green, blue, 1.])
+                .flat_map(|c| f16::from_f32(c).to_le_bytes())
+                .collect(),
+            LutFormat::Rgba32Float => self
+                .texels
+                .iter()
+                .flat_map(|&[red, green, blue]| [red, green, blue, 1.])
+                .flat_map(f32::to_le_bytes)
+                .collect(),
+        };
+        let size = self.size as u32;
+        Image::new(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: size,
+            },
+            TextureDimension::D3,
+            data,
+            format.texture_format(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::assert_approx_eq, ColorGrade, ColorRange, Grade, SRgba, Saturation, Tonemap,
+        Tonemapper,
+    };
+
+    #[test]
+    fn texel_centers() {
+        let range = ColorRange::new(
+            LinearRgba::new(0.0, 0.0, 0.0, 1.0),
+            LinearRgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+        let texels = range.bake_f32(4);
+        assert_eq!(texels.len(), 4);
+        for (i, texel) in texels.iter().enumerate() {
+            let expected = (i as f32 + 0.5) / 4.;
+            assert_approx_eq!(texel[0], expected, 0.0001);
+            assert_approx_eq!(texel[3], 1.0, 0.0001);
+        }
+
+        let half = range.bake_f16(4);
+        assert_approx_eq!(half[1][1].to_f32(), 0.375, 0.001);
+    }
+
+    #[test]
+    fn srgb_encoding() {
+        let range: Box<dyn AnyColorRange> =
+            Box::new(ColorRange::new(SRgba::RED, SRgba::new(0.0, 0.0, 1.0, 0.0)));
+        let texels = range.bake_srgba8(2, false);
+        // The range is interpolated in sRGB, and the texels should hold the same values.
+        assert_eq!(texels[0], [191, 0, 64, 191]);
+        assert_eq!(texels[1], [64, 0, 191, 64]);
+    }
+
+    #[test]
+    fn dithering() {
+        let value = 100.3 / 255.;
+        let color = SRgba::new(value, value, value, value);
+        let range = ColorRange::new(color, color);
+
+        let plain = range.bake_srgba8(1000, false);
+        assert!(plain.iter().all(|texel| texel[0] == 100 && texel[3] == 100));
+
+        let dithered = range.bake_srgba8(1000, true);
+        assert!(dithered.iter().any(|texel| texel[0] == 101));
+        assert!(dithered
+            .iter()
+            .all(|texel| texel[0] == 100 || texel[0] == 101));
+        for channel in 0..4 {
+            let mean = dithered
+                .iter()
+                .map(|texel| texel[channel] as f32)
+                .sum::<f32>()
+                / 1000.;
+            assert_approx_eq!(mean, 100.3, 0.05);
+        }
+        assert_eq!(dithered, range.bake_srgba8(1000, true));
+    }
+
+    #[test]
+    fn image() {
+        let range = ColorRange::new(SRgba::BLACK, SRgba::WHITE);
+        for (format, texel_size) in [
+            (LutFormat::Srgba8, 4),
+            (LutFormat::Rgba16Float, 8),
+            (LutFormat::Rgba32Float, 16),
+        ] {
+            let image = range.bake_image(16, format, false);
+            assert_eq!(image.texture_descriptor.format, format.texture_format());
+            assert_eq!(image.texture_descriptor.dimension, TextureDimension::D1);
+            assert_eq!(image.texture_descriptor.size.width, 16);
+            assert_eq!(image.data.len(), 16 * texel_size);
+        }
+
+        let image = range.bake_image(2, LutFormat::Rgba32Float, false);
+        let first = f32::from_le_bytes(image.data[0..4].try_into().unwrap());
+        assert_approx_eq!(
+            first,
+            SRgba::new(0.25, 0.25, 0.25, 1.).to_linear().red,
+            0.0001
+        );
+    }
+
+    const CUBE: &str = "# Created by hand
+TITLE \"Swap red and blue\"
+LUT_3D_SIZE 2
+
+DOMAIN_MIN 0 0 0
+DOMAIN_MAX 1 1 1
+0 0 0
+0 0 1
+0 1 0
+0 1 1
+1 0 0
+1 0 1
+1 1 0
+1 1 1
+";
+
+    #[test]
+    fn parse_cube() {
+        let CubeLut::Lut3d(lut) = CubeLut::parse(CUBE).unwrap() else {
+            panic!("expected a 3D table");
+        };
+        assert_eq!(lut.title(), Some("Swap red and blue"));
+        assert_eq!(lut.size(), 2);
+        assert_eq!(lut.texels()[1], [0., 0., 1.]);
+        for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+            let swapped = lut.sample_srgba(SRgba::new(0.8, 0.5, 0.1, 0.3), interpolation);
+            assert_approx_eq!(swapped.red, 0.1, 0.0001);
+            assert_approx_eq!(swapped.green, 0.5, 0.0001);
+            assert_approx_eq!(swapped.blue, 0.8, 0.0001);
+            assert_eq!(swapped.alpha, 0.3);
+        }
+
+        let one_d = "LUT_1D_SIZE 3\nLUT_1D_INPUT_RANGE 0 2\n0 0 0\n0.25 0.5 1\n1 1 1\n";
+        let CubeLut::Lut1d(lut) = CubeLut::parse(one_d).unwrap() else {
+            panic!("expected a 1D table");
+        };
+        assert_eq!(lut.domain_max(), [2.; 3]);
+        let mapped = lut.sample_linear_rgba(LinearRgba::new(0.5, 1.0, 4.0, 1.0));
+        assert_approx_eq!(mapped.red, 0.125, 0.0001);
+        assert_approx_eq!(mapped.green, 0.5, 0.0001);
+        assert_approx_eq!(mapped.blue, 1.0, 0.0001);
+    }
+
+    #[test]
+    fn cube_errors() {
+        assert_eq!(
+            CubeLut::parse("0 0 0\n1 1 1\n"),
+            Err(CubeError::MissingSize)
+        );
+        assert_eq!(
+            CubeLut::parse("LUT_3D_SIZE 2\n0 0 0\n"),
+            Err(CubeError::EntryCount {
+                expected: 8,
+                found: 1
+            })
+        );
+        assert_eq!(
+            CubeLut::parse("LUT_3D_SIZE 1\n0 0 0\n"),
+            Err(CubeError::InvalidSize(1))
+        );
+        assert_eq!(
+            CubeLut::parse("LUT_1D_SIZE 2\n0 0\n1 1 1\n"),
+            Err(CubeError::InvalidLine(2))
+        );
+        assert_eq!(
+            CubeLut::parse("LUT_1D_SIZE 2\nLUT_3D_SIZE 2\n"),
+            Err(CubeError::InvalidLine(2))
+        );
+        assert_eq!(
+            CubeLut::parse("LUT_SIZE 2\n"),
+            Err(CubeError::InvalidLine(1))
+        );
+    }
+
+    #[test]
+    fn write_cube() {
+        let lut = Lut3d::bake_in_domain(5, [0.; 3], [2.; 3], |color| {
+            LinearRgba::new(color.red * 0.5, color.green * color.blue, 0.25, 1.)
+        })
+        .with_title("Test");
+        let cube = lut.to_cube();
+        assert!(
+            cube.starts_with("TITLE \"Test\"\nLUT_3D_SIZE 5\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\n")
+        );
+        let CubeLut::Lut3d(parsed) = CubeLut::parse(&cube).unwrap() else {
+            panic!("expected a 3D table");
+        };
+        assert_eq!(parsed.title(), Some("Test"));
+        assert_eq!(parsed.domain_max(), [2.; 3]);
+        for (texel, parsed) in lut.texels().iter().zip(parsed.texels()) {
+            for channel in 0..3 {
+                assert_approx_eq!(texel[channel], parsed[channel], 0.000001);
+            }
+        }
+
+        let one_d = Lut1d::new(vec![[0.; 3], [0.5, 0.25, 1.]]);
+        let cube = CubeLut::Lut1d(one_d.clone()).to_cube();
+        assert_eq!(
+            cube,
+            "LUT_1D_SIZE 2\n0.000000 0.000000 0.000000\n0.500000 0.250000 1.000000\n"
+        );
+        assert_eq!(CubeLut::parse(&cube), Ok(CubeLut::Lut1d(one_d)));
+    }
+
+    #[test]
+    fn interpolation() {
+        // Both methods reproduce a table of a linear function exactly.
+        let identity = Lut3d::identity(3);
+        let tinted = Lut3d::bake(3, |color| {
+            LinearRgba::new(
+                color.red * 0.5 + color.blue * 0.25,
+                color.green,
+                color.blue,
+                1.,
+            )
+        });
+        for rgb in [
+            [0.1, 0.7, 0.3],
+            [0.9, 0.2, 0.55],
+            [0.33, 0.33, 0.9],
+            [2.0, -1.0, 0.5],
+        ] {
+            let clamped = rgb.map(|c: f32| c.clamp(0., 1.));
+            for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+                let sampled = identity.sample(rgb, interpolation);
+                for channel in 0..3 {
+                    assert_approx_eq!(sampled[channel], clamped[channel], 0.0001);
+                }
+                let sampled = tinted.sample(rgb, interpolation);
+                assert_approx_eq!(sampled[0], clamped[0] * 0.5 + clamped[2] * 0.25, 0.0001);
+            }
+        }
+
+        // On the neutral axis, tetrahedral interpolation only uses the neutral texels.
+        let mut texels = Lut3d::identity(2).texels().to_vec();
+        texels[1] = [5., 5., 5.];
+        let lut = Lut3d::new(2, texels);
+        let neutral = lut.sample([0.5; 3], LutInterpolation::Tetrahedral);
+        assert_eq!(neutral, [0.5; 3]);
+        let trilinear = lut.sample([0.5; 3], LutInterpolation::Trilinear);
+        assert!(trilinear[0] > 0.5);
+    }
+
+    #[test]
+    fn bake_functions() {
+        let grade = ColorGrade::new().then(Saturation::new(0.0));
+        let lut = Lut3d::bake(17, |color| grade.grade(color));
+        let color = LinearRgba::new(0.3, 0.6, 0.2, 1.0);
+        let expected = grade.grade(color);
+        let sampled = lut.sample_linear_rgba(color, LutInterpolation::Tetrahedral);
+        assert_approx_eq!(sampled.red, expected.red, 0.0001);
+        assert_approx_eq!(sampled.blue, expected.blue, 0.0001);
+
+        // Between nodes, a curved function is only approximated, within h² / 4 of the
+        // exact value for a node spacing of h.
+        let square = |color: LinearRgba| {
+            LinearRgba::new(
+                color.red.powi(2),
+                color.green.powi(2),
+                color.blue.powi(2),
+                1.,
+            )
+        };
+        let lut = Lut3d::bake(17, square);
+        let tolerance = (1. / 16_f32).powi(2) / 4. + 0.0001;
+        for color in [
+            LinearRgba::new(0.28125, 0.53125, 0.90625, 1.0),
+            LinearRgba::new(0.9, 0.1, 0.45, 1.0),
+            LinearRgba::new(0.7, 0.66, 0.05, 1.0),
+        ] {
+            let expected = square(color);
+            for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+                let sampled = lut.sample_linear_rgba(color, interpolation);
+                assert_approx_eq!(sampled.red, expected.red, tolerance);
+                assert_approx_eq!(sampled.green, expected.green, tolerance);
+                assert_approx_eq!(sampled.blue, expected.blue, tolerance);
+            }
+        }
+
+        let tonemapper = Tonemapper::Reinhard;
+        let lut = Lut3d::bake_in_domain(65, [0.; 3], [4.; 3], |color| tonemapper.tonemap(color));
+        let sampled = lut.sample([1.0, 2.0, 3.0], LutInterpolation::Trilinear);
+        assert_approx_eq!(sampled[0], 0.5, 0.0001);
+        assert_approx_eq!(sampled[2], 0.75, 0.0001);
+    }
+
+    #[test]
+    fn image_3d() {
+        let lut = Lut3d::bake(4, |color| {
+            LinearRgba::new(color.green, color.blue * 0.5, color.red, 1.)
+        });
+        let image = lut.to_image(LutFormat::Rgba32Float);
+        assert_eq!(image.texture_descriptor.dimension, TextureDimension::D3);
+        assert_eq!(image.texture_descriptor.size.depth_or_array_layers, 4);
+        assert_eq!(image.data.len(), 64 * 16);
+        assert_eq!(Lut3d::from_image(&image), Some(lut.clone()));
+
+        for format in [LutFormat::Rgba16Float, LutFormat::Srgba8] {
+            let read = Lut3d::from_image(&lut.to_image(format)).unwrap();
+            for (texel, read) in lut.texels().iter().zip(read.texels()) {
+                for channel in 0..3 {
+                    assert_approx_eq!(texel[channel], read[channel], 0.005);
+                }
+            }
+        }
+
+        let flat = ColorRange::new(SRgba::BLACK, SRgba::WHITE).bake_image(
+            8,
+            LutFormat::Rgba32Float,
+            false,
+        );
+        assert_eq!(Lut3d::from_image(&flat), None);
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{
+    math::{matrix_multiply, EuclideanModulo},
+    LinearRgba, Mix, SRgba, WithAlpha,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the CIE 1976 L\*u\*v\* (CIELUV) color space, with alpha.
+///
+/// Components are scaled by 1/100 like [`crate::Lcha`], so that lightness goes from 0.0 to
+/// 1.0. The white point is D65.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Luva {
+    pub l: f32,
+    pub u: f32,
+    pub v: f32,
+    pub alpha: f32,
+}
+
+impl Luva {
+    /// Construct a new [`Luva`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `l` - Lightness channel. [0.0, 1.0]
+    /// * `u` - Green-red channel. [~-0.84, ~1.76]
+    /// * `v` - Blue-yellow channel. [~-1.35, ~1.08]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(l: f32, u: f32, v: f32, alpha: f32) -> Self {
+        Self { l, u, v, alpha }
+    }
+
+    /// Convert the Luva color to a tuple of components (l, u, v, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.l, self.u, self.v, self.alpha)
+    }
+
+    /// Construct a new [`Luva`] color from a tuple of components (l, u, v, alpha).
+    #[inline]
+    pub const fn from_components((l, u, v, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(l, u, v, alpha)
+    }
+}
+
+impl Default for Luva {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Luva {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            l: self.l * n_factor + other.l * factor,
+            u: self.u * n_factor + other.u * factor,
+            v: self.v * n_factor + other.v * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Luva {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+/// Color in CIE LCh(uv), the polar form of [`Luva`], with alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Lchuva {
+    pub lightness: f32,
+    pub chroma: f32,
+    pub hue: f32,
+    pub alpha: f32,
+}
+
+impl Lchuva {
+    /// Construct a new [`Lchuva`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `lightness` - Lightness channel. [0.0, 1.0]
+    /// * `chroma` - Chroma channel. [0.0, ~1.8]
+    /// * `hue` - Hue channel. [0.0, 360.0]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> Self {
+        Self {
+            lightness,
+            chroma,
+            hue,
+            alpha,
+        }
+    }
+
+    /// Convert the Lchuva color to a tuple of components (lightness, chroma, hue, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.lightness, self.chroma, self.hue, self.alpha)
+    }
+
+    /// Construct a new [`Lchuva`] color from a tuple of components (lightness, chroma, hue,
+    /// alpha).
+    #[inline]
+    pub const fn from_components((lightness, chroma, hue, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(lightness, chroma, hue, alpha)
+    }
+}
+
+impl Default for Lchuva {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Lchuva {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        let hue = self.hue.lerp_modulo(other.hue, factor, 360.);
+        Self {
+            lightness: self.lightness * n_factor + other.lightness * factor,
+            chroma: self.chroma * n_factor + other.chroma * factor,
+            hue,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Lchuva {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+// The conversion follows the reference HSLuv implementation, including its sRGB matrices,
+// so that the gamut boundaries computed for `Hsluva` match the conversion exactly.
+
+pub(crate) const XYZ_TO_LINEAR_RGB: [[f32; 3]; 3] = [
+    [3.240_97, -1.537_383_2, -0.498_610_76],
+    [-0.969_243_6, 1.875_967_5, 0.041_555_06],
+    [0.055_630_08, -0.203_976_96, 1.056_971_5],
+];
+
+const LINEAR_RGB_TO_XYZ: [[f32; 3]; 3] = [
+    [0.412_390_8, 0.357_584_33, 0.180_480_8],
+    [0.212_639, 0.715_168_65, 0.072_192_32],
+    [0.019_330_818, 0.119_194_78, 0.950_532_14],
+];
+
+#[allow(clippy::excessive_precision)]
+const REF_U: f32 = 0.197_830_01;
+#[allow(clippy::excessive_precision)]
+const REF_V: f32 = 0.468_319_98;
+pub(crate) const KAPPA: f32 = 903.296_3;
+pub(crate) const EPSILON: f32 = 0.008_856_452;
+
+impl From<LinearRgba> for Luva {
+    fn from(value: LinearRgba) -> Self {
+        let [x, y, z] = matrix_multiply(&LINEAR_RGB_TO_XYZ, [value.red, value.green, value.blue]);
+        let lightness = if y <= EPSILON {
+            y * KAPPA
+        } else {
+            116. * y.cbrt() - 16.
+        };
+        let divider = x + 15. * y + 3. * z;
+        if lightness <= 0. || divider == 0. {
+            return Luva::new(0., 0., 0., value.alpha);
+        }
+        let u_prime = 4. * x / divider;
+        let v_prime = 9. * y / divider;
+        Luva::new(
+            lightness / 100.,
+            13. * lightness * (u_prime - REF_U) / 100.,
+            13. * lightness * (v_prime - REF_V) / 100.,
+            value.alpha,
+        )
+    }
+}
+
+impl From<Luva> for LinearRgba {
+    fn from(value: Luva) -> Self {
+        let Luva { l, u, v, alpha } = value;
+        if l <= 0. {
+            return LinearRgba::new(0., 0., 0., alpha);
+        }
+        let lightness = l * 100.;
+        let u_prime = u / (13. * l) + REF_U;
+        let v_prime = v / (13. * l) + REF_V;
+        let y = if lightness <= 8. {
+            lightness / KAPPA
+        } else {
+            ((lightness + 16.) / 116.).powi(3)
+        };
+        let x = 9. * y * u_prime / (4. * v_prime);
+        let z = (9. * y - 15. * v_prime * y - v_prime * x) / (3. * v_prime);
+        let [red, green, blue] = matrix_multiply(&XYZ_TO_LINEAR_RGB, [x, y, z]);
+        LinearRgba::new(red, green, blue, alpha)
+    }
+}
+
+impl From<SRgba> for Luva {
+    fn from(value: SRgba) -> Self {
+        Luva::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Luva> for SRgba {
+    fn from(value: Luva) -> Self {
+        SRgba::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Luva> for Lchuva {
+    fn from(value: Luva) -> Self {
+        let Luva { l, u, v, alpha } = value;
+        let chroma = u.hypot(v);
+        let hue = if chroma < 1e-8 {
+            0.
+        } else {
+            v.atan2(u).to_degrees().rem_euclid(360.)
+        };
+        Lchuva::new(l, chroma, hue, alpha)
+    }
+}
+
+impl From<Lchuva> for Luva {
+    fn from(value: Lchuva) -> Self {
+        let (sin, cos) = value.hue.to_radians().sin_cos();
+        Luva::new(
+            value.lightness,
+            value.chroma * cos,
+            value.chroma * sin,
+            value.alpha,
+        )
+    }
+}
+
+impl From<LinearRgba> for Lchuva {
+    fn from(value: LinearRgba) -> Self {
+        Luva::from(value).into()
+    }
+}
+
+impl From<Lchuva> for LinearRgba {
+    fn from(value: Lchuva) -> Self {
+        Luva::from(value).into()
+    }
+}
+
+impl From<SRgba> for Lchuva {
+    fn from(value: SRgba) -> Self {
+        Luva::from(value).into()
+    }
+}
+
+impl From<Lchuva> for SRgba {
+    fn from(value: Lchuva) -> Self {
+        Luva::from(value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn round_trip() {
+        for color in TEST_COLORS.iter() {
+            let lch = Lchuva::from(color.linear_rgb);
+            let back = LinearRgba::from(lch);
+            assert_approx_eq!(back.red, color.linear_rgb.red, 0.0001);
+            assert_approx_eq!(back.green, color.linear_rgb.green, 0.0001);
+            assert_approx_eq!(back.blue, color.linear_rgb.blue, 0.0001);
+            assert_approx_eq!(back.alpha, color.linear_rgb.alpha, 0.0001);
+        }
+    }
+
+    #[test]
+    fn white_and_black() {
+        let white = Luva::from(SRgba::WHITE);
+        assert_approx_eq!(white.l, 1.0, 0.0001);
+        assert_approx_eq!(white.u, 0.0, 0.0001);
+        assert_approx_eq!(white.v, 0.0, 0.0001);
+        assert_eq!(Luva::from(SRgba::BLACK), Luva::new(0., 0., 0., 1.));
+        assert_eq!(
+            LinearRgba::from(Luva::default()),
+            LinearRgba::new(0., 0., 0., 1.)
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{Hcta, SRgba};
+
+/// A palette of colors sharing one HCT hue and chroma, varying only in tone. This is the
+/// building block of the Material Design 3 color system.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{SRgba, TonalPalette};
+/// let palette = TonalPalette::from_color(SRgba::hex("#6750a4").unwrap());
+/// let container = palette.tone(90.);
+/// let on_container = palette.tone(10.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TonalPalette {
+    hue: f32,
+    chroma: f32,
+}
+
+impl TonalPalette {
+    /// Construct a palette from an HCT hue and chroma.
+    pub const fn new(hue: f32, chroma: f32) -> Self {
+        Self { hue, chroma }
+    }
+
+    /// Construct a palette from the hue and chroma of a color.
+    pub fn from_color(color: impl Into<Hcta>) -> Self {
+        let hct: Hcta = color.into();
+        Self::new(hct.hue, hct.chroma)
+    }
+
+    /// The HCT hue of this palette.
+    pub fn hue(&self) -> f32 {
+        self.hue
+    }
+
+    /// The HCT chroma of this palette. Colors at very high or low tones may have less chroma,
+    /// as they would otherwise be outside of the sRGB gamut.
+    pub fn chroma(&self) -> f32 {
+        self.chroma
+    }
+
+    /// The color of this palette with the given tone, from 0.0 (black) to 100.0 (white).
+    pub fn tone(&self, tone: f32) -> SRgba {
+        Hcta::new(self.hue, self.chroma, tone, 1.).into()
+    }
+}
+
+/// The set of tonal palettes from which a [`MaterialScheme`] is built, derived from a single
+/// seed color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorePalette {
+    pub primary: TonalPalette,
+    pub secondary: TonalPalette,
+    pub tertiary: TonalPalette,
+    pub neutral: TonalPalette,
+    pub neutral_variant: TonalPalette,
+    pub error: TonalPalette,
+}
+
+impl CorePalette {
+    /// The palettes of the default Material Design 3 scheme: the primary palette is the
+    /// seed's hue with a chroma of at least 48, and the other palettes have fixed, muted
+    /// chromas.
+    pub fn new(seed: impl Into<Hcta>) -> Self {
+        let seed: Hcta = seed.into();
+        let hue = seed.hue;
+        Self {
+            primary: TonalPalette::new(hue, seed.chroma.max(48.)),
+            secondary: TonalPalette::new(hue, 16.),
+            tertiary: TonalPalette::new(hue + 60., 24.),
+            neutral: TonalPalette::new(hue, 4.),
+            neutral_variant: TonalPalette::new(hue, 8.),
+            error: ERROR_PALETTE,
+        }
+    }
+
+    /// The palettes of the content variant, which keeps the seed's chroma so that the
+    /// scheme stays faithful to the seed (for example, the colors of an image).
+    pub fn content(seed: impl Into<Hcta>) -> Self {
+        let seed: Hcta = seed.into();
+        let (hue, chroma) = (seed.hue, seed.chroma);
+        Self {
+            primary: TonalPalette::new(hue, chroma),
+            secondary: TonalPalette::new(hue, chroma / 3.),
+            tertiary: TonalPalette::new(hue + 60., chroma / 2.),
+            neutral: TonalPalette::new(hue, (chroma / 12.).min(4.)),
+            neutral_variant: TonalPalette::new(hue, (chroma / 6.).min(8.)),
+            error: ERROR_PALETTE,
+        }
+    }
+}
+
+const ERROR_PALETTE: TonalPalette = TonalPalette::new(25., 84.);
+
+/// The color roles of a Material Design 3 color scheme.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{MaterialScheme, SRgba};
+/// let seed = SRgba::hex("#6750a4").unwrap();
+/// let light = MaterialScheme::light(seed);
+/// let dark = MaterialScheme::dark(seed);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaterialScheme {
+    pub primary: SRgba,
+    pub on_primary: SRgba,
+    pub primary_container: SRgba,
+    pub on_primary_container: SRgba,
+    pub secondary: SRgba,
+    pub on_secondary: SRgba,
+    pub secondary_container: SRgba,
+    pub on_secondary_container: SRgba,
+    pub tertiary: SRgba,
+    pub on_tertiary: SRgba,
+    pub tertiary_container: SRgba,
+    pub on_tertiary_container: SRgba,
+    pub error: SRgba,
+    pub on_error: SRgba,
+    pub error_container: SRgba,
+    pub on_error_container: SRgba,
+    pub background: SRgba,
+    pub on_background: SRgba,
+    pub surface: SRgba,
+    pub on_surface: SRgba,
+    pub surface_variant: SRgba,
+    pub on_surface_variant: SRgba,
+    pub outline: SRgba,
+    pub outline_variant: SRgba,
+    pub shadow: SRgba,
+    pub scrim: SRgba,
+    pub inverse_surface: SRgba,
+    pub inverse_on_surface: SRgba,
+    pub inverse_primary: SRgba,
+}
+
+impl MaterialScheme {
+    /// The light scheme for a seed color.
+    pub fn light(seed: impl Into<Hcta>) -> Self {
+        Self::light_from_palette(&CorePalette::new(seed))
+    }
+
+    /// The dark scheme for a seed color.
+    pub fn dark(seed: impl Into<Hcta>) -> Self {
+        Self::dark_from_palette(&CorePalette::new(seed))
+    }
+
+    /// The light scheme for a set of palettes, such as [`CorePalette::content`].
+    pub fn light_from_palette(palette: &CorePalette) -> Self {
+        let p = palette;
+        Self {
+            primary: p.primary.tone(40.),
+            on_primary: p.primary.tone(100.),
+            primary_container: p.primary.tone(90.),
+            on_primary_container: p.primary.tone(10.),
+            secondary: p.secondary.tone(40.),
+            on_secondary: p.secondary.tone(100.),
+            secondary_container: p.secondary.tone(90.),
+            on_secondary_container: p.secondary.tone(10.),
+            tertiary: p.tertiary.tone(40.),
+            on_tertiary: p.tertiary.tone(100.),
+            tertiary_container: p.tertiary.tone(90.),
+            on_tertiary_container: p.tertiary.tone(10.),
+            error: p.error.tone(40.),
+            on_error: p.error.tone(100.),
+            error_container: p.error.tone(90.),
+            on_error_container: p.error.tone(10.),
+            background: p.neutral.tone(99.),
+            on_background: p.neutral.tone(10.),
+            surface: p.neutral.tone(99.),
+            on_surface: p.neutral.tone(10.),
+            surface_variant: p.neutral_variant.tone(90.),
+            on_surface_variant: p.neutral_variant.tone(30.),
+            outline: p.neutral_variant.tone(50.),
+            outline_variant: p.neutral_variant.tone(80.),
+            shadow: p.neutral.tone(0.),
+            scrim: p.neutral.tone(0.),
+            inverse_surface: p.neutral.tone(20.),
+            inverse_on_surface: p.neutral.tone(95.),
+            inverse_primary: p.primary.tone(80.),
+        }
+    }
+
+    /// The dark scheme for a set of palettes, such as [`CorePalette::content`].
+    pub fn dark_from_palette(palette: &CorePalette) -> Self {
+        let p = palette;
+        Self {
+            primary: p.primary.tone(80.),
+            on_primary: p.primary.tone(20.),
+            primary_container: p.primary.tone(30.),
+            on_primary_container: p.primary.tone(90.),
+            secondary: p.secondary.tone(80.),
+            on_secondary: p.secondary.tone(20.),
+            secondary_container: p.secondary.tone(30.),
+            on_secondary_container: p.secondary.tone(90.),
+            tertiary: p.tertiary.tone(80.),
+            on_tertiary: p.tertiary.tone(20.),
+            tertiary_container: p.tertiary.tone(30.),
+            on_tertiary_container: p.tertiary.tone(90.),
+            error: p.error.tone(80.),
+            on_error: p.error.tone(20.),
+            error_container: p.error.tone(30.),
+            on_error_container: p.error.tone(90.),
+            background: p.neutral.tone(10.),
+            on_background: p.neutral.tone(90.),
+            surface: p.neutral.tone(10.),
+            on_surface: p.neutral.tone(90.),
+            surface_variant: p.neutral_variant.tone(30.),
+            on_surface_variant: p.neutral_variant.tone(80.),
+            outline: p.neutral_variant.tone(60.),
+            outline_variant: p.neutral_variant.tone(30.),
+            shadow: p.neutral.tone(0.),
+            scrim: p.neutral.tone(0.),
+            inverse_surface: p.neutral.tone(90.),
+            inverse_on_surface: p.neutral.tone(20.),
+            inverse_primary: p.primary.tone(40.),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(color: SRgba) -> String {
+        let [r, g, b] = [color.red, color.green, color.blue].map(|c| (c * 255.).round() as u8);
+        format!("{:02x}{:02x}{:02x}", r, g, b)
+    }
+
+    // Reference values from Material Color Utilities.
+
+    #[test]
+    fn tonal_palette() {
+        let palette = TonalPalette::from_color(SRgba::hex("0000ff").unwrap());
+        for (tone, expected) in [
+            (100., "ffffff"),
+            (95., "f1efff"),
+            (90., "e0e0ff"),
+            (80., "bec2ff"),
+            (70., "9da3ff"),
+            (60., "7c84ff"),
+            (50., "5a64ff"),
+            (40., "343dff"),
+            (30., "0000ef"),
+            (20., "0001ac"),
+            (10., "00006e"),
+            (0., "000000"),
+        ] {
+            assert_eq!(hex(palette.tone(tone)), expected, "tone {}", tone);
+        }
+    }
+
+    #[test]
+    fn schemes() {
+        let blue = SRgba::hex("0000ff").unwrap();
+        assert_eq!(hex(MaterialScheme::light(blue).primary), "343dff");
+        assert_eq!(hex(MaterialScheme::dark(blue).primary), "bec2ff");
+
+        let seed = SRgba::hex("6750a4").unwrap();
+        let light = MaterialScheme::light(seed);
+        assert_eq!(hex(light.primary), "6750a4");
+        assert_eq!(hex(light.secondary), "625b71");
+        assert_eq!(hex(light.tertiary), "7e5260");
+        assert_eq!(hex(light.surface), "fffbff");
+        assert_eq!(hex(light.on_surface), "1c1b1e");
+
+        let dark = MaterialScheme::dark(seed);
+        assert_eq!(hex(dark.primary), "cfbcff");
+        assert_eq!(hex(dark.secondary), "cbc2db");
+        assert_eq!(hex(dark.tertiary), "efb8c8");
+        assert_eq!(hex(dark.surface), "1c1b1e");
+        assert_eq!(hex(dark.on_surface), "e6e1e6");
+    }
+
+    #[test]
+    fn content_palette() {
+        let seed = SRgba::hex("6750a4").unwrap();
+        let hct = Hcta::from(seed);
+        let palette = CorePalette::content(seed);
+        assert_eq!(palette.primary.chroma(), hct.chroma);
+        assert_eq!(palette.secondary.chroma(), hct.chroma / 3.);
+        assert_eq!(palette.neutral.chroma(), (hct.chroma / 12.).min(4.));
+        assert_eq!(
+            hex(MaterialScheme::light_from_palette(&palette).primary),
+            "6750a4"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Small vector and matrix helpers shared by the color space conversions.
+
+use std::ops::{Add, Mul};
+
+/// A 3x3 matrix, as an array of rows.
+pub(crate) type Matrix3 = [[f32; 3]; 3];
+
+/// The dot product of two 3-vectors.
+#[inline]
+pub(crate) fn dot<T>(a: [T; 3], b: [T; 3]) -> T
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Multiply a 3-vector by a 3x3 matrix, given as an array of rows.
+#[inline]
+pub(crate) fn matrix_multiply<T>(m: &[[T; 3]; 3], v: [T; 3]) -> [T; 3]
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    m.map(|row| dot(row, v))
+}
+
+/// Arithmetic on values which wrap around, such as hues in degrees.
+pub(crate) trait EuclideanModulo {
+    /// Interpolate from `self` to `other` the shortest way around a circle of size
+    /// `modulus`. The result is in the range `[0, modulus)`.
+    fn lerp_modulo(self, other: Self, factor: Self, modulus: Self) -> Self;
+}
+
+impl EuclideanModulo for f32 {
+    #[inline]
+    fn lerp_modulo(self, other: f32, factor: f32, modulus: f32) -> f32 {
+        let half = modulus / 2.;
+        let shortest_angle = (other - self + half).rem_euclid(modulus) - half;
+        (self + shortest_angle * factor).rem_euclid(modulus)
+    }
+}
+
+/// The product of two 3x3 matrices.
+pub(crate) fn multiply(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut result = [[0.; 3]; 3];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    result
+}
+
+/// The inverse of a 3x3 matrix, computed in `f64` for accuracy.
+pub(crate) fn invert(m: &Matrix3) -> Matrix3 {
+    let m = m.map(|row| row.map(f64::from));
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    let det = m[0][0] * cofactor(1, 2, 1, 2) - m[0][1] * cofactor(1, 2, 0, 2)
+        + m[0][2] * cofactor(1, 2, 0, 1);
+    [
+        [
+            cofactor(1, 2, 1, 2) / det,
+            -cofactor(0, 2, 1, 2) / det,
+            cofactor(0, 1, 1, 2) / det,
+        ],
+        [
+            -cofactor(1, 2, 0, 2) / det,
+            cofactor(0, 2, 0, 2) / det,
+            -cofactor(0, 1, 0, 2) / det,
+        ],
+        [
+            cofactor(1, 2, 0, 1) / det,
+            -cofactor(0, 2, 0, 1) / det,
+            cofactor(0, 1, 0, 1) / det,
+        ],
+    ]
+    .map(|row| row.map(|v| v as f32))
+}
+
+const BRADFORD: Matrix3 = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+/// The Bradford chromatic adaptation matrix from one white point to another.
+pub(crate) fn bradford(from: [f32; 3], to: [f32; 3]) -> Matrix3 {
+    let from = matrix_multiply(&BRADFORD, from);
+    let to = matrix_multiply(&BRADFORD, to);
+    let mut scale = [[0.; 3]; 3];
+    for i in 0..3 {
+        scale[i][i] = to[i] / from[i];
+    }
+    multiply(&invert(&BRADFORD), &multiply(&scale, &BRADFORD))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_approx_eq;
+
+    #[test]
+    fn lerp_modulo() {
+        // The hue takes the shortest way around the circle.
+        assert_approx_eq!(350_f32.lerp_modulo(10., 0.25, 360.), 355., 0.001);
+        assert_approx_eq!(350_f32.lerp_modulo(10., 0.5, 360.), 0., 0.001);
+        assert_approx_eq!(350_f32.lerp_modulo(10., 0.75, 360.), 5., 0.001);
+        assert_approx_eq!(10_f32.lerp_modulo(350., 0.75, 360.), 355., 0.001);
+        assert_approx_eq!(40_f32.lerp_modulo(100., 0.5, 360.), 70., 0.001);
+        assert_approx_eq!(350_f32.lerp_modulo(10., 1., 360.), 10., 0.001);
+        assert_approx_eq!(0.9_f32.lerp_modulo(0.1, 0.5, 1.), 0., 0.001);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    gamut::{okhsl_chromas, toe, toe_inv},
+    math::EuclideanModulo,
+    LinearRgba, Mix, Oklaba, SRgba, WithAlpha,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the Okhsl color space (hue, saturation, lightness), with alpha.
+///
+/// Okhsl, by Björn Ottosson, is an alternative to [`crate::Hsla`] built on [`Oklaba`]. The
+/// hue is the Oklab hue, lightness is a close estimate of CIE L*, so that colors of equal
+/// lightness look equally light whatever their hue, and saturation is scaled so that 1.0 is
+/// the edge of the sRGB gamut. Every combination of components is inside the sRGB gamut,
+/// which makes it a good fit for color pickers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Okhsla {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub alpha: f32,
+}
+
+impl Okhsla {
+    /// Construct a new [`Okhsla`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue` - Hue channel. [0.0, 360.0]
+    /// * `saturation` - Saturation channel. [0.0, 1.0]
+    /// * `lightness` - Lightness channel. [0.0, 1.0]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        }
+    }
+
+    /// Convert the Okhsla color to a tuple of components (hue, saturation, lightness, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.hue, self.saturation, self.lightness, self.alpha)
+    }
+
+    /// Construct a new [`Okhsla`] color from a tuple of components (hue, saturation,
+    /// lightness, alpha).
+    #[inline]
+    pub const fn from_components(
+        (hue, saturation, lightness, alpha): (f32, f32, f32, f32),
+    ) -> Self {
+        Self::new(hue, saturation, lightness, alpha)
+    }
+}
+
+impl Default for Okhsla {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Okhsla {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        let hue = self.hue.lerp_modulo(other.hue, factor, 360.);
+        Self {
+            hue,
+            saturation: self.saturation * n_factor + other.saturation * factor,
+            lightness: self.lightness * n_factor + other.lightness * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Okhsla {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+/// Okhsl saturation at which the chroma reaches `c_mid`.
+const MID: f32 = 0.8;
+const MID_INV: f32 = 1.25;
+
+impl From<Oklaba> for Okhsla {
+    fn from(value: Oklaba) -> Self {
+        let Oklaba { l, a, b, alpha } = value;
+        let chroma = a.hypot(b);
+        let hue = b.atan2(a).to_degrees().rem_euclid(360.);
+        if l <= 0. || l >= 1. || chroma < 1e-7 {
+            return Okhsla::new(hue, 0., toe(l.clamp(0., 1.)), alpha);
+        }
+        let (a_, b_) = (a / chroma, b / chroma);
+        let (c_0, c_mid, c_max) = okhsl_chromas(l, a_, b_);
+        let saturation = if chroma < c_mid {
+            let k_1 = MID * c_0;
+            let k_2 = 1. - k_1 / c_mid;
+            let t = chroma / (k_1 + k_2 * chroma);
+            t * MID
+        } else {
+            let k_0 = c_mid;
+            let k_1 = (1. - MID) * c_mid * c_mid * MID_INV * MID_INV / c_0;
+            let k_2 = 1. - k_1 / (c_max - c_mid);
+            let t = (chroma - k_0) / (k_1 + k_2 * (chroma - k_0));
+            MID + (1. - MID) * t
+        };
+        Okhsla::new(hue, saturation, toe(l), alpha)
+    }
+}
+
+impl From<Okhsla> for Oklaba {
+    fn from(value: Okhsla) -> Self {
+        let Okhsla {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        } = value;
+        if lightness >= 1. {
+            return Oklaba::new(1., 0., 0., alpha);
+        } else if lightness <= 0. {
+            return Oklaba::new(0., 0., 0., alpha);
+        }
+        let (b_, a_) = hue.to_radians().sin_cos();
+        let l = toe_inv(lightness);
+        let (c_0, c_mid, c_max) = okhsl_chromas(l, a_, b_);
+        let chroma = if saturation < MID {
+            let t = MID_INV * saturation;
+            let k_1 = MID * c_0;
+            let k_2 = 1. - k_1 / c_mid;
+            t * k_1 / (1. - k_2 * t)
+        } else {
+            let t = (saturation - MID) / (1. - MID);
+            let k_0 = c_mid;
+            let k_1 = (1. - MID) * c_mid * c_mid * MID_INV * MID_INV / c_0;
+            let k_2 = 1. - k_1 / (c_max - c_mid);
+            k_0 + t * k_1 / (1. - k_2 * t)
+        };
+        Oklaba::new(l, chroma * a_, chroma * b_, alpha)
+    }
+}
+
+impl From<LinearRgba> for Okhsla {
+    fn from(value: LinearRgba) -> Self {
+        Okhsla::from(Oklaba::from(value))
+    }
+}
+
+impl From<Okhsla> for LinearRgba {
+    fn from(value: Okhsla) -> Self {
+        LinearRgba::from(Oklaba::from(value))
+    }
+}
+
+impl From<SRgba> for Okhsla {
+    fn from(value: SRgba) -> Self {
+        Okhsla::from(Oklaba::from(value))
+    }
+}
+
+impl From<Okhsla> for SRgba {
+    fn from(value: Okhsla) -> Self {
+        SRgba::from(Oklaba::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gamut::in_srgb_gamut, test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn round_trip() {
+        for color in TEST_COLORS.iter() {
+            let okhsl = Okhsla::from(color.rgb);
+            let back = SRgba::from(okhsl);
+            assert_approx_eq!(back.red, color.rgb.red, 0.001);
+            assert_approx_eq!(back.green, color.rgb.green, 0.001);
+            assert_approx_eq!(back.blue, color.rgb.blue, 0.001);
+            assert_approx_eq!(back.alpha, color.rgb.alpha, 0.001);
+        }
+    }
+
+    #[test]
+    fn gamut_edges() {
+        // Fully saturated sRGB colors have a saturation of 1.0.
+        for color in [
+            SRgba::RED,
+            SRgba::GREEN,
+            SRgba::BLUE,
+            SRgba::new(1., 1., 0., 1.),
+            SRgba::new(0., 1., 1., 1.),
+            SRgba::new(1., 0., 1., 1.),
+        ] {
+            assert_approx_eq!(Okhsla::from(color).saturation, 1.0, 0.002);
+        }
+        let white = Okhsla::from(SRgba::WHITE);
+        assert_approx_eq!(white.lightness, 1.0, 0.0001);
+        assert_approx_eq!(white.saturation, 0.0, 0.0001);
+        assert_eq!(Okhsla::from(SRgba::BLACK).lightness, 0.);
+
+        // Every combination of components is in gamut.
+        for hue in (0..360).step_by(15) {
+            for lightness in [0.05, 0.3, 0.5, 0.7, 0.95] {
+                let color = Okhsla::new(hue as f32, 1., lightness, 1.);
+                assert!(in_srgb_gamut(color.into()), "{:?}", color);
+            }
+        }
+    }
+
+    #[test]
+    fn lightness_is_perceptual() {
+        // #777777 has a CIE L* of 50.
+        let gray = Okhsla::from(SRgba::hex("777777").unwrap());
+        assert_approx_eq!(gray.lightness, 0.5, 0.01);
+
+        // Yellow and blue at the same Okhsl lightness have the same Oklab lightness.
+        let yellow = Oklaba::from(Okhsla::new(110., 1., 0.6, 1.));
+        let blue = Oklaba::from(Okhsla::new(265., 1., 0.6, 1.));
+        assert_approx_eq!(yellow.l, blue.l, 0.0001);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    gamut::{find_cusp, toe, toe_inv},
+    math::EuclideanModulo,
+    LinearRgba, Mix, Oklaba, SRgba, WithAlpha,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the Okhsv color space (hue, saturation, value), with alpha.
+///
+/// Okhsv, by Björn Ottosson, is an alternative to HSV built on [`Oklaba`]. Like HSV, the
+/// sRGB gamut maps to the full cylinder: a saturation and value of 1.0 give the most
+/// saturated color of a hue, and every combination of components is inside the sRGB gamut.
+/// Unlike HSV, the hue is the perceptual Oklab hue and saturation changes look even.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Okhsva {
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub alpha: f32,
+}
+
+impl Okhsva {
+    /// Construct a new [`Okhsva`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue` - Hue channel. [0.0, 360.0]
+    /// * `saturation` - Saturation channel. [0.0, 1.0]
+    /// * `value` - Value channel. [0.0, 1.0]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            value,
+            alpha,
+        }
+    }
+
+    /// Convert the Okhsva color to a tuple of components (hue, saturation, value, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.hue, self.saturation, self.value, self.alpha)
+    }
+
+    /// Construct a new [`Okhsva`] color from a tuple of components (hue, saturation, value,
+    /// alpha).
+    #[inline]
+    pub const fn from_components((hue, saturation, value, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(hue, saturation, value, alpha)
+    }
+}
+
+impl Default for Okhsva {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Okhsva {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        let hue = self.hue.lerp_modulo(other.hue, factor, 360.);
+        Self {
+            hue,
+            saturation: self.saturation * n_factor + other.saturation * factor,
+            value: self.value * n_factor + other.value * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for Okhsva {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+/// The saturation slope of the triangle approximating the gamut, which is the same for all
+/// hues so that saturation is consistent between them.
+const S_0: f32 = 0.5;
+
+/// The factor by which to scale an Oklab lightness and chroma, so that the color at the
+/// top of the triangle approximation lands exactly on the gamut boundary.
+fn boundary_scale(a_: f32, b_: f32, l_v: f32, c_v: f32) -> f32 {
+    let l_vt = toe_inv(l_v);
+    let c_vt = c_v * l_vt / l_v;
+    let rgb = LinearRgba::from(Oklaba::new(l_vt, a_ * c_vt, b_ * c_vt, 1.));
+    (1. / rgb.red.max(rgb.green).max(rgb.blue).max(0.)).cbrt()
+}
+
+impl From<Oklaba> for Okhsva {
+    fn from(value: Oklaba) -> Self {
+        let Oklaba { l, a, b, alpha } = value;
+        let chroma = a.hypot(b);
+        let hue = b.atan2(a).to_degrees().rem_euclid(360.);
+        if l <= 0. {
+            return Okhsva::new(hue, 0., 0., alpha);
+        }
+        let (a_, b_) = if chroma < 1e-7 {
+            (1., 0.)
+        } else {
+            (a / chroma, b / chroma)
+        };
+        let (s_max, t_max) = find_cusp(a_, b_).to_st();
+        let k = 1. - S_0 / s_max;
+
+        // Find the point on the top edge of the triangle, on the line from black.
+        let t = t_max / (chroma + l * t_max);
+        let l_v = t * l;
+        let c_v = t * chroma;
+
+        let l = l / boundary_scale(a_, b_, l_v, c_v);
+        Okhsva::new(
+            hue,
+            (S_0 + t_max) * c_v / (t_max * S_0 + t_max * k * c_v),
+            toe(l) / l_v,
+            alpha,
+        )
+    }
+}
+
+impl From<Okhsva> for Oklaba {
+    fn from(value: Okhsva) -> Self {
+        let Okhsva {
+            hue,
+            saturation,
+            value,
+            alpha,
+        } = value;
+        if value <= 0. {
+            return Oklaba::new(0., 0., 0., alpha);
+        }
+        let (b_, a_) = hue.to_radians().sin_cos();
+        let (s_max, t_max) = find_cusp(a_, b_).to_st();
+        let k = 1. - S_0 / s_max;
+
+        // The lightness and chroma at the top edge of the triangle for this saturation.
+        let denominator = S_0 + t_max - t_max * k * saturation;
+        let l_v = 1. - saturation * S_0 / denominator;
+        let c_v = saturation * t_max * S_0 / denominator;
+
+        let l = value * l_v;
+        let chroma = value * c_v;
+        let l_new = toe_inv(l);
+        let chroma = chroma * l_new / l;
+
+        let scale = boundary_scale(a_, b_, l_v, c_v);
+        let l = l_new * scale;
+        let chroma = chroma * scale;
+        Oklaba::new(l, chroma * a_, chroma * b_, alpha)
+    }
+}
+
+impl From<LinearRgba> for Okhsva {
+    fn from(value: LinearRgba) -> Self {
+        Okhsva::from(Oklaba::from(value))
+    }
+}
+
+impl From<Okhsva> for LinearRgba {
+    fn from(value: Okhsva) -> Self {
+        LinearRgba::from(Oklaba::from(value))
+    }
+}
+
+impl From<SRgba> for Okhsva {
+    fn from(value: SRgba) -> Self {
+        Okhsva::from(Oklaba::from(value))
+    }
+}
+
+impl From<Okhsva> for SRgba {
+    fn from(value: Okhsva) -> Self {
+        SRgba::from(Oklaba::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gamut::in_srgb_gamut, test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn round_trip() {
+        for color in TEST_COLORS.iter() {
+            let okhsv = Okhsva::from(color.rgb);
+            let back = SRgba::from(okhsv);
+            assert_approx_eq!(back.red, color.rgb.red, 0.001);
+            assert_approx_eq!(back.green, color.rgb.green, 0.001);
+            assert_approx_eq!(back.blue, color.rgb.blue, 0.001);
+            assert_approx_eq!(back.alpha, color.rgb.alpha, 0.001);
+        }
+    }
+
+    #[test]
+    fn gamut_edges() {
+        // Fully saturated sRGB colors have a saturation and value of 1.0.
+        for color in [
+            SRgba::RED,
+            SRgba::GREEN,
+            SRgba::BLUE,
+            SRgba::new(1., 1., 0., 1.),
+            SRgba::new(0., 1., 1., 1.),
+            SRgba::new(1., 0., 1., 1.),
+        ] {
+            let okhsv = Okhsva::from(color);
+            assert_approx_eq!(okhsv.saturation, 1.0, 0.002);
+            assert_approx_eq!(okhsv.value, 1.0, 0.002);
+        }
+        let white = Okhsva::from(SRgba::WHITE);
+        assert_approx_eq!(white.value, 1.0, 0.0001);
+        assert_approx_eq!(white.saturation, 0.0, 0.0001);
+        assert_eq!(Okhsva::from(SRgba::BLACK).value, 0.);
+
+        // Every combination of components is in gamut, and full saturation and value is on
+        // the gamut boundary.
+        for hue in (0..360).step_by(15) {
+            for saturation in [0., 0.5, 1.] {
+                for value in [0.25, 0.5, 1.] {
+                    let color = Okhsva::new(hue as f32, saturation, value, 1.);
+                    assert!(in_srgb_gamut(color.into()), "{:?}", color);
+                }
+            }
+            let cusp = LinearRgba::from(Okhsva::new(hue as f32, 1., 1., 1.));
+            let channels = [cusp.red, cusp.green, cusp.blue];
+            assert_approx_eq!(channels.into_iter().fold(0., f32::max), 1., 0.001);
+            assert_approx_eq!(channels.into_iter().fold(1., f32::min), 0., 0.001);
+        }
+    }
+
+    #[test]
+    fn hue_matches_oklab() {
+        let seed = Oklaba::from(SRgba::hex("#3a86ff").unwrap());
+        let okhsv = Okhsva::from(seed);
+        assert_approx_eq!(
+            okhsv.hue,
+            seed.b.atan2(seed.a).to_degrees().rem_euclid(360.),
+            0.0001
+        );
+    }
+}
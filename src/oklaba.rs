@@ -1,7 +1,8 @@
 use crate::{
     color_difference::EuclideanDistance,
+    color_ops::impl_componentwise_ops,
     to_css_string::{RoundToDecimalPlaces, ToCssString},
-    LinearRgba, LuminanceOps, Mix, SRgba, WithAlpha,
+    Hsla, Lcha, LinearRgba, LuminanceOps, Mix, SRgba, WithAlpha,
 };
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
@@ -75,6 +76,8 @@ impl Mix for Oklaba {
     }
 }
 
+impl_componentwise_ops!(Oklaba, [l, a, b, alpha]);
+
 impl WithAlpha for Oklaba {
     #[inline]
     fn with_alpha(&self, alpha: f32) -> Self {
@@ -137,6 +140,18 @@ impl From<SRgba> for Oklaba {
     }
 }
 
+impl From<Hsla> for Oklaba {
+    fn from(value: Hsla) -> Self {
+        Oklaba::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Lcha> for Oklaba {
+    fn from(value: Lcha) -> Self {
+        Oklaba::from(LinearRgba::from(value))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,6 +204,21 @@ mod tests {
         assert_approx_eq!(oklaba.alpha, oklaba2.alpha, 0.001);
     }
 
+    #[test]
+    fn arithmetic() {
+        let a = Oklaba::new(0.5, 0.1, -0.2, 1.0);
+        let b = Oklaba::new(0.25, -0.1, 0.1, 0.5);
+        assert_eq!(a + b, Oklaba::new(0.75, 0.0, -0.1, 1.5));
+        assert_eq!(a - b, Oklaba::new(0.25, 0.2, -0.3, 0.5));
+        assert_eq!(a * 2.0, Oklaba::new(1.0, 0.2, -0.4, 2.0));
+        assert_eq!(a / 2.0, Oklaba::new(0.25, 0.05, -0.1, 0.5));
+        assert_eq!(-b, Oklaba::new(-0.25, 0.1, -0.1, -0.5));
+
+        // The average of two colors is the same as mixing them halfway.
+        let avg = [a, b].into_iter().sum::<Oklaba>() / 2.0;
+        assert!(avg.distance(&a.mix(&b, 0.5)) < 0.0001);
+    }
+
     #[test]
     fn to_css_string() {
         assert_eq!(
@@ -0,0 +1,346 @@
+//! Physical units for authoring colors and lights.
+//!
+//! [`LinearRgba`] is relative, with no unit attached to its values. The helpers here connect
+//! it to photometric quantities: camera [`Exposure`], luminance in nits relative to a paper
+//! white, and lights specified by color temperature and intensity in lumens, lux or candela.
+
+use crate::{LinearRgba, LuminanceOps, Xyza};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Camera exposure as an exposure value at ISO 100, in the same convention as Bevy's
+/// physically based camera exposure.
+///
+/// A higher EV100 is a darker exposure, for brighter scenes. Scene luminance is scaled by
+/// `1 / (1.2 * 2^ev100)`, so that a luminance of `1.2 * 2^ev100` nits saturates the sensor
+/// to 1.0. The factor of 1.2 comes from the saturation-based ISO sensitivity with a lens
+/// attenuation of 0.65.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Exposure {
+    pub ev100: f32,
+}
+
+impl Exposure {
+    /// Exposure for a scene in direct sunlight.
+    pub const SUNLIGHT: Self = Self::new(15.0);
+    /// Exposure for an overcast day.
+    pub const OVERCAST: Self = Self::new(12.0);
+    /// Exposure for a typical indoor scene.
+    pub const INDOOR: Self = Self::new(7.0);
+    /// The exposure which matches Blender's default, and Bevy's default.
+    pub const BLENDER: Self = Self::new(9.7);
+
+    /// Construct a new [`Exposure`] from an exposure value at ISO 100.
+    pub const fn new(ev100: f32) -> Self {
+        Self { ev100 }
+    }
+
+    /// Construct an [`Exposure`] from physical camera settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `aperture_f_stops` - The f-number of the lens aperture, such as 1.4 or 16.
+    /// * `shutter_speed_s` - The shutter speed in seconds, such as 1/125.
+    /// * `sensitivity_iso` - The sensor sensitivity, such as 100 or 800.
+    pub fn from_camera(aperture_f_stops: f32, shutter_speed_s: f32, sensitivity_iso: f32) -> Self {
+        Self::new(
+            (aperture_f_stops * aperture_f_stops * 100.0 / (shutter_speed_s * sensitivity_iso))
+                .log2(),
+        )
+    }
+
+    /// The factor which scene luminance in nits is multiplied by.
+    pub fn scale(&self) -> f32 {
+        (-self.ev100).exp2() / 1.2
+    }
+
+    /// The scene luminance in nits which is exposed to 1.0.
+    pub fn max_luminance(&self) -> f32 {
+        1.2 * self.ev100.exp2()
+    }
+
+    /// Scale scene radiance, where 1.0 is one nit, into exposed values. Alpha is not changed.
+    pub fn apply(&self, color: LinearRgba) -> LinearRgba {
+        let scale = self.scale();
+        LinearRgba::new(
+            color.red * scale,
+            color.green * scale,
+            color.blue * scale,
+            color.alpha,
+        )
+    }
+}
+
+impl Default for Exposure {
+    fn default() -> Self {
+        Self::BLENDER
+    }
+}
+
+/// Convert a luminance in nits into a normalized value, where 1.0 is `paper_white` nits.
+#[inline]
+pub fn nits_to_normalized(nits: f32, paper_white: f32) -> f32 {
+    nits / paper_white
+}
+
+/// Convert a normalized value, where 1.0 is `paper_white` nits, into a luminance in nits.
+#[inline]
+pub fn normalized_to_nits(value: f32, paper_white: f32) -> f32 {
+    value * paper_white
+}
+
+/// The luminance in nits of a color, when 1.0 is displayed at `paper_white` nits.
+#[inline]
+pub fn luminance_nits(color: LinearRgba, paper_white: f32) -> f32 {
+    normalized_to_nits(color.luminance(), paper_white)
+}
+
+/// The CIE 1931 xy chromaticity of a black body at a temperature in Kelvin, using the cubic
+/// spline approximation of the Planckian locus by Kim et al. The temperature is clamped to
+/// the valid range of 1667 K to 25000 K.
+#[allow(clippy::excessive_precision)]
+pub(crate) fn planckian_xy(kelvin: f32) -> [f32; 2] {
+    let t = kelvin.clamp(1667.0, 25000.0) as f64;
+    let (t2, t3) = (t * t, t * t * t);
+    let x = if t <= 4000.0 {
+        -0.2661239e9 / t3 - 0.2343589e6 / t2 + 0.8776956e3 / t + 0.179910
+    } else {
+        -3.0258469e9 / t3 + 2.1070379e6 / t2 + 0.2226347e3 / t + 0.240390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+    } else if t <= 4000.0 {
+        -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+    } else {
+        3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483
+    };
+    [x as f32, y as f32]
+}
+
+/// The color of a black body at a temperature in Kelvin, as linear sRGB scaled so that its
+/// largest channel is 1.0. Channels outside the sRGB gamut, which happens below about
+/// 1900 K, are clamped to zero. The temperature is clamped to 1667 K to 25000 K.
+pub fn kelvin_to_linear_rgba(kelvin: f32) -> LinearRgba {
+    let [x, y] = planckian_xy(kelvin);
+    let LinearRgba {
+        red, green, blue, ..
+    } = LinearRgba::from(Xyza::new(x / y, 1.0, (1.0 - x - y) / y, 1.0));
+    let [red, green, blue] = [red, green, blue].map(|c| c.max(0.0));
+    let max = red.max(green).max(blue);
+    LinearRgba::new(red / max, green / max, blue / max, 1.0)
+}
+
+/// The intensity of a light, in one of the photometric units which Bevy's lights use.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum LightIntensity {
+    /// Luminous flux, for point and spot lights. Like Bevy, the flux is spread evenly over
+    /// the whole sphere, so it is converted to candela by dividing by 4π.
+    Lumens(f32),
+    /// Illuminance, for directional lights. Bevy scales it by the exposure of a fixed camera
+    /// (f/4, 1/250 s, ISO 100) before it reaches the shader.
+    Lux(f32),
+    /// Luminous intensity.
+    Candela(f32),
+}
+
+impl LightIntensity {
+    /// The intensity as the factor which a light's color is multiplied by in Bevy's shaders:
+    /// candela for point and spot lights, or exposed lux for directional lights.
+    pub fn shader_intensity(&self) -> f32 {
+        match *self {
+            LightIntensity::Lumens(lumens) => lumens / (4.0 * PI),
+            LightIntensity::Lux(lux) => {
+                lux * Exposure::from_camera(4.0, 1.0 / 250.0, 100.0).scale()
+            }
+            LightIntensity::Candela(candela) => candela,
+        }
+    }
+}
+
+impl Default for LightIntensity {
+    fn default() -> Self {
+        Self::Lumens(800.0)
+    }
+}
+
+/// The color of a light, as a chromaticity and a photometric intensity.
+///
+/// Like Bevy, the intensity scales the linear color as it is, so a saturated light is dimmer
+/// than a white light of the same intensity. To make the intensity the true photometric
+/// quantity whatever the hue, construct the light from its [`LightColor::chromaticity`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct LightColor {
+    pub color: LinearRgba,
+    pub intensity: LightIntensity,
+}
+
+impl LightColor {
+    /// Construct a new [`LightColor`] from a color and an intensity.
+    pub fn new(color: impl Into<LinearRgba>, intensity: LightIntensity) -> Self {
+        Self {
+            color: color.into(),
+            intensity,
+        }
+    }
+
+    /// Construct a new [`LightColor`] from the black body color at a temperature in Kelvin,
+    /// such as 2700 for a warm incandescent bulb or 6500 for daylight.
+    pub fn from_kelvin(kelvin: f32, intensity: LightIntensity) -> Self {
+        Self::new(kelvin_to_linear_rgba(kelvin), intensity)
+    }
+
+    /// The color normalized to a luminance of 1.0, with an alpha of 1.0. A black color stays
+    /// black.
+    pub fn chromaticity(&self) -> LinearRgba {
+        let luminance = self.color.luminance();
+        let scale = if luminance > 0.0 {
+            1.0 / luminance
+        } else {
+            0.0
+        };
+        LinearRgba::new(
+            self.color.red * scale,
+            self.color.green * scale,
+            self.color.blue * scale,
+            1.0,
+        )
+    }
+
+    /// The linear color multiplied by the shader intensity, which is the light color that
+    /// Bevy's shaders compute from a light's `color` and intensity. Alpha is 1.0.
+    pub fn premultiplied(&self) -> LinearRgba {
+        let intensity = self.intensity.shader_intensity();
+        LinearRgba::new(
+            self.color.red * intensity,
+            self.color.green * intensity,
+            self.color.blue * intensity,
+            1.0,
+        )
+    }
+}
+
+impl From<LightColor> for LinearRgba {
+    fn from(value: LightColor) -> Self {
+        value.premultiplied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::assert_approx_eq, SRgba};
+    use bevy::render::color::Color;
+
+    #[test]
+    fn exposure() {
+        // The "sunny 16" rule: f/16 at 1/100 s and ISO 100 is about EV 15.
+        let sunny = Exposure::from_camera(16.0, 1.0 / 100.0, 100.0);
+        assert_approx_eq!(sunny.ev100, 14.644, 0.001);
+        assert_approx_eq!(Exposure::from_camera(1.0, 1.0, 100.0).ev100, 0.0, 0.0001);
+        // Doubling the ISO gains one stop.
+        assert_approx_eq!(
+            Exposure::from_camera(2.8, 1.0 / 60.0, 200.0).ev100,
+            Exposure::from_camera(2.8, 1.0 / 60.0, 100.0).ev100 - 1.0,
+            0.0001
+        );
+
+        let exposure = Exposure::new(0.0);
+        assert_approx_eq!(exposure.scale(), 1.0 / 1.2, 0.0001);
+        let max = Exposure::INDOOR.max_luminance();
+        assert_approx_eq!(max, 153.6, 0.001);
+        let exposed = Exposure::INDOOR.apply(LinearRgba::new(max, max * 0.5, 0.0, 0.5));
+        assert_approx_eq!(exposed.red, 1.0, 0.0001);
+        assert_approx_eq!(exposed.green, 0.5, 0.0001);
+        assert_approx_eq!(exposed.blue, 0.0, 0.0001);
+        assert_eq!(exposed.alpha, 0.5);
+        assert_eq!(Exposure::default(), Exposure::BLENDER);
+    }
+
+    #[test]
+    fn nits() {
+        assert_approx_eq!(nits_to_normalized(406.0, 203.0), 2.0, 0.0001);
+        assert_approx_eq!(normalized_to_nits(0.5, 80.0), 40.0, 0.0001);
+        for value in [0.0, 0.18, 1.0, 12.5] {
+            assert_approx_eq!(
+                nits_to_normalized(normalized_to_nits(value, 203.0), 203.0),
+                value,
+                0.0001
+            );
+        }
+        let white = LinearRgba::from(SRgba::WHITE);
+        assert_approx_eq!(luminance_nits(white, 203.0), 203.0, 0.01);
+    }
+
+    #[test]
+    fn kelvin() {
+        // D65 is close to the black body at 6504 K, but slightly greener.
+        let daylight = kelvin_to_linear_rgba(6504.0);
+        assert_approx_eq!(daylight.red, 1.0, 0.01);
+        assert_approx_eq!(daylight.green, 0.94, 0.01);
+        assert_approx_eq!(daylight.blue, 1.0, 0.05);
+
+        let [x, y] = planckian_xy(2856.0);
+        // CIE illuminant A.
+        assert_approx_eq!(x, 0.4476, 0.001);
+        assert_approx_eq!(y, 0.4074, 0.001);
+
+        let warm = kelvin_to_linear_rgba(2700.0);
+        assert_eq!(warm.red, 1.0);
+        assert!(warm.green < warm.red && warm.blue < warm.green);
+        let cool = kelvin_to_linear_rgba(12000.0);
+        assert_eq!(cool.blue, 1.0);
+        assert!(cool.red < cool.green);
+        assert_eq!(kelvin_to_linear_rgba(100.0), kelvin_to_linear_rgba(1667.0));
+    }
+
+    #[test]
+    fn light_color() {
+        let white = LightColor::new(SRgba::WHITE, LightIntensity::Lumens(800.0));
+        let premultiplied = LinearRgba::from(white);
+        let candela = 800.0 / (4.0 * PI);
+        assert_approx_eq!(premultiplied.red, candela, 0.001);
+        assert_approx_eq!(premultiplied.green, candela, 0.001);
+        assert_approx_eq!(premultiplied.blue, candela, 0.001);
+        assert_eq!(premultiplied.alpha, 1.0);
+
+        // The color is not normalized, so a red light is dimmer than a white one, unless it
+        // is constructed from its chromaticity.
+        let red = LightColor::new(SRgba::RED, LightIntensity::Candela(1000.0));
+        assert_approx_eq!(red.premultiplied().red, 1000.0, 0.01);
+        assert_approx_eq!(red.premultiplied().luminance(), 212.6, 0.01);
+        let red = LightColor::new(red.chromaticity(), red.intensity);
+        assert_approx_eq!(red.premultiplied().luminance(), 1000.0, 0.01);
+        assert_approx_eq!(red.premultiplied().green, 0.0, 0.0001);
+
+        let sun = LightColor::from_kelvin(5778.0, LightIntensity::Candela(100000.0));
+        assert_approx_eq!(sun.chromaticity().luminance(), 1.0, 0.0001);
+
+        let black = LightColor::new(LinearRgba::new(0.0, 0.0, 0.0, 1.0), Default::default());
+        assert_eq!(black.premultiplied(), LinearRgba::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(LightIntensity::Candela(5.0).shader_intensity(), 5.0);
+    }
+
+    #[test]
+    fn matches_bevy() {
+        // The light colors which `bevy_pbr` uploads for point and directional lights.
+        let color = Color::rgb(0.9, 0.2, 0.4);
+        let [red, green, blue, _] = color.as_linear_rgba_f32();
+        let point = LightColor::new(color, LightIntensity::Lumens(800.0)).premultiplied();
+        let intensity = 800.0 / (4.0 * PI);
+        assert_approx_eq!(point.red, red * intensity, 0.0001);
+        assert_approx_eq!(point.green, green * intensity, 0.0001);
+        assert_approx_eq!(point.blue, blue * intensity, 0.0001);
+
+        let directional = LightColor::new(color, LightIntensity::Lux(100000.0)).premultiplied();
+        let ev100 = (4.0f32 * 4.0 / (1.0 / 250.0)).log2() - (100.0f32 / 100.0).log2();
+        let illuminance = 100000.0 / (2.0f32.powf(ev100) * 1.2);
+        assert_approx_eq!(directional.red, red * illuminance, 0.0001);
+        assert_approx_eq!(directional.green, green * illuminance, 0.0001);
+        assert_approx_eq!(directional.blue, blue * illuminance, 0.0001);
+    }
+}
@@ -0,0 +1,440 @@
+use crate::{AnyColorRange, InterpolationSpace, LinearRgba, Oklaba};
+
+/// The kind of curve used by a [`ColorSpline`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplineKind {
+    /// A uniform Catmull-Rom spline, which passes through every control color.
+    #[default]
+    CatmullRom,
+    /// A uniform cubic B-spline, which passes through the first and last colors and is
+    /// pulled towards the others. It is smoother than Catmull-Rom and never overshoots.
+    BSpline,
+    /// A single Bézier curve whose control points are the colors, which passes through the
+    /// first and last colors. This is the curve used by chroma.js's `bezier()`.
+    Bezier,
+}
+
+/// A smooth color ramp through any number of colors, without the visible kinks that a
+/// piecewise-linear [`crate::Gradient`] has at its stops.
+///
+/// The spline is evaluated in the components of an [`InterpolationSpace`]. In polar spaces
+/// the hues are unwrapped so that every step between neighboring colors takes the shorter
+/// way around the hue circle, and achromatic colors borrow the hue of a neighbor.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{ColorSpline, SplineKind, SRgba};
+/// let ramp = ColorSpline::new(SplineKind::Bezier, [SRgba::WHITE, SRgba::RED, SRgba::BLACK])
+///     .with_lightness_correction(true);
+/// let color: SRgba = ramp.at(0.5).into();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorSpline {
+    colors: Vec<LinearRgba>,
+    kind: SplineKind,
+    space: InterpolationSpace,
+    correct_lightness: bool,
+    /// The control colors converted into the components of `space`, with unwrapped hues.
+    points: Vec<[f32; 4]>,
+    /// Oklab lightness of the uncorrected spline, sampled evenly from 0.0 to 1.0 and made
+    /// monotonic. Empty unless lightness correction is enabled.
+    lightness: Vec<f32>,
+}
+
+/// The number of samples used to invert the lightness of a spline.
+const LIGHTNESS_SAMPLES: usize = 128;
+
+impl ColorSpline {
+    /// Construct a spline of the given kind through a list of colors, interpolating in
+    /// Oklab.
+    pub fn new<C: Into<LinearRgba>>(kind: SplineKind, colors: impl IntoIterator<Item = C>) -> Self {
+        let mut spline = Self {
+            colors: colors.into_iter().map(Into::into).collect(),
+            kind,
+            space: InterpolationSpace::Oklaba,
+            correct_lightness: false,
+            points: Vec::new(),
+            lightness: Vec::new(),
+        };
+        spline.update();
+        spline
+    }
+
+    /// Return a copy of this spline which is evaluated in the given color space.
+    pub fn with_space(&self, space: InterpolationSpace) -> Self {
+        let mut spline = Self {
+            space,
+            ..self.clone()
+        };
+        spline.update();
+        spline
+    }
+
+    /// Return a copy of this spline with or without lightness correction, like chroma.js's
+    /// `correctLightness()`.
+    ///
+    /// When enabled, the spline is reparameterized so that its Oklab lightness changes
+    /// evenly from the first color to the last. The lightness of the result is then set to
+    /// exactly the target value, which only makes a visible difference where the spline's
+    /// own lightness is not monotonic. This is recommended for data visualization.
+    pub fn with_lightness_correction(&self, correct_lightness: bool) -> Self {
+        let mut spline = Self {
+            correct_lightness,
+            ..self.clone()
+        };
+        spline.update();
+        spline
+    }
+
+    /// The control colors of this spline.
+    pub fn colors(&self) -> &[LinearRgba] {
+        &self.colors
+    }
+
+    /// The kind of curve.
+    pub fn kind(&self) -> SplineKind {
+        self.kind
+    }
+
+    /// The color space used for interpolation.
+    pub fn space(&self) -> InterpolationSpace {
+        self.space
+    }
+
+    /// Whether lightness correction is enabled.
+    pub fn lightness_correction(&self) -> bool {
+        self.correct_lightness
+    }
+
+    /// Get the color at the given factor, which is clamped to the range 0.0 to 1.0. An
+    /// empty spline is transparent.
+    pub fn at(&self, factor: f32) -> LinearRgba {
+        let factor = factor.clamp(0., 1.);
+        if self.lightness.is_empty() {
+            return self.evaluate(factor);
+        }
+
+        let first = self.lightness[0];
+        let last = self.lightness[LIGHTNESS_SAMPLES - 1];
+        let target = first + (last - first) * factor;
+        let color = self.evaluate(self.invert_lightness(target));
+        let mut oklab = Oklaba::from(color);
+        oklab.l = target;
+        oklab.into()
+    }
+
+    /// Recompute the cached control points and lightness table.
+    fn update(&mut self) {
+        let space = self.space;
+        self.points = self
+            .colors
+            .iter()
+            .map(|color| space.to_components(*color))
+            .collect();
+        if let Some(hue) = space.hue_index() {
+            unwrap_hues(&mut self.points, hue);
+        }
+
+        self.lightness.clear();
+        if !self.correct_lightness || self.points.len() < 2 {
+            return;
+        }
+        let samples: Vec<f32> = (0..LIGHTNESS_SAMPLES)
+            .map(|i| {
+                let factor = i as f32 / (LIGHTNESS_SAMPLES - 1) as f32;
+                Oklaba::from(self.evaluate(factor)).l
+            })
+            .collect();
+        let increasing = samples[LIGHTNESS_SAMPLES - 1] >= samples[0];
+        let mut extreme = samples[0];
+        self.lightness = samples
+            .into_iter()
+            .map(|l| {
+                extreme = if increasing {
+                    extreme.max(l)
+                } else {
+                    extreme.min(l)
+                };
+                extreme
+            })
+            .collect();
+        if (self.lightness[LIGHTNESS_SAMPLES - 1] - self.lightness[0]).abs() < 1e-4 {
+            // A ramp with no change in lightness can't be corrected.
+            self.lightness.clear();
+        }
+    }
+
+    /// Find the factor at which the monotonic lightness table reaches `target`.
+    fn invert_lightness(&self, target: f32) -> f32 {
+        let increasing = self.lightness[LIGHTNESS_SAMPLES - 1] > self.lightness[0];
+        let index = self
+            .lightness
+            .partition_point(|&l| if increasing { l < target } else { l > target })
+            .clamp(1, LIGHTNESS_SAMPLES - 1);
+        let (l0, l1) = (self.lightness[index - 1], self.lightness[index]);
+        let within = if (l1 - l0).abs() > f32::EPSILON {
+            ((target - l0) / (l1 - l0)).clamp(0., 1.)
+        } else {
+            0.
+        };
+        (index as f32 - 1. + within) / (LIGHTNESS_SAMPLES - 1) as f32
+    }
+
+    /// Evaluate the spline without lightness correction.
+    fn evaluate(&self, factor: f32) -> LinearRgba {
+        let mut components = match (self.points.as_slice(), self.kind) {
+            ([], _) => return LinearRgba::new(0., 0., 0., 0.),
+            ([point], _) => *point,
+            (points, SplineKind::CatmullRom) => {
+                let (i, t) = segment(factor, points.len() - 1);
+                // Extrapolate a phantom point past each end, so the end segments are not
+                // pulled towards the end points.
+                let point = |j: isize| {
+                    let last = points.len() as isize - 1;
+                    if j < 0 {
+                        lerp_components(&points[1], &points[0], 2.)
+                    } else if j > last {
+                        lerp_components(&points[last as usize - 1], &points[last as usize], 2.)
+                    } else {
+                        points[j as usize]
+                    }
+                };
+                let i = i as isize;
+                catmull_rom(point(i - 1), point(i), point(i + 1), point(i + 2), t)
+            }
+            (points, SplineKind::BSpline) => {
+                // Triple the end points, so that the curve starts and ends on them.
+                let last = points.len() - 1;
+                let (i, t) = segment(factor, points.len() + 1);
+                let point = |j: usize| points[j.saturating_sub(2).min(last)];
+                b_spline(point(i), point(i + 1), point(i + 2), point(i + 3), t)
+            }
+            (points, SplineKind::Bezier) => {
+                let mut points = points.to_vec();
+                for level in (1..points.len()).rev() {
+                    for i in 0..level {
+                        points[i] = lerp_components(&points[i], &points[i + 1], factor);
+                    }
+                }
+                points[0]
+            }
+        };
+        components[3] = components[3].clamp(0., 1.);
+        if let Some(hue) = self.space.hue_index() {
+            components[hue] = components[hue].rem_euclid(360.);
+        }
+        self.space.to_color(components)
+    }
+}
+
+impl AnyColorRange for ColorSpline {
+    fn at_linear(&self, factor: f32) -> LinearRgba {
+        self.at(factor)
+    }
+}
+
+/// Unwrap the hues of a sequence of polar colors, so that each hue is within 180° of the
+/// previous one. Achromatic colors take the hue of the nearest chromatic neighbor.
+fn unwrap_hues(points: &mut [[f32; 4]], hue: usize) {
+    // In both polar spaces, chroma or saturation is the second component.
+    let chromatic = |point: &[f32; 4]| point[1].abs() >= 1e-4;
+    let Some(first) = points.iter().position(chromatic) else {
+        return;
+    };
+    let mut previous = points[first][hue];
+    for point in points.iter_mut() {
+        if chromatic(point) {
+            let delta = (point[hue] - previous + 180.).rem_euclid(360.) - 180.;
+            point[hue] = previous + delta;
+        } else {
+            point[hue] = previous;
+        }
+        previous = point[hue];
+    }
+}
+
+/// Split a factor from 0.0 to 1.0 into a segment index and the factor within that segment.
+fn segment(factor: f32, segments: usize) -> (usize, f32) {
+    let scaled = factor * segments as f32;
+    let index = (scaled.floor() as usize).min(segments - 1);
+    (index, scaled - index as f32)
+}
+
+fn lerp_components(a: &[f32; 4], b: &[f32; 4], factor: f32) -> [f32; 4] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * factor)
+}
+
+fn catmull_rom(p0: [f32; 4], p1: [f32; 4], p2: [f32; 4], p3: [f32; 4], t: f32) -> [f32; 4] {
+    let (t2, t3) = (t * t, t * t * t);
+    std::array::from_fn(|i| {
+        0.5 * (2. * p1[i]
+            + (p2[i] - p0[i]) * t
+            + (2. * p0[i] - 5. * p1[i] + 4. * p2[i] - p3[i]) * t2
+            + (3. * p1[i] - p0[i] - 3. * p2[i] + p3[i]) * t3)
+    })
+}
+
+fn b_spline(p0: [f32; 4], p1: [f32; 4], p2: [f32; 4], p3: [f32; 4], t: f32) -> [f32; 4] {
+    let n = 1. - t;
+    let w0 = n * n * n / 6.;
+    let w1 = (3. * t * t * t - 6. * t * t + 4.) / 6.;
+    let w2 = (-3. * t * t * t + 3. * t * t + 3. * t + 1.) / 6.;
+    let w3 = t * t * t / 6.;
+    std::array::from_fn(|i| w0 * p0[i] + w1 * p1[i] + w2 * p2[i] + w3 * p3[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color_difference::EuclideanDistance, testing::assert_approx_eq, Lcha, SRgba};
+
+    fn assert_close(a: LinearRgba, b: impl Into<LinearRgba>) {
+        let b = b.into();
+        assert!(a.distance(&b) < 0.001, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn end_points() {
+        let colors = [SRgba::RED, SRgba::GREEN, SRgba::BLUE, SRgba::WHITE];
+        for kind in [
+            SplineKind::CatmullRom,
+            SplineKind::BSpline,
+            SplineKind::Bezier,
+        ] {
+            let spline = ColorSpline::new(kind, colors);
+            assert_close(spline.at(0.0), SRgba::RED);
+            assert_close(spline.at(1.0), SRgba::WHITE);
+            assert_close(spline.at(-1.0), SRgba::RED);
+        }
+
+        assert_eq!(
+            ColorSpline::new(SplineKind::Bezier, Vec::<SRgba>::new()).at(0.5),
+            LinearRgba::new(0., 0., 0., 0.)
+        );
+        assert_close(
+            ColorSpline::new(SplineKind::BSpline, [SRgba::RED]).at(0.5),
+            SRgba::RED,
+        );
+    }
+
+    #[test]
+    fn catmull_rom_interpolates() {
+        let colors = [SRgba::RED, SRgba::GREEN, SRgba::BLUE, SRgba::WHITE];
+        let spline = ColorSpline::new(SplineKind::CatmullRom, colors);
+        for (i, color) in colors.into_iter().enumerate() {
+            assert_close(spline.at(i as f32 / 3.), color);
+        }
+
+        // Two colors give a straight line.
+        let spline = ColorSpline::new(SplineKind::CatmullRom, [SRgba::BLACK, SRgba::WHITE]);
+        let mid = Oklaba::from(spline.at(0.5));
+        assert_approx_eq!(mid.l, 0.5, 0.001);
+    }
+
+    #[test]
+    fn bezier() {
+        let spline = ColorSpline::new(
+            SplineKind::Bezier,
+            [
+                Oklaba::new(0.2, 0.0, 0.0, 1.0),
+                Oklaba::new(0.8, 0.1, 0.0, 1.0),
+                Oklaba::new(0.4, 0.0, 0.0, 1.0),
+            ],
+        );
+        let mid = Oklaba::from(spline.at(0.5));
+        assert_approx_eq!(mid.l, 0.55, 0.001);
+        assert_approx_eq!(mid.a, 0.05, 0.001);
+    }
+
+    #[test]
+    fn b_spline_is_smooth() {
+        let spline = ColorSpline::new(
+            SplineKind::BSpline,
+            [SRgba::BLACK, SRgba::WHITE, SRgba::BLACK, SRgba::WHITE],
+        );
+        // The curve doesn't pass through the interior points, and has no sharp corners.
+        let l = |t: f32| Oklaba::from(spline.at(t)).l;
+        assert!(l(1. / 3.) < 0.9);
+        for i in 1..99 {
+            let t = i as f32 / 100.;
+            let curvature = l(t - 0.01) - 2. * l(t) + l(t + 0.01);
+            assert!(curvature.abs() < 0.01, "{} {}", t, curvature);
+        }
+    }
+
+    #[test]
+    fn hue_wrapping() {
+        let oklch = |l: f32, c: f32, h: f32| {
+            let (sin, cos) = h.to_radians().sin_cos();
+            Oklaba::new(l, c * cos, c * sin, 1.0)
+        };
+        let hue = |color: LinearRgba| InterpolationSpace::Oklcha.to_components(color)[2];
+        let spline = ColorSpline::new(
+            SplineKind::CatmullRom,
+            [
+                oklch(0.6, 0.1, 340.0),
+                oklch(0.6, 0.1, 20.0),
+                oklch(0.6, 0.0, 0.0),
+            ],
+        )
+        .with_space(InterpolationSpace::Oklcha);
+        let h = hue(spline.at(0.25));
+        assert!(!(30.0..330.0).contains(&h), "{}", h);
+
+        // The achromatic end takes the hue of its neighbor rather than 0°.
+        let h = hue(spline.at(0.75));
+        assert!(h > 10.0 && h < 30.0, "{}", h);
+    }
+
+    #[test]
+    fn lightness_correction() {
+        let spline = ColorSpline::new(
+            SplineKind::Bezier,
+            [SRgba::new(1.0, 1.0, 0.0, 1.0), SRgba::RED, SRgba::BLACK],
+        );
+        let corrected = spline.with_lightness_correction(true);
+        assert!(corrected.lightness_correction());
+        assert_close(corrected.at(0.0), spline.at(0.0));
+        assert_close(corrected.at(1.0), spline.at(1.0));
+
+        let first = Oklaba::from(spline.at(0.0)).l;
+        let last = Oklaba::from(spline.at(1.0)).l;
+        for i in 0..=10 {
+            let t = i as f32 / 10.;
+            let l = Oklaba::from(corrected.at(t)).l;
+            assert_approx_eq!(l, first + (last - first) * t, 0.002);
+        }
+
+        // A spline whose lightness goes up and back down is still made monotonic.
+        let spline = ColorSpline::new(
+            SplineKind::CatmullRom,
+            [SRgba::BLACK, SRgba::WHITE, SRgba::new(0.5, 0.5, 0.5, 1.0)],
+        )
+        .with_lightness_correction(true);
+        let mut previous = 0.;
+        for i in 0..=20 {
+            let l = Oklaba::from(spline.at(i as f32 / 20.)).l;
+            assert!(l >= previous - 0.001);
+            previous = l;
+        }
+    }
+
+    #[test]
+    fn lab() {
+        let space = InterpolationSpace::Laba;
+        assert!(!space.is_polar());
+        let red = Lcha::from(SRgba::RED);
+        let components = space.to_components(SRgba::RED.into());
+        assert_approx_eq!(components[0], red.lightness, 0.0001);
+        assert_approx_eq!(components[1].hypot(components[2]), red.chroma, 0.0001);
+        assert_close(space.to_color(components), SRgba::RED);
+
+        // Two colors give a straight line in Lab, so the midpoint of black and white has
+        // half of their lightness.
+        let spline = ColorSpline::new(SplineKind::CatmullRom, [SRgba::BLACK, SRgba::WHITE])
+            .with_space(space);
+        assert_approx_eq!(Lcha::from(spline.at(0.5)).lightness, 0.5, 0.001);
+        assert_approx_eq!(Lcha::from(spline.at(0.5)).chroma, 0., 0.001);
+    }
+}
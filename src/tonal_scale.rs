@@ -0,0 +1,229 @@
+use crate::{
+    gamut::{chroma_hue, max_srgb_chroma, oklch},
+    Oklaba, SRgba,
+};
+
+/// A named stop of a [`TonalScale`], with its target Oklab lightness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TonalStop {
+    /// The name of this stop, such as 500.
+    pub name: u16,
+    /// The target Oklab lightness, between 0.0 and 1.0.
+    pub lightness: f32,
+}
+
+impl TonalStop {
+    /// Construct a new stop.
+    pub const fn new(name: u16, lightness: f32) -> Self {
+        Self { name, lightness }
+    }
+}
+
+/// Stops named like the Tailwind CSS palette, from 50 (lightest) to 950 (darkest), with
+/// lightness targets close to those of the Tailwind colors.
+pub const TAILWIND_STOPS: [TonalStop; 11] = [
+    TonalStop::new(50, 0.975),
+    TonalStop::new(100, 0.945),
+    TonalStop::new(200, 0.89),
+    TonalStop::new(300, 0.82),
+    TonalStop::new(400, 0.72),
+    TonalStop::new(500, 0.635),
+    TonalStop::new(600, 0.555),
+    TonalStop::new(700, 0.49),
+    TonalStop::new(800, 0.425),
+    TonalStop::new(900, 0.37),
+    TonalStop::new(950, 0.28),
+];
+
+/// A ramp of tints and shades of a seed color, with one color for each named
+/// [`TonalStop`]. Create one with [`tonal_scale`].
+///
+/// The ramp is built in Oklch. Every color has exactly the lightness of its stop and the hue
+/// of the seed, unless hue drift is enabled. Chroma tapers off towards black and white, and
+/// is further reduced where needed so that every color is inside the sRGB gamut.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TonalScale {
+    seed: Oklaba,
+    stops: Vec<TonalStop>,
+    hue_drift: f32,
+    colors: Vec<SRgba>,
+}
+
+/// Build a [`TonalScale`] from a seed color, for the given stops, such as
+/// [`TAILWIND_STOPS`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_color::{tonal_scale, SRgba, TAILWIND_STOPS};
+/// let blue = tonal_scale(SRgba::hex("#3b82f6").unwrap(), &TAILWIND_STOPS);
+/// let background = blue.get(50).unwrap();
+/// let text = blue.get(900).unwrap();
+/// ```
+pub fn tonal_scale(seed: impl Into<Oklaba>, stops: &[TonalStop]) -> TonalScale {
+    let mut scale = TonalScale {
+        seed: seed.into(),
+        stops: stops.to_vec(),
+        hue_drift: 0.,
+        colors: Vec::new(),
+    };
+    scale.update();
+    scale
+}
+
+impl TonalScale {
+    /// Return this scale with hue drift, which rotates the hue of darker stops by up to
+    /// `degrees` and the hue of lighter stops by up to `-degrees`, in proportion to how far
+    /// their lightness is from the seed's towards black or white. A drift of 10° to 20°
+    /// imitates the way hand-tuned palettes warm or cool towards the extremes.
+    pub fn with_hue_drift(mut self, degrees: f32) -> Self {
+        self.hue_drift = degrees;
+        self.update();
+        self
+    }
+
+    /// The seed color.
+    pub fn seed(&self) -> Oklaba {
+        self.seed
+    }
+
+    /// The stops of this scale, in their original order.
+    pub fn stops(&self) -> &[TonalStop] {
+        &self.stops
+    }
+
+    /// The hue drift in degrees.
+    pub fn hue_drift(&self) -> f32 {
+        self.hue_drift
+    }
+
+    /// The colors of this scale, in the same order as the stops.
+    pub fn colors(&self) -> &[SRgba] {
+        &self.colors
+    }
+
+    /// Get the color of the stop with the given name.
+    pub fn get(&self, name: u16) -> Option<SRgba> {
+        self.stops
+            .iter()
+            .position(|stop| stop.name == name)
+            .map(|index| self.colors[index])
+    }
+
+    /// Iterate over the names and colors of this scale.
+    pub fn iter(&self) -> impl Iterator<Item = (u16, SRgba)> + '_ {
+        self.stops
+            .iter()
+            .zip(&self.colors)
+            .map(|(stop, color)| (stop.name, *color))
+    }
+
+    fn update(&mut self) {
+        let (seed_chroma, seed_hue) = chroma_hue(self.seed);
+        let seed_l = self.seed.l.clamp(0.01, 0.99);
+        self.colors = self
+            .stops
+            .iter()
+            .map(|stop| {
+                let l = stop.lightness.clamp(0., 1.);
+                // How far this stop is from the seed towards black (positive) or white
+                // (negative), from -1.0 to 1.0.
+                let distance = if l < seed_l {
+                    (seed_l - l) / seed_l
+                } else {
+                    (seed_l - l) / (1. - seed_l)
+                };
+                let hue = seed_hue + self.hue_drift * distance;
+                // A parabola through black and white, normalized to 1.0 at the seed.
+                let taper = (l * (1. - l) / (seed_l * (1. - seed_l))).min(1.);
+                let chroma = max_srgb_chroma(l, hue, seed_chroma * taper);
+                let SRgba {
+                    red,
+                    green,
+                    blue,
+                    alpha: _,
+                } = oklch(l, chroma, hue, 1.).into();
+                SRgba::new(
+                    red.clamp(0., 1.),
+                    green.clamp(0., 1.),
+                    blue.clamp(0., 1.),
+                    self.seed.alpha,
+                )
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_approx_eq;
+
+    #[test]
+    fn tailwind_scale() {
+        let seed = SRgba::hex("#3b82f6").unwrap();
+        let scale = tonal_scale(seed, &TAILWIND_STOPS);
+        assert_eq!(scale.colors().len(), 11);
+        assert_eq!(
+            scale.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec![50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950]
+        );
+        assert!(scale.get(550).is_none());
+
+        let seed_hue = chroma_hue(seed.into()).1;
+        let mut chroma = Vec::new();
+        for (stop, color) in TAILWIND_STOPS.iter().zip(scale.colors()) {
+            for c in [color.red, color.green, color.blue] {
+                assert!((0.0..=1.0).contains(&c), "{:?}", color);
+            }
+            let oklab = Oklaba::from(*color);
+            assert_approx_eq!(oklab.l, stop.lightness, 0.005);
+            let (c, h) = chroma_hue(oklab);
+            assert_approx_eq!(h, seed_hue, 1.0);
+            chroma.push(c);
+        }
+
+        // Chroma is reduced towards the extremes.
+        let peak = chroma.iter().copied().fold(0., f32::max);
+        assert!(chroma[0] < peak * 0.3);
+        assert!(chroma[10] < peak);
+    }
+
+    #[test]
+    fn saturated_seed_stays_in_gamut() {
+        for seed in [SRgba::RED, SRgba::GREEN, SRgba::new(1.0, 1.0, 0.0, 1.0)] {
+            let scale = tonal_scale(seed, &TAILWIND_STOPS);
+            for color in scale.colors() {
+                let linear = color.to_linear();
+                for c in [linear.red, linear.green, linear.blue] {
+                    assert!((0.0..=1.0).contains(&c), "{:?}", color);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hue_drift() {
+        let seed = Oklaba::from(SRgba::hex("#16a34a").unwrap());
+        let (_, seed_hue) = chroma_hue(seed);
+        let scale = tonal_scale(seed, &TAILWIND_STOPS).with_hue_drift(20.);
+        assert_eq!(scale.hue_drift(), 20.);
+
+        let dark = chroma_hue(scale.get(900).unwrap().into()).1;
+        let light = chroma_hue(scale.get(100).unwrap().into()).1;
+        assert!(dark > seed_hue + 5.);
+        assert!(light < seed_hue - 5.);
+    }
+
+    #[test]
+    fn custom_stops() {
+        let scale = tonal_scale(
+            SRgba::new(0.5, 0.5, 0.5, 0.5),
+            &[TonalStop::new(1, 0.3), TonalStop::new(2, 0.8)],
+        );
+        let dark = Oklaba::from(scale.get(1).unwrap());
+        assert_approx_eq!(dark.l, 0.3, 0.001);
+        assert_approx_eq!(dark.a.hypot(dark.b), 0., 0.001);
+        assert_eq!(scale.get(2).unwrap().alpha, 0.5);
+    }
+}
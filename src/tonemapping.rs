@@ -0,0 +1,457 @@
+//! Tone mapping operators.
+//!
+//! A tone mapping operator compresses scene-referred [`LinearRgba`] values, which may be far
+//! brighter than 1.0, into the display-referred range. The operators here are CPU versions of
+//! the ones Bevy applies in its tone mapping pass, for baking, previews, or picking colors
+//! that look the same on screen as in a rendered scene. Like Bevy, every operator clamps
+//! negative input to zero first, and passes alpha through.
+
+use crate::{
+    math::{dot, matrix_multiply},
+    LinearRgba, Lut3d, LutInterpolation,
+};
+use bevy::render::texture::Image;
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Mapping of HDR colors to display-referred colors.
+pub trait Tonemap {
+    /// Tone map non-negative linear RGB values.
+    fn tonemap_rgb(&self, rgb: [f32; 3]) -> [f32; 3];
+
+    /// Tone map a linear color. Negative channels are clamped to zero, and alpha is passed
+    /// through.
+    fn tonemap(&self, color: LinearRgba) -> LinearRgba {
+        let [red, green, blue] =
+            self.tonemap_rgb([color.red, color.green, color.blue].map(|c| c.max(0.)));
+        LinearRgba::new(red, green, blue, color.alpha)
+    }
+
+    /// Tone map a slice of linear colors, writing into `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than `colors`.
+    fn tonemap_slice(&self, colors: &[LinearRgba], out: &mut [LinearRgba]) {
+        assert!(out.len() >= colors.len(), "output buffer is too small");
+        for (color, out) in colors.iter().zip(out.iter_mut()) {
+            *out = self.tonemap(*color);
+        }
+    }
+}
+
+/// The looks which can be applied at the end of [`Tonemapper::Agx`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Hash, Serialize, Deserialize)]
+pub enum AgxLook {
+    /// The neutral base image.
+    #[default]
+    Base,
+    /// More contrast and saturation.
+    Punchy,
+}
+
+/// The analytic tone mapping operators. For the LUT-based Tony McMapface, see
+/// [`TonyMcMapface`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub enum Tonemapper {
+    /// Reinhard on each RGB channel, `c / (1 + c)`. This oversaturates bright colors, and
+    /// never reaches white.
+    Reinhard,
+    /// Reinhard on each RGB channel, extended so that `max_white` maps to 1.0.
+    ReinhardExtended { max_white: f32 },
+    /// Jodie's Reinhard variant, which blends per-channel Reinhard with Reinhard on the
+    /// luminance, so that bright colors desaturate towards white.
+    ReinhardJodie,
+    /// Krzysztof Narkowicz's fit of the ACES filmic curve, applied to each channel. Unlike the
+    /// original, the input is not scaled by 0.6, so it is somewhat brighter.
+    AcesNarkowicz,
+    /// Stephen Hill's fit of the ACES RRT and sRGB ODT, with the color matrices into and out
+    /// of the ACES working space. Bevy calls this `AcesFitted`.
+    #[default]
+    AcesFitted,
+    /// Troy Sobotka's AgX, using the polynomial approximation of the default contrast curve
+    /// and the 2.2 display gamma. Bevy bakes Blender's AgX into a LUT instead, so results
+    /// are close to, but not identical with, Bevy's `AgX`.
+    Agx { look: AgxLook },
+    /// John Hable's filmic curve from Uncharted 2, with an exposure bias of 2 and a linear
+    /// white point of 11.2, so an input of 5.6 maps to white.
+    Uncharted2,
+    /// The Khronos PBR Neutral operator, which keeps base colors unchanged up to 0.76 and
+    /// only compresses and desaturates the highlights.
+    KhronosPbrNeutral,
+}
+
+/// Rec. 709 luminance weights, as used by Bevy's tone mapping.
+const LUMINANCE: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Linear sRGB to the ACES RRT working space with the RRT saturation applied, as rows.
+const ACES_INPUT: [[f32; 3]; 3] = [
+    [0.59719, 0.35458, 0.04823],
+    [0.07600, 0.90834, 0.01566],
+    [0.02840, 0.13383, 0.83777],
+];
+
+/// The ODT saturation and the ACES working space back to linear sRGB, as rows.
+const ACES_OUTPUT: [[f32; 3]; 3] = [
+    [1.60475, -0.53108, -0.07367],
+    [-0.10208, 1.10813, -0.00605],
+    [-0.00327, -0.07276, 1.07602],
+];
+
+/// Linear sRGB to the AgX inset working space, as rows.
+#[allow(clippy::excessive_precision)]
+const AGX_INSET: [[f32; 3]; 3] = [
+    [0.842479062253094, 0.0784335999999992, 0.0792237451477643],
+    [0.0423282422610123, 0.878468636469772, 0.0791661274605434],
+    [0.0423756549057051, 0.0784336, 0.879142973793104],
+];
+
+/// The AgX outset back to sRGB primaries, as rows.
+#[allow(clippy::excessive_precision)]
+const AGX_OUTSET: [[f32; 3]; 3] = [
+    [1.19687900512017, -0.0980208811401368, -0.0990297440797205],
+    [-0.0528968517574562, 1.15190312990417, -0.0989611768448433],
+    [-0.0529716355144438, -0.0980434501171241, 1.15107367264116],
+];
+
+const AGX_MIN_EV: f32 = -12.47393;
+const AGX_MAX_EV: f32 = 4.026069;
+
+fn reinhard_jodie(rgb: [f32; 3]) -> [f32; 3] {
+    let luminance = dot(rgb, LUMINANCE);
+    rgb.map(|c| {
+        let per_channel = c / (1. + c);
+        let by_luminance = c / (1. + luminance);
+        by_luminance + (per_channel - by_luminance) * per_channel
+    })
+}
+
+fn aces_fitted(rgb: [f32; 3]) -> [f32; 3] {
+    let fitted = matrix_multiply(&ACES_INPUT, rgb).map(|v| {
+        let a = v * (v + 0.0245786) - 0.000090537;
+        let b = v * (0.983729 * v + 0.432951) + 0.238081;
+        a / b
+    });
+    matrix_multiply(&ACES_OUTPUT, fitted).map(|c| c.clamp(0., 1.))
+}
+
+fn agx(rgb: [f32; 3], look: AgxLook) -> [f32; 3] {
+    let encoded = matrix_multiply(&AGX_INSET, rgb).map(|c| {
+        let x = (c.log2().clamp(AGX_MIN_EV, AGX_MAX_EV) - AGX_MIN_EV) / (AGX_MAX_EV - AGX_MIN_EV);
+        // Polynomial approximation of the default contrast sigmoid.
+        let x2 = x * x;
+        let x4 = x2 * x2;
+        15.5 * x4 * x2 - 40.14 * x4 * x + 31.96 * x4 - 6.868 * x2 * x + 0.4298 * x2 + 0.1191 * x
+            - 0.00232
+    });
+    let graded = match look {
+        AgxLook::Base => encoded,
+        AgxLook::Punchy => {
+            let luminance = dot(encoded, LUMINANCE);
+            encoded.map(|c| luminance + 1.4 * (c.max(0.).powf(1.35) - luminance))
+        }
+    };
+    matrix_multiply(&AGX_OUTSET, graded).map(|c| c.max(0.).powf(2.2))
+}
+
+fn uncharted2(rgb: [f32; 3]) -> [f32; 3] {
+    const A: f32 = 0.15;
+    const B: f32 = 0.50;
+    const C: f32 = 0.10;
+    const D: f32 = 0.20;
+    const E: f32 = 0.02;
+    const F: f32 = 0.30;
+    const W: f32 = 11.2;
+    const EXPOSURE_BIAS: f32 = 2.0;
+    let partial = |x: f32| ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F;
+    let white_scale = 1. / partial(W);
+    rgb.map(|c| partial(c * EXPOSURE_BIAS) * white_scale)
+}
+
+fn khronos_pbr_neutral(rgb: [f32; 3]) -> [f32; 3] {
+    const START_COMPRESSION: f32 = 0.8 - 0.04;
+    const DESATURATION: f32 = 0.15;
+    let x = rgb[0].min(rgb[1]).min(rgb[2]);
+    let offset = if x < 0.08 { x - 6.25 * x * x } else { 0.04 };
+    let rgb = rgb.map(|c| c - offset);
+    let peak = rgb[0].max(rgb[1]).max(rgb[2]);
+    if peak < START_COMPRESSION {
+        return rgb;
+    }
+    let d = 1. - START_COMPRESSION;
+    let new_peak = 1. - d * d / (peak + d - START_COMPRESSION);
+    let g = 1. - 1. / (DESATURATION * (peak - new_peak) + 1.);
+    rgb.map(|c| {
+        let c = c * new_peak / peak;
+        c + (new_peak - c) * g
+    })
+}
+
+impl Tonemap for Tonemapper {
+    fn tonemap_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        match *self {
+            Tonemapper::Reinhard => rgb.map(|c| c / (1. + c)),
+            Tonemapper::ReinhardExtended { max_white } => {
+                rgb.map(|c| c * (1. + c / (max_white * max_white)) / (1. + c))
+            }
+            Tonemapper::ReinhardJodie => reinhard_jodie(rgb),
+            Tonemapper::AcesNarkowicz => {
+                rgb.map(|c| (c * (2.51 * c + 0.03) / (c * (2.43 * c + 0.59) + 0.14)).clamp(0., 1.))
+            }
+            Tonemapper::AcesFitted => aces_fitted(rgb),
+            Tonemapper::Agx { look } => agx(rgb, look),
+            Tonemapper::Uncharted2 => uncharted2(rgb),
+            Tonemapper::KhronosPbrNeutral => khronos_pbr_neutral(rgb),
+        }
+    }
+}
+
+/// Tomasz Stachowiak's Tony McMapface, a tone mapping operator defined by a 3D lookup table.
+///
+/// The table is sampled at `c / (c + 1)` of each channel with trilinear filtering, at the
+/// texel centers, the same as Bevy's shader. The table itself is not included in this crate;
+/// load `tony_mc_mapface.ktx2` (shipped with Bevy's `tonemapping_luts` feature, or from the
+/// project's repository) into an [`Image`] and use [`TonyMcMapface::from_image`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TonyMcMapface {
+    lut: Lut3d,
+}
+
+impl TonyMcMapface {
+    /// Construct from a `size` x `size` x `size` table of linear RGB texels, with red varying
+    /// fastest and blue slowest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is less than 2, or if `texels` does not hold `size³` texels.
+    pub fn new(size: usize, texels: Vec<[f32; 3]>) -> Self {
+        Self {
+            lut: Lut3d::new(size, texels),
+        }
+    }
+
+    /// Read the table from a cubic 3D texture, in any format which [`Lut3d::from_image`]
+    /// reads. Bevy ships the table as [`TextureFormat::Rgb9e5Ufloat`].
+    ///
+    /// [`TextureFormat::Rgb9e5Ufloat`]: bevy::render::render_resource::TextureFormat::Rgb9e5Ufloat
+    pub fn from_image(image: &Image) -> Option<Self> {
+        Lut3d::from_image(image).map(|lut| Self { lut })
+    }
+
+    /// The number of texels along each side of the table.
+    pub fn size(&self) -> usize {
+        self.lut.size()
+    }
+
+    /// The lookup table.
+    pub fn lut(&self) -> &Lut3d {
+        &self.lut
+    }
+}
+
+impl Tonemap for TonyMcMapface {
+    fn tonemap_rgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        self.lut
+            .sample(rgb.map(|c| c / (c + 1.)), LutInterpolation::Trilinear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_approx_eq;
+    use bevy::render::texture::{CompressedImageFormats, ImageSampler, ImageType};
+
+    fn assert_rgb(actual: LinearRgba, expected: [f32; 3]) {
+        assert_approx_eq!(actual.red, expected[0], 0.00002);
+        assert_approx_eq!(actual.green, expected[1], 0.00002);
+        assert_approx_eq!(actual.blue, expected[2], 0.00002);
+    }
+
+    #[test]
+    fn reference_values() {
+        // Computed in double precision from the shader sources.
+        let inputs = [
+            LinearRgba::new(0.18, 0.18, 0.18, 1.),
+            LinearRgba::new(2.0, 0.5, 0.1, 1.),
+            LinearRgba::new(8.0, 3.0, 0.25, 1.),
+        ];
+        let cases: [(Tonemapper, [[f32; 3]; 3]); 9] = [
+            (
+                Tonemapper::Reinhard,
+                [
+                    [0.152542, 0.152542, 0.152542],
+                    [0.666667, 0.333333, 0.090909],
+                    [0.888889, 0.750000, 0.200000],
+                ],
+            ),
+            (
+                Tonemapper::ReinhardExtended { max_white: 4. },
+                [
+                    [0.154258, 0.154258, 0.154258],
+                    [0.750000, 0.343750, 0.091477],
+                    [1.333333, 0.890625, 0.203125],
+                ],
+            ),
+            (
+                Tonemapper::ReinhardJodie,
+                [
+                    [0.152542, 0.152542, 0.152542],
+                    [0.816880, 0.297329, 0.059051],
+                    [0.972855, 0.716680, 0.081115],
+                ],
+            ),
+            (
+                Tonemapper::AcesNarkowicz,
+                [
+                    [0.266899, 0.266899, 0.266899],
+                    [0.914855, 0.616307, 0.125840],
+                    [1.000000, 0.953743, 0.374111],
+                ],
+            ),
+            (
+                Tonemapper::AcesFitted,
+                [
+                    [0.105591, 0.105591, 0.105590],
+                    [0.901499, 0.418689, 0.105001],
+                    [1.000000, 0.883965, 0.534454],
+                ],
+            ),
+            (
+                Tonemapper::Agx {
+                    look: AgxLook::Base,
+                },
+                [
+                    [0.214467, 0.214533, 0.214537],
+                    [0.807289, 0.449624, 0.210690],
+                    [0.980513, 0.842965, 0.494094],
+                ],
+            ),
+            (
+                Tonemapper::Agx {
+                    look: AgxLook::Punchy,
+                },
+                [
+                    [0.096612, 0.096622, 0.096623],
+                    [0.865365, 0.290775, 0.046914],
+                    [1.036983, 0.783110, 0.259220],
+                ],
+            ),
+            (
+                Tonemapper::Uncharted2,
+                [
+                    [0.128338, 0.128338, 0.128338],
+                    [0.713238, 0.304301, 0.074215],
+                    [1.071748, 0.837871, 0.171970],
+                ],
+            ),
+            (
+                Tonemapper::KhronosPbrNeutral,
+                [
+                    [0.140000, 0.140000, 0.140000],
+                    [0.960000, 0.321136, 0.150772],
+                    [0.992258, 0.687501, 0.519884],
+                ],
+            ),
+        ];
+        for (tonemapper, expected) in cases {
+            for (input, expected) in inputs.iter().zip(expected) {
+                assert_rgb(tonemapper.tonemap(*input), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn clamps_negative_input_and_keeps_alpha() {
+        let color = LinearRgba::new(-1.0, 0.5, 2.0, 0.25);
+        for tonemapper in [
+            Tonemapper::Reinhard,
+            Tonemapper::ReinhardJodie,
+            Tonemapper::AcesFitted,
+            Tonemapper::Agx {
+                look: AgxLook::Punchy,
+            },
+            Tonemapper::KhronosPbrNeutral,
+        ] {
+            let mapped = tonemapper.tonemap(color);
+            assert_eq!(
+                mapped,
+                tonemapper.tonemap(LinearRgba::new(0.0, 0.5, 2.0, 0.25))
+            );
+            assert_eq!(mapped.alpha, 0.25);
+        }
+
+        let mut out = [LinearRgba::default(); 1];
+        Tonemapper::Uncharted2.tonemap_slice(&[color], &mut out);
+        assert_eq!(out[0], Tonemapper::Uncharted2.tonemap(color));
+    }
+
+    #[test]
+    fn white_points() {
+        // The exposure bias halves the input white point.
+        let white = LinearRgba::new(5.6, 5.6, 5.6, 1.);
+        assert_rgb(Tonemapper::Uncharted2.tonemap(white), [1., 1., 1.]);
+        let white = LinearRgba::new(3., 3., 3., 1.);
+        assert_rgb(
+            Tonemapper::ReinhardExtended { max_white: 3. }.tonemap(white),
+            [1., 1., 1.],
+        );
+        let black = LinearRgba::new(0., 0., 0., 1.);
+        assert_rgb(Tonemapper::AcesFitted.tonemap(black), [0., 0., 0.]);
+        assert_rgb(Tonemapper::KhronosPbrNeutral.tonemap(black), [0., 0., 0.]);
+    }
+
+    #[test]
+    fn tony_mc_mapface_sampling() {
+        // A table holding its own coordinates samples back the coordinates exactly.
+        let size = 5;
+        let last = (size - 1) as f32;
+        let mut texels = Vec::new();
+        for blue in 0..size {
+            for green in 0..size {
+                for red in 0..size {
+                    texels.push([red as f32 / last, green as f32 / last, blue as f32 / last]);
+                }
+            }
+        }
+        let lut = TonyMcMapface::new(size, texels);
+        let mapped = lut.tonemap(LinearRgba::new(0.3, 1.0, 7.0, 0.5));
+        assert_rgb(mapped, [0.3 / 1.3, 0.5, 7.0 / 8.0]);
+        assert_eq!(mapped.alpha, 0.5);
+    }
+
+    #[test]
+    fn tony_mc_mapface_lut() {
+        let image = Image::from_buffer(
+            include_bytes!("../data/tonemapping/tony_mc_mapface.ktx2"),
+            ImageType::Extension("ktx2"),
+            CompressedImageFormats::NONE,
+            false,
+            ImageSampler::Default,
+        )
+        .unwrap();
+        let lut = TonyMcMapface::from_image(&image).unwrap();
+        assert_eq!(lut.size(), 48);
+
+        let black = lut.tonemap(LinearRgba::new(0., 0., 0., 1.));
+        assert!(black.red.abs() < 0.001 && black.green.abs() < 0.001);
+
+        // Grays stay neutral, increase monotonically and approach white.
+        let mut previous = 0.;
+        for i in 1..=20 {
+            let value = 0.01 * 2f32.powi(i);
+            let gray = lut.tonemap(LinearRgba::new(value, value, value, 1.));
+            assert_approx_eq!(gray.red, gray.green, 0.01);
+            assert_approx_eq!(gray.blue, gray.green, 0.01);
+            assert!(gray.green > previous);
+            previous = gray.green;
+        }
+        assert!(previous > 0.95 && previous <= 1.01);
+
+        // Very bright saturated colors desaturate towards white.
+        let red = lut.tonemap(LinearRgba::new(1000., 0., 0., 1.));
+        assert!(red.green > 0.5 && red.blue > 0.5);
+    }
+}
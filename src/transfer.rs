@@ -0,0 +1,406 @@
+//! Transfer functions for HDR signals.
+//!
+//! [`LinearRgba`] is relative: 1.0 is white, with no notion of how bright white is. The
+//! transfer functions here map such linear values, where 1.0 is a reference white of a
+//! configurable luminance in nits, to and from HDR signal values. They apply to linear data
+//! in any primaries; HDR10 and HLG video use BT.2020 primaries, which
+//! [`linear_rgba_to_rec2020`] and [`rec2020_to_linear_rgba`] convert to and from.
+
+use crate::{math::matrix_multiply, LinearRgba, SDR_REFERENCE_WHITE};
+
+/// A transfer function between linear RGB values and an encoded signal.
+///
+/// Linear values are relative to a reference white of 1.0, and are not clamped, so they may
+/// be negative or greater than 1.0.
+pub trait TransferFunction {
+    /// Encode linear RGB values into signal values.
+    fn encode(&self, linear: [f32; 3]) -> [f32; 3];
+
+    /// Decode signal values into linear RGB values.
+    fn decode(&self, signal: [f32; 3]) -> [f32; 3];
+
+    /// Encode a linear color into signal values and alpha. Alpha is passed through.
+    fn encode_linear_rgba(&self, color: LinearRgba) -> [f32; 4] {
+        let [red, green, blue] = self.encode([color.red, color.green, color.blue]);
+        [red, green, blue, color.alpha]
+    }
+
+    /// Decode signal values and alpha into a linear color. Alpha is passed through.
+    fn decode_linear_rgba(&self, [red, green, blue, alpha]: [f32; 4]) -> LinearRgba {
+        let [red, green, blue] = self.decode([red, green, blue]);
+        LinearRgba::new(red, green, blue, alpha)
+    }
+}
+
+const PQ_M1: f32 = 2610. / 16384.;
+const PQ_M2: f32 = 2523. / 32.;
+const PQ_C1: f32 = 3424. / 4096.;
+const PQ_C2: f32 = 2413. / 128.;
+const PQ_C3: f32 = 2392. / 128.;
+
+/// The peak luminance in nits of the PQ signal range.
+pub const PQ_MAX_NITS: f32 = 10000.;
+
+/// The PQ inverse EOTF of SMPTE ST 2084, from a luminance in nits to a signal in [0, 1].
+pub fn pq_inverse_eotf(nits: f32) -> f32 {
+    let y = (nits.max(0.) / PQ_MAX_NITS).powf(PQ_M1);
+    ((PQ_C1 + PQ_C2 * y) / (1. + PQ_C3 * y)).powf(PQ_M2)
+}
+
+/// The PQ EOTF of SMPTE ST 2084, from a signal in [0, 1] to a luminance in nits.
+pub fn pq_eotf(signal: f32) -> f32 {
+    let e = signal.max(0.).powf(1. / PQ_M2);
+    PQ_MAX_NITS * ((e - PQ_C1).max(0.) / (PQ_C2 - PQ_C3 * e)).powf(1. / PQ_M1)
+}
+
+/// The Perceptual Quantizer (PQ) of SMPTE ST 2084, the transfer function of HDR10.
+///
+/// PQ encodes absolute luminance up to [`PQ_MAX_NITS`]. A linear value of 1.0 is
+/// `reference_white` nits; negative values encode as 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pq {
+    pub reference_white: f32,
+}
+
+impl Pq {
+    /// Construct a new [`Pq`] transfer function, where a linear value of 1.0 is
+    /// `reference_white` nits.
+    pub const fn new(reference_white: f32) -> Self {
+        Self { reference_white }
+    }
+}
+
+impl Default for Pq {
+    fn default() -> Self {
+        Self::new(SDR_REFERENCE_WHITE)
+    }
+}
+
+impl TransferFunction for Pq {
+    fn encode(&self, linear: [f32; 3]) -> [f32; 3] {
+        linear.map(|c| pq_inverse_eotf(c * self.reference_white))
+    }
+
+    fn decode(&self, signal: [f32; 3]) -> [f32; 3] {
+        signal.map(|c| pq_eotf(c) / self.reference_white)
+    }
+}
+
+const HLG_A: f32 = 0.178_832_77;
+const HLG_B: f32 = 1. - 4. * HLG_A;
+const HLG_C: f32 = 0.559_910_7;
+
+/// The luma weights of BT.2020, which the HLG OOTF uses to compute luminance.
+const BT2020_LUMA: [f32; 3] = [0.2627, 0.678, 0.0593];
+
+/// Hybrid Log-Gamma (HLG) of ITU-R BT.2100, the transfer function of HLG broadcast video.
+///
+/// HLG encodes relative scene light. The EOTF renders it for a display with
+/// `peak_luminance` and `black_level` in nits, applying the system gamma to the luminance
+/// of the color, so it is only exact for linear data in BT.2020 primaries. A linear value of
+/// 1.0 is `reference_white` nits of display light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hlg {
+    pub peak_luminance: f32,
+    pub black_level: f32,
+    pub reference_white: f32,
+}
+
+impl Hlg {
+    /// Construct a new [`Hlg`] transfer function for a display with `peak_luminance` and
+    /// `black_level` in nits, where a linear value of 1.0 is `reference_white` nits.
+    pub const fn new(peak_luminance: f32, black_level: f32, reference_white: f32) -> Self {
+        Self {
+            peak_luminance,
+            black_level,
+            reference_white,
+        }
+    }
+
+    /// The HLG OETF, from normalized scene light in [0, 1] to a signal in [0, 1].
+    pub fn oetf(scene: f32) -> f32 {
+        if scene <= 1. / 12. {
+            (3. * scene.max(0.)).sqrt()
+        } else {
+            HLG_A * (12. * scene - HLG_B).ln() + HLG_C
+        }
+    }
+
+    /// The inverse of the HLG OETF, from a signal in [0, 1] to normalized scene light.
+    pub fn inverse_oetf(signal: f32) -> f32 {
+        if signal <= 0.5 {
+            signal.max(0.).powi(2) / 3.
+        } else {
+            (((signal - HLG_C) / HLG_A).exp() + HLG_B) / 12.
+        }
+    }
+
+    /// The system gamma for the peak luminance of the display, which is 1.2 for the
+    /// reference display of 1000 nits.
+    pub fn system_gamma(&self) -> f32 {
+        1.2 + 0.42 * (self.peak_luminance / 1000.).log10()
+    }
+
+    /// The HLG OOTF, from normalized scene light to display light in nits.
+    pub fn ootf(&self, scene: [f32; 3]) -> [f32; 3] {
+        let luminance = luminance(scene).max(0.);
+        let gain = self.peak_luminance * luminance.powf(self.system_gamma() - 1.);
+        scene.map(|c| if luminance > 0. { c * gain } else { 0. })
+    }
+
+    /// The inverse of the HLG OOTF, from display light in nits to normalized scene light.
+    pub fn inverse_ootf(&self, display: [f32; 3]) -> [f32; 3] {
+        let gamma = self.system_gamma();
+        let luminance = (luminance(display) / self.peak_luminance).max(0.);
+        if luminance <= 0. {
+            return [0.; 3];
+        }
+        let scene_luminance = luminance.powf(1. / gamma);
+        let gain = self.peak_luminance * scene_luminance.powf(gamma - 1.);
+        display.map(|c| c / gain)
+    }
+
+    /// The HLG EOTF, from signal values to display light in nits.
+    pub fn eotf(&self, signal: [f32; 3]) -> [f32; 3] {
+        let beta = self.black_lift();
+        self.ootf(signal.map(|c| Self::inverse_oetf(((1. - beta) * c + beta).max(0.))))
+    }
+
+    /// The inverse of the HLG EOTF, from display light in nits to signal values.
+    pub fn inverse_eotf(&self, display: [f32; 3]) -> [f32; 3] {
+        let beta = self.black_lift();
+        self.inverse_ootf(display)
+            .map(|c| (Self::oetf(c) - beta) / (1. - beta))
+    }
+
+    /// The black level lift β of the EOTF.
+    fn black_lift(&self) -> f32 {
+        let ratio = (self.black_level / self.peak_luminance).max(0.);
+        (3. * ratio.powf(1. / self.system_gamma())).sqrt()
+    }
+}
+
+impl Default for Hlg {
+    fn default() -> Self {
+        Self::new(1000., 0., SDR_REFERENCE_WHITE)
+    }
+}
+
+impl TransferFunction for Hlg {
+    fn encode(&self, linear: [f32; 3]) -> [f32; 3] {
+        self.inverse_eotf(linear.map(|c| c * self.reference_white))
+    }
+
+    fn decode(&self, signal: [f32; 3]) -> [f32; 3] {
+        self.eotf(signal).map(|c| c / self.reference_white)
+    }
+}
+
+fn luminance(rgb: [f32; 3]) -> f32 {
+    BT2020_LUMA[0] * rgb[0] + BT2020_LUMA[1] * rgb[1] + BT2020_LUMA[2] * rgb[2]
+}
+
+/// The luminance in nits of a scRGB value of 1.0.
+pub const SCRGB_WHITE_NITS: f32 = 80.;
+
+/// scRGB (IEC 61966-2-2) extended range linear encoding, used for HDR swap chains with
+/// floating point formats.
+///
+/// scRGB has sRGB primaries and a linear encoding where 1.0 is [`SCRGB_WHITE_NITS`], and
+/// allows negative values and values above 1.0 for colors outside of the sRGB gamut and
+/// brighter than its white. A linear value of 1.0 is `reference_white` nits, so with the
+/// default reference white, SDR white encodes as 203 / 80 ≈ 2.54.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScRgb {
+    pub reference_white: f32,
+}
+
+impl ScRgb {
+    /// Construct a new [`ScRgb`] encoding, where a linear value of 1.0 is
+    /// `reference_white` nits.
+    pub const fn new(reference_white: f32) -> Self {
+        Self { reference_white }
+    }
+}
+
+impl Default for ScRgb {
+    fn default() -> Self {
+        Self::new(SDR_REFERENCE_WHITE)
+    }
+}
+
+impl TransferFunction for ScRgb {
+    fn encode(&self, linear: [f32; 3]) -> [f32; 3] {
+        linear.map(|c| c * self.reference_white / SCRGB_WHITE_NITS)
+    }
+
+    fn decode(&self, signal: [f32; 3]) -> [f32; 3] {
+        signal.map(|c| c * SCRGB_WHITE_NITS / self.reference_white)
+    }
+}
+
+/// The sRGB transfer function extended to all real numbers, mirrored for negative values
+/// and continued above 1.0, as used by extended sRGB and the non-linear form of scRGB.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExtendedSrgb;
+
+impl ExtendedSrgb {
+    fn encode_channel(linear: f32) -> f32 {
+        let magnitude = linear.abs();
+        let encoded = if magnitude <= 0.003_130_8 {
+            magnitude * 12.92
+        } else {
+            1.055 * magnitude.powf(1. / 2.4) - 0.055
+        };
+        encoded.copysign(linear)
+    }
+
+    fn decode_channel(signal: f32) -> f32 {
+        let magnitude = signal.abs();
+        let decoded = if magnitude <= 0.04045 {
+            magnitude / 12.92
+        } else {
+            ((magnitude + 0.055) / 1.055).powf(2.4)
+        };
+        decoded.copysign(signal)
+    }
+}
+
+impl TransferFunction for ExtendedSrgb {
+    fn encode(&self, linear: [f32; 3]) -> [f32; 3] {
+        linear.map(Self::encode_channel)
+    }
+
+    fn decode(&self, signal: [f32; 3]) -> [f32; 3] {
+        signal.map(Self::decode_channel)
+    }
+}
+
+/// Conversion of linear RGB from BT.709 (sRGB) to BT.2020 primaries, from ITU-R BT.2087.
+pub(crate) const BT709_TO_BT2020: [[f32; 3]; 3] = [
+    [0.627404, 0.329282, 0.0433136],
+    [0.069097, 0.91954, 0.0113612],
+    [0.0163916, 0.0880132, 0.895595],
+];
+
+pub(crate) const BT2020_TO_BT709: [[f32; 3]; 3] = [
+    [1.660491, -0.5876411, -0.0728499],
+    [-0.1245505, 1.1328999, -0.0083494],
+    [-0.0181508, -0.1005789, 1.1187297],
+];
+
+/// Convert a linear sRGB color to linear RGB in BT.2020 (Rec. 2020) primaries.
+pub fn linear_rgba_to_rec2020(color: LinearRgba) -> [f32; 3] {
+    matrix_multiply(&BT709_TO_BT2020, [color.red, color.green, color.blue])
+}
+
+/// Convert linear RGB in BT.2020 (Rec. 2020) primaries to a linear sRGB color. The result
+/// is not clamped, so colors outside of the sRGB gamut have components outside of [0, 1].
+pub fn rec2020_to_linear_rgba(rgb: [f32; 3], alpha: f32) -> LinearRgba {
+    let [red, green, blue] = matrix_multiply(&BT2020_TO_BT709, rgb);
+    LinearRgba::new(red, green, blue, alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    fn assert_round_trip(transfer: &impl TransferFunction, linear: [f32; 3], tolerance: f32) {
+        let back = transfer.decode(transfer.encode(linear));
+        for (back, linear) in back.into_iter().zip(linear) {
+            assert_approx_eq!(back, linear, tolerance);
+        }
+    }
+
+    #[test]
+    fn pq() {
+        assert_approx_eq!(pq_inverse_eotf(100.), 0.508078, 0.00001);
+        assert_approx_eq!(pq_inverse_eotf(1000.), 0.751827, 0.00001);
+        assert_approx_eq!(pq_inverse_eotf(10000.), 1.0, 0.00001);
+        assert_approx_eq!(pq_eotf(0.508078), 100., 0.01);
+        assert_eq!(pq_eotf(0.), 0.);
+
+        // With a reference white of 100 nits, 10.0 is 1000 nits.
+        let pq = Pq::new(100.);
+        assert_approx_eq!(pq.encode([1., 10., 0.])[0], 0.508078, 0.00001);
+        assert_approx_eq!(pq.encode([1., 10., 0.])[1], 0.751827, 0.00001);
+        assert_approx_eq!(Pq::default().encode([1.; 3])[0], 0.580689, 0.00001);
+        assert_eq!(pq.encode([-1., 0., 0.])[0], pq.encode([0.; 3])[0]);
+        for linear in [[0., 0.5, 1.], [2., 10., 49.]] {
+            assert_round_trip(&Pq::default(), linear, 0.001);
+        }
+    }
+
+    #[test]
+    fn hlg() {
+        assert_approx_eq!(Hlg::oetf(1. / 12.), 0.5, 0.00001);
+        assert_approx_eq!(Hlg::oetf(1.), 1., 0.00001);
+        assert_approx_eq!(Hlg::inverse_oetf(0.75), 0.26497, 0.0001);
+        for scene in [0., 0.01, 0.1, 0.5, 1.] {
+            assert_approx_eq!(Hlg::inverse_oetf(Hlg::oetf(scene)), scene, 0.00001);
+        }
+
+        let hlg = Hlg::default();
+        assert_eq!(hlg.system_gamma(), 1.2);
+        assert_approx_eq!(Hlg::new(2000., 0., 203.).system_gamma(), 1.3264, 0.0001);
+        // A signal of 75% is reference white, and 100% is the display peak.
+        let white = hlg.eotf([0.75; 3]);
+        assert_approx_eq!(white[1], 203., 1.);
+        assert_approx_eq!(hlg.eotf([1.; 3])[1], 1000., 0.1);
+        assert_approx_eq!(hlg.decode([0.75; 3])[0], 1., 0.01);
+
+        for transfer in [hlg, Hlg::new(600., 0.1, 100.)] {
+            for linear in [[0.2, 0.5, 1.], [1., 1., 1.], [0.01, 0., 0.3]] {
+                assert_round_trip(&transfer, linear, 0.001);
+            }
+        }
+        // The black level lifts the darkest signal.
+        assert_approx_eq!(Hlg::new(1000., 0.1, 203.).eotf([0.; 3])[0], 0.1, 0.0001);
+        assert!(Hlg::new(1000., 0.1, 203.).eotf([0.1; 3])[0] > hlg.eotf([0.1; 3])[0]);
+    }
+
+    #[test]
+    fn scrgb() {
+        let scrgb = ScRgb::new(80.);
+        assert_eq!(scrgb.encode([1., -0.5, 4.]), [1., -0.5, 4.]);
+        let [white, ..] = ScRgb::default().encode([1.; 3]);
+        assert_approx_eq!(white, 2.5375, 0.0001);
+        assert_round_trip(&ScRgb::default(), [-0.2, 1.5, 12.], 0.0001);
+
+        let extended = ExtendedSrgb;
+        assert_approx_eq!(extended.encode([0., 1., 0.])[1], 1., 0.00001);
+        let [negative, positive, bright] = extended.encode([-0.5, 0.5, 2.]);
+        assert_approx_eq!(negative, -positive, 0.00001);
+        assert!(bright > 1.);
+        assert_round_trip(&extended, [-0.5, 0.002, 3.], 0.00001);
+
+        for color in TEST_COLORS.iter() {
+            let [red, green, blue, alpha] = extended.encode_linear_rgba(color.linear_rgb);
+            assert_approx_eq!(red, color.rgb.red, 0.0001);
+            assert_approx_eq!(green, color.rgb.green, 0.0001);
+            assert_approx_eq!(blue, color.rgb.blue, 0.0001);
+            assert_eq!(alpha, color.rgb.alpha);
+        }
+    }
+
+    #[test]
+    fn rec2020() {
+        let white = linear_rgba_to_rec2020(LinearRgba::new(1., 1., 1., 1.));
+        for channel in white {
+            assert_approx_eq!(channel, 1., 0.0001);
+        }
+        // Pure sRGB red is inside the BT.2020 gamut, and BT.2020 red is outside sRGB.
+        let red = linear_rgba_to_rec2020(LinearRgba::new(1., 0., 0., 1.));
+        assert!(red.iter().all(|c| *c >= 0.));
+        let back = rec2020_to_linear_rgba(red, 0.5);
+        assert_approx_eq!(back.red, 1., 0.0001);
+        assert_approx_eq!(back.green, 0., 0.0001);
+        assert_eq!(back.alpha, 0.5);
+        assert!(rec2020_to_linear_rgba([1., 0., 0.], 1.).green < 0.);
+
+        // HDR10 is BT.2020 primaries with PQ.
+        let hdr10 = Pq::default().encode(red);
+        assert!(hdr10.iter().all(|c| (0. ..=1.).contains(c)));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{
+    color_ops::impl_componentwise_ops, Hsla, Lcha, LinearRgba, Mix, Oklaba, SRgba, WithAlpha,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the CIE 1931 XYZ color space, with alpha.
+///
+/// The white point is D65, and Y is the relative luminance, so white has a Y of 1.0. XYZ is
+/// linear, and is mostly useful as the connection space of other color models, such as
+/// [`crate::Cam16`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct Xyza {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub alpha: f32,
+}
+
+impl Xyza {
+    /// The D65 white point, the white of sRGB.
+    pub const D65_WHITE: Self = Self::new(0.95047, 1.0, 1.08883, 1.0);
+
+    /// Construct a new [`Xyza`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X channel. [0.0, ~0.95]
+    /// * `y` - Y channel, the relative luminance. [0.0, 1.0]
+    /// * `z` - Z channel. [0.0, ~1.09]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(x: f32, y: f32, z: f32, alpha: f32) -> Self {
+        Self { x, y, z, alpha }
+    }
+
+    /// Convert the Xyza color to a tuple of components (x, y, z, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, self.z, self.alpha)
+    }
+
+    /// Construct a new [`Xyza`] color from a tuple of components (x, y, z, alpha).
+    #[inline]
+    pub const fn from_components((x, y, z, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(x, y, z, alpha)
+    }
+}
+
+impl Default for Xyza {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for Xyza {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            x: self.x * n_factor + other.x * factor,
+            y: self.y * n_factor + other.y * factor,
+            z: self.z * n_factor + other.z * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl_componentwise_ops!(Xyza, [x, y, z, alpha]);
+
+impl WithAlpha for Xyza {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl From<LinearRgba> for Xyza {
+    fn from(value: LinearRgba) -> Self {
+        let LinearRgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } = value;
+        // Same matrix as Bevy's LCH conversion, from http://www.brucelindbloom.com
+        Xyza::new(
+            red * 0.4124564 + green * 0.3575761 + blue * 0.1804375,
+            red * 0.2126729 + green * 0.7151522 + blue * 0.072175,
+            red * 0.0193339 + green * 0.119192 + blue * 0.9503041,
+            alpha,
+        )
+    }
+}
+
+impl From<Xyza> for LinearRgba {
+    fn from(value: Xyza) -> Self {
+        let Xyza { x, y, z, alpha } = value;
+        LinearRgba::new(
+            x * 3.2404542 + y * -1.5371385 + z * -0.4985314,
+            x * -0.969266 + y * 1.8760108 + z * 0.041556,
+            x * 0.0556434 + y * -0.2040259 + z * 1.0572252,
+            alpha,
+        )
+    }
+}
+
+impl From<SRgba> for Xyza {
+    fn from(value: SRgba) -> Self {
+        Xyza::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Xyza> for SRgba {
+    fn from(value: Xyza) -> Self {
+        SRgba::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Hsla> for Xyza {
+    fn from(value: Hsla) -> Self {
+        Xyza::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Lcha> for Xyza {
+    fn from(value: Lcha) -> Self {
+        Xyza::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Oklaba> for Xyza {
+    fn from(value: Oklaba) -> Self {
+        Xyza::from(LinearRgba::from(value))
+    }
+}
+
+impl From<Xyza> for Oklaba {
+    fn from(value: Xyza) -> Self {
+        Oklaba::from(LinearRgba::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq, LuminanceOps};
+
+    #[test]
+    fn white_point() {
+        let white = Xyza::from(LinearRgba::new(1., 1., 1., 1.));
+        assert_approx_eq!(white.x, Xyza::D65_WHITE.x, 0.0001);
+        assert_approx_eq!(white.y, Xyza::D65_WHITE.y, 0.0001);
+        assert_approx_eq!(white.z, Xyza::D65_WHITE.z, 0.0001);
+    }
+
+    #[test]
+    fn round_trip() {
+        for color in TEST_COLORS.iter() {
+            let xyz = Xyza::from(color.linear_rgb);
+            let linear = LinearRgba::from(xyz);
+            assert_approx_eq!(linear.red, color.linear_rgb.red, 0.0001);
+            assert_approx_eq!(linear.green, color.linear_rgb.green, 0.0001);
+            assert_approx_eq!(linear.blue, color.linear_rgb.blue, 0.0001);
+            assert_approx_eq!(xyz.y, color.linear_rgb.luminance(), 0.001);
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use crate::{LinearRgba, Mix, SRgba, WithAlpha};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// The matrix coefficients which define luma and the color difference signals of
+/// [`YCbCr`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Hash, Serialize, Deserialize)]
+pub enum YCbCrMatrix {
+    /// ITU-R BT.601, used by standard definition video and JPEG.
+    Bt601,
+    /// ITU-R BT.709, used by high definition video.
+    #[default]
+    Bt709,
+    /// ITU-R BT.2020 non-constant luminance, used by ultra high definition and HDR video.
+    Bt2020,
+}
+
+impl YCbCrMatrix {
+    /// The luma weights of the red and blue channels, (Kr, Kb).
+    pub fn kr_kb(self) -> (f32, f32) {
+        match self {
+            YCbCrMatrix::Bt601 => (0.299, 0.114),
+            YCbCrMatrix::Bt709 => (0.2126, 0.0722),
+            YCbCrMatrix::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+/// The range of integer codes used by quantized [`YCbCr`] values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Hash, Serialize, Deserialize)]
+pub enum YCbCrRange {
+    /// All codes are used, as in JPEG: luma from 0 to 255 and chroma centered on 128 for 8
+    /// bits.
+    Full,
+    /// Studio (or "TV") range, which leaves headroom and footroom: luma from 16 to 235 and
+    /// chroma from 16 to 240 for 8 bits, scaled up for higher bit depths.
+    #[default]
+    Limited,
+}
+
+/// The matrix and range of a YCbCr video signal.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct YCbCrFormat {
+    pub matrix: YCbCrMatrix,
+    pub range: YCbCrRange,
+}
+
+impl YCbCrFormat {
+    /// Construct a new [`YCbCrFormat`].
+    pub const fn new(matrix: YCbCrMatrix, range: YCbCrRange) -> Self {
+        Self { matrix, range }
+    }
+}
+
+/// Color in the YCbCr (often called YUV) color encoding of digital video, with alpha.
+///
+/// YCbCr is a linear transform of gamma-encoded [`SRgba`], so its conversions take the
+/// [`YCbCrMatrix`] of the signal. The [`From`] conversions use [`YCbCrMatrix::Bt709`].
+/// Components are analog values; use [`YCbCr::quantize`] and [`YCbCr::from_quantized`] for
+/// integer codes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct YCbCr {
+    pub y: f32,
+    pub cb: f32,
+    pub cr: f32,
+    pub alpha: f32,
+}
+
+impl YCbCr {
+    /// Construct a new [`YCbCr`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - Luma channel. [0.0, 1.0]
+    /// * `cb` - Blue difference channel. [-0.5, 0.5]
+    /// * `cr` - Red difference channel. [-0.5, 0.5]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(y: f32, cb: f32, cr: f32, alpha: f32) -> Self {
+        Self { y, cb, cr, alpha }
+    }
+
+    /// Convert the YCbCr color to a tuple of components (y, cb, cr, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.y, self.cb, self.cr, self.alpha)
+    }
+
+    /// Construct a new [`YCbCr`] color from a tuple of components (y, cb, cr, alpha).
+    #[inline]
+    pub const fn from_components((y, cb, cr, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(y, cb, cr, alpha)
+    }
+
+    /// Convert an sRGB color with the given matrix coefficients.
+    pub fn from_srgba(color: SRgba, matrix: YCbCrMatrix) -> Self {
+        let (kr, kb) = matrix.kr_kb();
+        let y = kr * color.red + (1. - kr - kb) * color.green + kb * color.blue;
+        Self::new(
+            y,
+            (color.blue - y) / (2. * (1. - kb)),
+            (color.red - y) / (2. * (1. - kr)),
+            color.alpha,
+        )
+    }
+
+    /// Convert into an sRGB color with the given matrix coefficients. The result is not
+    /// clamped.
+    pub fn to_srgba(self, matrix: YCbCrMatrix) -> SRgba {
+        let (kr, kb) = matrix.kr_kb();
+        let red = self.y + 2. * (1. - kr) * self.cr;
+        let blue = self.y + 2. * (1. - kb) * self.cb;
+        let green = (self.y - kr * red - kb * blue) / (1. - kr - kb);
+        SRgba::new(red, green, blue, self.alpha)
+    }
+
+    /// Quantize into integer codes (y, cb, cr) of `bit_depth` bits, such as 8, 10 or 12.
+    /// Codes are rounded and clamped to the valid range of the bit depth.
+    pub fn quantize(self, range: YCbCrRange, bit_depth: u32) -> [u16; 3] {
+        debug_assert!((8..=16).contains(&bit_depth));
+        let max = ((1u32 << bit_depth) - 1) as f32;
+        let (y_scale, y_offset, c_scale) = quantization(range, bit_depth);
+        let c_offset = (1u32 << (bit_depth - 1)) as f32;
+        [
+            self.y * y_scale + y_offset,
+            self.cb * c_scale + c_offset,
+            self.cr * c_scale + c_offset,
+        ]
+        .map(|code| code.round().clamp(0., max) as u16)
+    }
+
+    /// Construct a new [`YCbCr`] color from integer codes (y, cb, cr) of `bit_depth` bits.
+    /// Alpha is 1.0.
+    pub fn from_quantized([y, cb, cr]: [u16; 3], range: YCbCrRange, bit_depth: u32) -> Self {
+        debug_assert!((8..=16).contains(&bit_depth));
+        let (y_scale, y_offset, c_scale) = quantization(range, bit_depth);
+        let c_offset = (1u32 << (bit_depth - 1)) as f32;
+        Self::new(
+            (y as f32 - y_offset) / y_scale,
+            (cb as f32 - c_offset) / c_scale,
+            (cr as f32 - c_offset) / c_scale,
+            1.,
+        )
+    }
+}
+
+/// The luma scale and offset and the chroma scale of a range and bit depth, from ITU-R
+/// BT.2100.
+fn quantization(range: YCbCrRange, bit_depth: u32) -> (f32, f32, f32) {
+    match range {
+        YCbCrRange::Full => {
+            let max = ((1u32 << bit_depth) - 1) as f32;
+            (max, 0., max)
+        }
+        YCbCrRange::Limited => {
+            let scale = (1u32 << (bit_depth - 8)) as f32;
+            (219. * scale, 16. * scale, 224. * scale)
+        }
+    }
+}
+
+impl Default for YCbCr {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for YCbCr {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            y: self.y * n_factor + other.y * factor,
+            cb: self.cb * n_factor + other.cb * factor,
+            cr: self.cr * n_factor + other.cr * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for YCbCr {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl From<SRgba> for YCbCr {
+    fn from(value: SRgba) -> Self {
+        YCbCr::from_srgba(value, YCbCrMatrix::Bt709)
+    }
+}
+
+impl From<YCbCr> for SRgba {
+    fn from(value: YCbCr) -> Self {
+        value.to_srgba(YCbCrMatrix::Bt709)
+    }
+}
+
+impl From<LinearRgba> for YCbCr {
+    fn from(value: LinearRgba) -> Self {
+        YCbCr::from(SRgba::from(value))
+    }
+}
+
+impl From<YCbCr> for LinearRgba {
+    fn from(value: YCbCr) -> Self {
+        LinearRgba::from(SRgba::from(value))
+    }
+}
+
+/// Decode 8-bit codes into an opaque sRGB color, clamped to [0, 1].
+fn decode_pixel(y: u8, cb: u8, cr: u8, format: YCbCrFormat) -> SRgba {
+    let color =
+        YCbCr::from_quantized([y, cb, cr].map(u16::from), format.range, 8).to_srgba(format.matrix);
+    SRgba::new(
+        color.red.clamp(0., 1.),
+        color.green.clamp(0., 1.),
+        color.blue.clamp(0., 1.),
+        1.,
+    )
+}
+
+/// Check the plane sizes of a 4:2:0 frame, and return the width of its chroma planes.
+fn check_420_frame(
+    y_plane: &[u8],
+    chroma_samples: usize,
+    width: usize,
+    height: usize,
+    out: &[SRgba],
+) -> usize {
+    let chroma_width = width.div_ceil(2);
+    assert!(y_plane.len() >= width * height, "Y plane is too small");
+    assert!(
+        chroma_samples >= chroma_width * height.div_ceil(2),
+        "chroma plane is too small"
+    );
+    assert!(out.len() >= width * height, "output buffer is too small");
+    chroma_width
+}
+
+/// Decode an 8-bit planar 4:2:0 (I420) frame of `width` x `height` pixels into `out`, in
+/// row-major order.
+///
+/// The Y plane holds `width * height` samples, and the U (Cb) and V (Cr) planes each hold
+/// `ceil(width / 2) * ceil(height / 2)` samples, one for every 2 x 2 block of pixels. Rows
+/// are tightly packed.
+///
+/// # Panics
+///
+/// Panics if a plane or the output buffer is too small for the frame.
+pub fn i420_to_srgba(
+    y_plane: &[u8],
+    u_plane: &[u8],
+    v_plane: &[u8],
+    width: usize,
+    height: usize,
+    format: YCbCrFormat,
+    out: &mut [SRgba],
+) {
+    let chroma_width = check_420_frame(
+        y_plane,
+        u_plane.len().min(v_plane.len()),
+        width,
+        height,
+        out,
+    );
+    for row in 0..height {
+        for column in 0..width {
+            let chroma = (row / 2) * chroma_width + column / 2;
+            let pixel = row * width + column;
+            out[pixel] = decode_pixel(y_plane[pixel], u_plane[chroma], v_plane[chroma], format);
+        }
+    }
+}
+
+/// Decode an 8-bit semi-planar 4:2:0 (NV12) frame of `width` x `height` pixels into `out`,
+/// in row-major order.
+///
+/// The Y plane holds `width * height` samples, and the UV plane holds interleaved U (Cb)
+/// and V (Cr) pairs, one for every 2 x 2 block of pixels. Rows are tightly packed.
+///
+/// # Panics
+///
+/// Panics if a plane or the output buffer is too small for the frame.
+pub fn nv12_to_srgba(
+    y_plane: &[u8],
+    uv_plane: &[u8],
+    width: usize,
+    height: usize,
+    format: YCbCrFormat,
+    out: &mut [SRgba],
+) {
+    let chroma_width = check_420_frame(y_plane, uv_plane.len() / 2, width, height, out);
+    for row in 0..height {
+        for column in 0..width {
+            let chroma = 2 * ((row / 2) * chroma_width + column / 2);
+            let pixel = row * width + column;
+            out[pixel] = decode_pixel(
+                y_plane[pixel],
+                uv_plane[chroma],
+                uv_plane[chroma + 1],
+                format,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn reference_values() {
+        let red = YCbCr::from_srgba(SRgba::RED, YCbCrMatrix::Bt601);
+        assert_approx_eq!(red.y, 0.299, 0.0001);
+        assert_approx_eq!(red.cb, -0.168736, 0.0001);
+        assert_approx_eq!(red.cr, 0.5, 0.0001);
+        assert_eq!(red.quantize(YCbCrRange::Full, 8), [76, 85, 255]);
+        assert_eq!(red.quantize(YCbCrRange::Limited, 8), [81, 90, 240]);
+
+        let white = YCbCr::from(SRgba::WHITE);
+        let black = YCbCr::from(SRgba::BLACK);
+        assert_eq!(white.quantize(YCbCrRange::Limited, 8), [235, 128, 128]);
+        assert_eq!(black.quantize(YCbCrRange::Limited, 8), [16, 128, 128]);
+        assert_eq!(white.quantize(YCbCrRange::Limited, 10), [940, 512, 512]);
+        assert_eq!(black.quantize(YCbCrRange::Limited, 12), [256, 2048, 2048]);
+        assert_eq!(white.quantize(YCbCrRange::Full, 10), [1023, 512, 512]);
+
+        let blue = YCbCr::from_srgba(SRgba::BLUE, YCbCrMatrix::Bt2020);
+        assert_approx_eq!(blue.y, 0.0593, 0.0001);
+        assert_approx_eq!(blue.cb, 0.5, 0.0001);
+    }
+
+    #[test]
+    fn round_trip() {
+        for matrix in [YCbCrMatrix::Bt601, YCbCrMatrix::Bt709, YCbCrMatrix::Bt2020] {
+            for color in TEST_COLORS.iter() {
+                let back = YCbCr::from_srgba(color.rgb, matrix).to_srgba(matrix);
+                assert_approx_eq!(back.red, color.rgb.red, 0.0001);
+                assert_approx_eq!(back.green, color.rgb.green, 0.0001);
+                assert_approx_eq!(back.blue, color.rgb.blue, 0.0001);
+                assert_approx_eq!(back.alpha, color.rgb.alpha, 0.0001);
+            }
+        }
+    }
+
+    #[test]
+    fn quantization_round_trip() {
+        for range in [YCbCrRange::Full, YCbCrRange::Limited] {
+            for bit_depth in [8, 10, 12] {
+                let step = 1. / (1 << (bit_depth - 8)) as f32 / 200.;
+                for color in TEST_COLORS.iter() {
+                    let ycbcr = YCbCr::from(color.rgb);
+                    let codes = ycbcr.quantize(range, bit_depth);
+                    let back = YCbCr::from_quantized(codes, range, bit_depth);
+                    assert_approx_eq!(back.y, ycbcr.y, step);
+                    assert_approx_eq!(back.cb, ycbcr.cb.min(0.5), step);
+                    assert_approx_eq!(back.cr, ycbcr.cr.min(0.5), step);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn frames() {
+        // A 3 x 3 frame: the last column and row share chroma samples with no neighbor.
+        let colors = [
+            SRgba::RED,
+            SRgba::RED,
+            SRgba::GREEN,
+            SRgba::RED,
+            SRgba::RED,
+            SRgba::GREEN,
+            SRgba::BLUE,
+            SRgba::BLUE,
+            SRgba::WHITE,
+        ];
+        let format = YCbCrFormat::new(YCbCrMatrix::Bt709, YCbCrRange::Limited);
+        let codes = colors.map(|c| YCbCr::from_srgba(c, format.matrix).quantize(format.range, 8));
+        let y_plane = codes.map(|c| c[0] as u8);
+        let u_plane = [0, 2, 6, 8].map(|i| codes[i][1] as u8);
+        let v_plane = [0, 2, 6, 8].map(|i| codes[i][2] as u8);
+        let uv_plane: Vec<u8> = u_plane
+            .iter()
+            .zip(v_plane.iter())
+            .flat_map(|(u, v)| [*u, *v])
+            .collect();
+
+        let mut i420 = [SRgba::NONE; 9];
+        let mut nv12 = [SRgba::NONE; 9];
+        i420_to_srgba(&y_plane, &u_plane, &v_plane, 3, 3, format, &mut i420);
+        nv12_to_srgba(&y_plane, &uv_plane, 3, 3, format, &mut nv12);
+        assert_eq!(i420, nv12);
+        for (decoded, expected) in i420.iter().zip(colors.iter()) {
+            assert_approx_eq!(decoded.red, expected.red, 0.01);
+            assert_approx_eq!(decoded.green, expected.green, 0.01);
+            assert_approx_eq!(decoded.blue, expected.blue, 0.01);
+            assert_eq!(decoded.alpha, 1.);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chroma plane is too small")]
+    fn frame_too_small() {
+        let mut out = [SRgba::NONE; 4];
+        nv12_to_srgba(&[0; 4], &[128; 1], 2, 2, YCbCrFormat::default(), &mut out);
+    }
+}
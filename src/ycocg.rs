@@ -0,0 +1,287 @@
+use crate::{color_ops::impl_componentwise_ops, LinearRgba, Mix, SRgba, WithAlpha};
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Color in the YCoCg color encoding (luma, orange chroma, green chroma), with alpha.
+///
+/// YCoCg is a cheap linear transform of gamma-encoded [`SRgba`] which decorrelates the
+/// channels better than RGB, so it is used to preprocess textures for compression. The
+/// transform only needs additions and divisions by powers of two. For lossless integer
+/// data, use [`YCoCgR`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Serialize, Deserialize)]
+pub struct YCoCg {
+    pub y: f32,
+    pub co: f32,
+    pub cg: f32,
+    pub alpha: f32,
+}
+
+impl YCoCg {
+    /// Construct a new [`YCoCg`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - Luma channel. [0.0, 1.0]
+    /// * `co` - Orange chroma channel. [-0.5, 0.5]
+    /// * `cg` - Green chroma channel. [-0.5, 0.5]
+    /// * `alpha` - Alpha channel. [0.0, 1.0]
+    pub const fn new(y: f32, co: f32, cg: f32, alpha: f32) -> Self {
+        Self { y, co, cg, alpha }
+    }
+
+    /// Convert the YCoCg color to a tuple of components (y, co, cg, alpha).
+    #[inline]
+    pub const fn to_components(&self) -> (f32, f32, f32, f32) {
+        (self.y, self.co, self.cg, self.alpha)
+    }
+
+    /// Construct a new [`YCoCg`] color from a tuple of components (y, co, cg, alpha).
+    #[inline]
+    pub const fn from_components((y, co, cg, alpha): (f32, f32, f32, f32)) -> Self {
+        Self::new(y, co, cg, alpha)
+    }
+}
+
+impl Default for YCoCg {
+    fn default() -> Self {
+        Self::new(0., 0., 0., 1.)
+    }
+}
+
+impl Mix for YCoCg {
+    #[inline]
+    fn mix(&self, other: &Self, factor: f32) -> Self {
+        let n_factor = 1.0 - factor;
+        Self {
+            y: self.y * n_factor + other.y * factor,
+            co: self.co * n_factor + other.co * factor,
+            cg: self.cg * n_factor + other.cg * factor,
+            alpha: self.alpha * n_factor + other.alpha * factor,
+        }
+    }
+}
+
+impl WithAlpha for YCoCg {
+    #[inline]
+    fn with_alpha(&self, alpha: f32) -> Self {
+        Self { alpha, ..*self }
+    }
+}
+
+impl_componentwise_ops!(YCoCg, [y, co, cg, alpha]);
+
+impl From<SRgba> for YCoCg {
+    fn from(value: SRgba) -> Self {
+        let SRgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } = value;
+        YCoCg::new(
+            0.25 * red + 0.5 * green + 0.25 * blue,
+            0.5 * red - 0.5 * blue,
+            -0.25 * red + 0.5 * green - 0.25 * blue,
+            alpha,
+        )
+    }
+}
+
+impl From<YCoCg> for SRgba {
+    fn from(value: YCoCg) -> Self {
+        let YCoCg { y, co, cg, alpha } = value;
+        let tmp = y - cg;
+        SRgba::new(tmp + co, y + cg, tmp - co, alpha)
+    }
+}
+
+impl From<LinearRgba> for YCoCg {
+    fn from(value: LinearRgba) -> Self {
+        YCoCg::from(SRgba::from(value))
+    }
+}
+
+impl From<YCoCg> for LinearRgba {
+    fn from(value: YCoCg) -> Self {
+        LinearRgba::from(SRgba::from(value))
+    }
+}
+
+/// An 8-bit color in the reversible YCoCg-R encoding, with alpha.
+///
+/// YCoCg-R is the integer lifting form of [`YCoCg`] by Malvar and Sullivan. Converting 8-bit
+/// RGB to YCoCg-R and back is exact. The luma takes 8 bits and each chroma channel takes 9
+/// bits, with Co and Cg twice the scale of the float encoding. Alpha is passed through.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect(PartialEq, Hash, Serialize, Deserialize)]
+pub struct YCoCgR {
+    pub y: u8,
+    pub co: i16,
+    pub cg: i16,
+    pub alpha: u8,
+}
+
+impl YCoCgR {
+    /// Construct a new [`YCoCgR`] color from components.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - Luma channel. [0, 255]
+    /// * `co` - Orange chroma channel. [-255, 255]
+    /// * `cg` - Green chroma channel. [-255, 255]
+    /// * `alpha` - Alpha channel. [0, 255]
+    pub const fn new(y: u8, co: i16, cg: i16, alpha: u8) -> Self {
+        Self { y, co, cg, alpha }
+    }
+
+    /// Encode 8-bit RGBA channels.
+    pub const fn from_rgba8([red, green, blue, alpha]: [u8; 4]) -> Self {
+        let co = red as i16 - blue as i16;
+        let tmp = blue as i16 + (co >> 1);
+        let cg = green as i16 - tmp;
+        let y = tmp + (cg >> 1);
+        Self::new(y as u8, co, cg, alpha)
+    }
+
+    /// Decode into 8-bit RGBA channels. This is the exact inverse of
+    /// [`YCoCgR::from_rgba8`].
+    pub const fn to_rgba8(self) -> [u8; 4] {
+        let tmp = self.y as i16 - (self.cg >> 1);
+        let green = self.cg + tmp;
+        let blue = tmp - (self.co >> 1);
+        let red = blue + self.co;
+        [red as u8, green as u8, blue as u8, self.alpha]
+    }
+}
+
+impl From<SRgba> for YCoCgR {
+    fn from(value: SRgba) -> Self {
+        let [red, green, blue, alpha] = [value.red, value.green, value.blue, value.alpha]
+            .map(|channel| (channel * 255.).round().clamp(0., 255.) as u8);
+        YCoCgR::from_rgba8([red, green, blue, alpha])
+    }
+}
+
+impl From<YCoCgR> for SRgba {
+    fn from(value: YCoCgR) -> Self {
+        let [red, green, blue, alpha] = value.to_rgba8();
+        SRgba::rgba_u8(red, green, blue, alpha)
+    }
+}
+
+/// Convert a slice of sRGB colors to [`YCoCg`], writing into `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `colors`.
+pub fn srgba_to_ycocg(colors: &[SRgba], out: &mut [YCoCg]) {
+    assert!(out.len() >= colors.len(), "output buffer is too small");
+    for (color, out) in colors.iter().zip(out.iter_mut()) {
+        *out = YCoCg::from(*color);
+    }
+}
+
+/// Convert a slice of [`YCoCg`] colors to sRGB, writing into `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `colors`.
+pub fn ycocg_to_srgba(colors: &[YCoCg], out: &mut [SRgba]) {
+    assert!(out.len() >= colors.len(), "output buffer is too small");
+    for (color, out) in colors.iter().zip(out.iter_mut()) {
+        *out = SRgba::from(*color);
+    }
+}
+
+/// Encode a slice of 8-bit RGBA pixels to [`YCoCgR`], writing into `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `pixels`.
+pub fn rgba8_to_ycocg_r(pixels: &[[u8; 4]], out: &mut [YCoCgR]) {
+    assert!(out.len() >= pixels.len(), "output buffer is too small");
+    for (pixel, out) in pixels.iter().zip(out.iter_mut()) {
+        *out = YCoCgR::from_rgba8(*pixel);
+    }
+}
+
+/// Decode a slice of [`YCoCgR`] colors to 8-bit RGBA pixels, writing into `out`.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `colors`.
+pub fn ycocg_r_to_rgba8(colors: &[YCoCgR], out: &mut [[u8; 4]]) {
+    assert!(out.len() >= colors.len(), "output buffer is too small");
+    for (color, out) in colors.iter().zip(out.iter_mut()) {
+        *out = color.to_rgba8();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_colors::TEST_COLORS, testing::assert_approx_eq};
+
+    #[test]
+    fn reference_values() {
+        let white = YCoCg::from(SRgba::WHITE);
+        assert_eq!(white, YCoCg::new(1., 0., 0., 1.));
+        assert_eq!(YCoCg::from(SRgba::RED), YCoCg::new(0.25, 0.5, -0.25, 1.));
+        assert_eq!(YCoCg::from(SRgba::GREEN), YCoCg::new(0.5, 0., 0.5, 1.));
+
+        assert_eq!(
+            YCoCgR::from_rgba8([255, 0, 0, 255]),
+            YCoCgR::new(63, 255, -127, 255)
+        );
+        assert_eq!(YCoCgR::from(SRgba::WHITE), YCoCgR::new(255, 0, 0, 255));
+        assert_eq!(SRgba::from(YCoCgR::new(255, 0, 0, 255)), SRgba::WHITE);
+    }
+
+    #[test]
+    fn round_trip() {
+        for color in TEST_COLORS.iter() {
+            let back = SRgba::from(YCoCg::from(color.rgb));
+            assert_approx_eq!(back.red, color.rgb.red, 0.0001);
+            assert_approx_eq!(back.green, color.rgb.green, 0.0001);
+            assert_approx_eq!(back.blue, color.rgb.blue, 0.0001);
+            assert_approx_eq!(back.alpha, color.rgb.alpha, 0.0001);
+        }
+    }
+
+    #[test]
+    fn ycocg_r_is_lossless() {
+        for red in 0..=255u8 {
+            for green in 0..=255u8 {
+                for blue in 0..=255u8 {
+                    let pixel = [red, green, blue, green];
+                    let encoded = YCoCgR::from_rgba8(pixel);
+                    assert!((-255..=255).contains(&encoded.co));
+                    assert!((-255..=255).contains(&encoded.cg));
+                    assert_eq!(encoded.to_rgba8(), pixel);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slices() {
+        let colors: Vec<SRgba> = TEST_COLORS.iter().map(|color| color.rgb).collect();
+        let mut ycocg = vec![YCoCg::default(); colors.len()];
+        let mut back = vec![SRgba::NONE; colors.len()];
+        srgba_to_ycocg(&colors, &mut ycocg);
+        ycocg_to_srgba(&ycocg, &mut back);
+        for (color, back) in colors.iter().zip(back.iter()) {
+            assert_approx_eq!(back.red, color.red, 0.0001);
+            assert_approx_eq!(back.green, color.green, 0.0001);
+            assert_approx_eq!(back.blue, color.blue, 0.0001);
+        }
+
+        let pixels = [[0, 0, 0, 0], [12, 200, 255, 128], [255, 1, 77, 255]];
+        let mut encoded = [YCoCgR::default(); 3];
+        let mut decoded = [[0; 4]; 3];
+        rgba8_to_ycocg_r(&pixels, &mut encoded);
+        ycocg_r_to_rgba8(&encoded, &mut decoded);
+        assert_eq!(decoded, pixels);
+    }
+}